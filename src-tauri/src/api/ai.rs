@@ -1,11 +1,26 @@
 use serde::{Deserialize, Serialize};
-use crate::error::Result;
+use crate::api::rag::{
+    build_artifact_prompt, build_context_prompt, extract_citations, fetch_artifact,
+    related_artifacts, retrieve_artifacts, Citation, RetrievedArtifact, DEFAULT_CONTEXT_ARTIFACTS,
+    DEFAULT_RELATED_ARTIFACTS,
+};
+use crate::api::auth::AuthState;
+use crate::api::roles::{ensure_permission, Permission};
+use crate::db::DbState;
+use crate::error::{Error, Result};
+use crate::llm::context::{estimate_tokens, fit_history};
+use crate::llm::{
+    ChatMessage, CompletionRequest, LlmConfig, LlmConfigUpdate, LlmConfigView, LlmProvider,
+};
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::oneshot;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ChatMessage {
-    pub role: String,
-    pub content: String,
-}
+// 流式回复通过该事件推送到前端，payload 中带有 request_id
+pub const CHAT_STREAM_EVENT: &str = "ai-chat-stream";
+
+const SYSTEM_PROMPT: &str = "你是一个专业的文物博物馆AI助手。你的名字叫'文博助手'，专门帮助用户了解中国文物知识、历史背景、文化内涵等。请用专业、友好、易懂的方式回答用户的问题。如果遇到超出文物知识范围的问题，可以礼貌地表示并尝试引导用户回到文物相关话题。";
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ChatRequest {
@@ -13,123 +28,356 @@ pub struct ChatRequest {
     pub conversation_history: Vec<ChatMessage>,
 }
 
+/// 针对单件文物的预设任务
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactTask {
+    /// 用儿童能理解的语言讲解
+    ExplainForChildren,
+    /// 与相似藏品比较
+    CompareSimilar,
+    /// 概括历史背景
+    HistoricalContext,
+}
+
+impl ArtifactTask {
+    // 未填写问题时代替用户提问
+    fn question(self) -> &'static str {
+        match self {
+            ArtifactTask::ExplainForChildren => "请给小朋友讲讲这件文物。",
+            ArtifactTask::CompareSimilar => "这件文物和馆里相似的藏品有什么异同？",
+            ArtifactTask::HistoricalContext => "请概括这件文物的历史背景。",
+        }
+    }
+
+    // 追加到系统提示的回答要求
+    fn instruction(self) -> &'static str {
+        match self {
+            ArtifactTask::ExplainForChildren => {
+                "请用适合 8 到 12 岁儿童的浅显语言和生动比喻介绍这件文物，避免生僻术语，篇幅控制在 300 字以内。"
+            }
+            ArtifactTask::CompareSimilar => {
+                "请从年代、材质、形制和用途等方面，把这件文物与相关藏品中最相近的一到两件进行比较，说明异同并标注所比较藏品的编号；没有相关藏品时与同类文物的一般特点比较。"
+            }
+            ArtifactTask::HistoricalContext => {
+                "请概括这件文物所处时代的历史背景，以及它反映出的社会生活、礼制或工艺特点。"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ArtifactQuestionRequest {
+    pub artifact_id: i64,
+    /// 可与 task 同时提供；只提供 task 时使用预设问题
+    #[serde(default)]
+    pub question: Option<String>,
+    #[serde(default)]
+    pub task: Option<ArtifactTask>,
+    #[serde(default)]
+    pub conversation_history: Vec<ChatMessage>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ChatResponse {
     pub response: String,
+    /// 回答中引用的馆藏文物
+    #[serde(default)]
+    pub citations: Vec<Citation>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DeepSeekRequest {
-    pub model: String,
-    pub messages: Vec<ChatMessage>,
-    pub temperature: f32,
-    pub max_tokens: i32,
-    pub stream: bool,
+/// AI 助手状态：当前模型配置和进行中的流式生成（键为前端传入的 request_id）
+#[derive(Debug)]
+pub struct ChatState {
+    config: RwLock<LlmConfig>,
+    generations: Mutex<HashMap<String, oneshot::Sender<()>>>,
 }
 
-#[tauri::command]
-pub async fn chat_with_ai(request: ChatRequest) -> Result<ChatResponse> {
-    // 获取API密钥（这里使用环境变量，实际部署时需要配置）
-    let api_key = std::env::var("DEEPSEEK_API_KEY")
-        .unwrap_or_else(|_| "sk-your-deepseek-api-key".to_string());
-
-    // 如果是默认的API密钥，返回模拟响应
-    if api_key == "sk-your-deepseek-api-key" {
-        return simulate_ai_response(&request.message);
+impl ChatState {
+    pub fn new(config: LlmConfig) -> Self {
+        ChatState {
+            config: RwLock::new(config),
+            generations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn provider(&self) -> Result<Box<dyn LlmProvider>> {
+        self.config.read().unwrap().build_provider()
+    }
+
+    fn history_budget(&self, fixed_tokens: usize) -> usize {
+        self.config.read().unwrap().history_budget(fixed_tokens)
     }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatStreamPayload {
+    /// 增量文本
+    Delta { content: String },
+    /// 生成结束；cancelled 为 true 表示被用户取消
+    Done { cancelled: bool, citations: Vec<Citation> },
+    /// 生成失败，error 为 { code, message, details }
+    Error { error: serde_json::Value },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatStreamEvent {
+    pub request_id: String,
+    #[serde(flatten)]
+    pub payload: ChatStreamPayload,
+}
 
-    // 构建发送给DeepSeek的消息列表
-    let mut messages = vec![
-        ChatMessage {
-            role: "system".to_string(),
-            content: "你是一个专业的文物博物馆AI助手。你的名字叫'文博助手'，专门帮助用户了解中国文物知识、历史背景、文化内涵等。请用专业、友好、易懂的方式回答用户的问题。如果遇到超出文物知识范围的问题，可以礼貌地表示并尝试引导用户回到文物相关话题。".to_string(),
+// 检索与问题相关的馆藏文物；数据库不可用或检索失败时不影响对话
+async fn retrieve_context(db: &DbState, question: &str) -> Vec<RetrievedArtifact> {
+    let Ok(pool) = db.pool() else {
+        return Vec::new();
+    };
+
+    match retrieve_artifacts(&pool, question, DEFAULT_CONTEXT_ARTIFACTS).await {
+        Ok(artifacts) => artifacts,
+        Err(e) => {
+            eprintln!("检索馆藏资料失败: {}", e);
+            Vec::new()
         }
-    ];
-
-    // 添加对话历史
-    for msg in request.conversation_history {
-        messages.push(ChatMessage {
-            role: msg.role,
-            content: msg.content,
-        });
     }
+}
+
+/// 构建补全请求：检索馆藏资料并拼接系统提示，对话历史按上下文窗口从最早的消息开始裁剪
+pub(crate) async fn prepare_completion(
+    db: &DbState,
+    chat_state: &ChatState,
+    question: String,
+    history: Vec<ChatMessage>,
+) -> CompletionRequest {
+    let artifacts = retrieve_context(db, &question).await;
+
+    let system_prompt = match build_context_prompt(&artifacts) {
+        Some(context) => format!("{}\n\n{}", SYSTEM_PROMPT, context),
+        None => SYSTEM_PROMPT.to_string(),
+    };
+
+    assemble_completion(chat_state, system_prompt, question, history, artifacts)
+}
+
+// 拼接系统提示、裁剪后的历史和当前问题
+fn assemble_completion(
+    chat_state: &ChatState,
+    system_prompt: String,
+    question: String,
+    history: Vec<ChatMessage>,
+    artifacts: Vec<RetrievedArtifact>,
+) -> CompletionRequest {
+    let fixed_tokens = estimate_tokens(&system_prompt) + estimate_tokens(&question);
+    let history = fit_history(history, chat_state.history_budget(fixed_tokens));
+
+    let mut messages = vec![ChatMessage {
+        role: "system".to_string(),
+        content: system_prompt,
+    }];
+
+    messages.extend(history);
 
-    // 添加当前用户消息
     messages.push(ChatMessage {
         role: "user".to_string(),
-        content: request.message,
+        content: question.clone(),
     });
 
-    // 构建DeepSeek API请求
-    let deepseek_request = DeepSeekRequest {
-        model: "deepseek-chat".to_string(),
+    CompletionRequest {
         messages,
-        temperature: 0.7,
-        max_tokens: 2000,
-        stream: false,
-    };
-
-    // 发送HTTP请求到DeepSeek API
-    let client = reqwest::Client::new();
-    let response = client
-        .post("https://api.deepseek.com/chat/completions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&deepseek_request)
-        .send()
-        .await
-        .map_err(|e| crate::error::Error::Network(format!("Failed to send request to DeepSeek: {}", e)))?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(crate::error::Error::Network(format!(
-            "DeepSeek API error: {} - {}", 
-            status, 
-            error_text
-        )));
+        question,
+        artifacts,
     }
+}
+
+#[tauri::command]
+pub async fn chat_with_ai(
+    db: State<'_, DbState>,
+    chat_state: State<'_, ChatState>,
+    request: ChatRequest,
+) -> Result<ChatResponse> {
+    let provider = chat_state.provider()?;
 
-    let deepseek_response: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| crate::error::Error::Network(format!("Failed to parse DeepSeek response: {}", e)))?;
+    let completion =
+        prepare_completion(&db, &chat_state, request.message, request.conversation_history).await;
 
-    // 提取回复内容
-    let content = deepseek_response
-        .get("choices")
-        .and_then(|choices| choices.get(0))
-        .and_then(|choice| choice.get("message"))
-        .and_then(|message| message.get("content"))
-        .and_then(|content| content.as_str())
-        .unwrap_or("抱歉，我现在无法回复您的消息。");
+    let response = provider.complete(&completion).await?;
 
     Ok(ChatResponse {
-        response: content.to_string(),
+        citations: extract_citations(&response, &completion.artifacts),
+        response,
     })
 }
 
-// 模拟AI响应函数，用于测试
-fn simulate_ai_response(message: &str) -> Result<ChatResponse> {
-    let response = if message.contains("你好") || message.contains("hi") || message.contains("Hello") {
-        "您好！我是文博助手，很高兴为您服务！我可以帮您了解各种文物知识，比如青铜器、陶瓷、玉器、书画等。请问有什么可以帮助您的吗？"
-    } else if message.contains("青铜") {
-        "青铜器是中国古代文明的重要标志，始于夏商时期。著名的青铜器包括司母戊鼎、四羊方尊等。青铜器主要用于礼器、乐器和兵器，体现了古代中国高超的冶金技术和艺术水平。"
-    } else if message.contains("陶瓷") {
-        "中国陶瓷有着悠久的历史，从新石器时代的陶器到宋元明清的精美瓷器。著名的景德镇瓷器、唐三彩、龙泉青瓷等都是中国陶瓷艺术的瑰宝。每种陶瓷都有其独特的制作工艺和文化内涵。"
-    } else if message.contains("玉器") {
-        "玉在中国文化中象征着纯洁和美德。从新石器时代的红山文化、良渚文化玉器，到明清时期的精美玉雕，玉器一直是中华文化的重要组成部分。古人说'君子比德于玉'，体现了玉的文化意义。"
-    } else if message.contains("书画") {
-        "中国书画是独特的艺术形式，书法包括篆、隶、楷、行、草五种字体，绘画则有人物、山水、花鸟等题材。著名的书画家有王羲之、顾恺之、吴道子、张大千等，他们的作品代表了中国艺术的最高成就。"
-    } else if message.contains("博物馆") || message.contains("参观") {
-        "参观博物馆时，建议您：1)提前了解展览内容；2)安排充足时间；3)注意观察文物的细节；4)可以拍照但要遵守规定；5)多听讲解了解背后的故事。如果您想了解特定博物馆的信息，我可以为您详细介绍。"
-    } else if message.contains("帮助") || message.contains("help") {
-        "我可以帮助您：\n📚 介绍各类文物知识（青铜器、陶瓷、玉器、书画等）\n🏛️ 提供博物馆参观建议\n📖 讲解文物历史背景\n🎨 介绍艺术流派和代表作品\n🔍 回答文物相关问题\n\n请问您想了解哪个方面呢？"
-    } else {
-        "感谢您的提问！作为文博助手，我主要专注于文物知识、历史文化和博物馆相关内容。如果您有关于青铜器、陶瓷、玉器、书画等方面的问题，我很乐意为您详细解答。您可以试试问我'介绍一下青铜器'或'博物馆参观注意事项'等问题。"
+fn emit_stream_event(app: &AppHandle, request_id: &str, payload: ChatStreamPayload) {
+    let event = ChatStreamEvent {
+        request_id: request_id.to_string(),
+        payload,
     };
+    if let Err(e) = app.emit(CHAT_STREAM_EVENT, event) {
+        eprintln!("发送对话流事件失败: {}", e);
+    }
+}
 
-    Ok(ChatResponse {
-        response: response.to_string(),
+/// 流式生成并推送事件，返回完整回复以及是否被取消
+///
+/// 生成期间 request_id 登记在 ChatState 中，可通过 `cancel_chat` 取消。
+pub(crate) async fn run_stream(
+    app: &AppHandle,
+    chat_state: &ChatState,
+    request_id: &str,
+    provider: &dyn LlmProvider,
+    completion: &CompletionRequest,
+) -> Result<(ChatResponse, bool)> {
+    let (cancel_tx, mut cancel_rx) = oneshot::channel();
+    {
+        let mut generations = chat_state.generations.lock().unwrap();
+        if generations.contains_key(request_id) {
+            return Err(Error::Conflict(format!("请求 {} 正在生成中", request_id)));
+        }
+        generations.insert(request_id.to_string(), cancel_tx);
+    }
+
+    // 逐段推送增量文本并累积完整内容；取消时丢弃生成中的 future
+    let mut response = String::new();
+    let mut on_delta = |delta: String| {
+        response.push_str(&delta);
+        emit_stream_event(app, request_id, ChatStreamPayload::Delta { content: delta });
+    };
+    let (result, cancelled) = tokio::select! {
+        result = provider.stream(completion, &mut on_delta) => (result, false),
+        _ = &mut cancel_rx => (Ok(()), true),
+    };
+
+    chat_state.generations.lock().unwrap().remove(request_id);
+
+    match result {
+        Ok(()) => {
+            let citations = extract_citations(&response, &completion.artifacts);
+            emit_stream_event(
+                app,
+                request_id,
+                ChatStreamPayload::Done { cancelled, citations: citations.clone() },
+            );
+            Ok((ChatResponse { response, citations }, cancelled))
+        }
+        Err(e) => {
+            let error = serde_json::to_value(&e).unwrap_or_default();
+            emit_stream_event(app, request_id, ChatStreamPayload::Error { error });
+            Err(e)
+        }
+    }
+}
+
+/// 流式对话：增量文本通过 `ai-chat-stream` 事件推送，命令在生成结束后返回完整回复
+///
+/// `request_id` 由前端生成，用于区分事件归属以及调用 `cancel_chat` 取消生成。
+#[tauri::command]
+pub async fn chat_with_ai_stream(
+    app: AppHandle,
+    db: State<'_, DbState>,
+    chat_state: State<'_, ChatState>,
+    request_id: String,
+    request: ChatRequest,
+) -> Result<ChatResponse> {
+    let provider = chat_state.provider()?;
+
+    let completion =
+        prepare_completion(&db, &chat_state, request.message, request.conversation_history).await;
+
+    let (response, _) = run_stream(&app, &chat_state, &request_id, provider.as_ref(), &completion).await?;
+    Ok(response)
+}
+
+/// 针对单件文物提问，回复以流式事件推送
+///
+/// 系统提示包含该文物的完整信息和同朝代、同类别的相关藏品；`task` 为预设任务，可不填写问题。
+#[tauri::command]
+pub async fn ask_about_artifact(
+    app: AppHandle,
+    db: State<'_, DbState>,
+    chat_state: State<'_, ChatState>,
+    request_id: String,
+    request: ArtifactQuestionRequest,
+) -> Result<ChatResponse> {
+    let question = request
+        .question
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .map(str::to_string);
+    let question = match (question, request.task) {
+        (Some(question), _) => question,
+        (None, Some(task)) => task.question().to_string(),
+        (None, None) => return Err(Error::invalid_field("question", "请填写问题或选择预设任务")),
+    };
+
+    let pool = db.pool()?;
+    let provider = chat_state.provider()?;
+
+    let artifact = fetch_artifact(&pool, request.artifact_id).await?;
+    let related = related_artifacts(&pool, &artifact, DEFAULT_RELATED_ARTIFACTS).await?;
+
+    let mut system_prompt = format!("{}\n\n{}", SYSTEM_PROMPT, build_artifact_prompt(&artifact, &related));
+    if let Some(task) = request.task {
+        system_prompt.push_str(&format!("\n{}", task.instruction()));
+    }
+
+    // 当前文物排在第一位，模拟模式据此组织回答，引用也可以链接到相关藏品
+    let mut artifacts = vec![RetrievedArtifact::from(&artifact)];
+    artifacts.extend(related);
+
+    let completion = assemble_completion(
+        &chat_state,
+        system_prompt,
+        question,
+        request.conversation_history,
+        artifacts,
+    );
+
+    let (response, _) = run_stream(&app, &chat_state, &request_id, provider.as_ref(), &completion).await?;
+    Ok(response)
+}
+
+/// 取消进行中的流式生成，返回是否找到对应的请求
+#[tauri::command]
+pub async fn cancel_chat(chat_state: State<'_, ChatState>, request_id: String) -> Result<bool> {
+    let sender = chat_state.generations.lock().unwrap().remove(&request_id);
+    Ok(match sender {
+        Some(sender) => sender.send(()).is_ok(),
+        None => false,
     })
 }
+
+/// 获取当前模型配置（不返回密钥）
+#[tauri::command]
+pub async fn get_llm_config(chat_state: State<'_, ChatState>) -> Result<LlmConfigView> {
+    Ok(chat_state.config.read().unwrap().view())
+}
+
+/// 修改模型配置，立即生效并写入配置文件；需要用户管理权限
+#[tauri::command]
+pub async fn set_llm_config(
+    app: AppHandle,
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    chat_state: State<'_, ChatState>,
+    token: String,
+    config: LlmConfigUpdate,
+) -> Result<LlmConfigView> {
+    // 服务地址和密钥决定对话内容发往何处，只允许管理员修改
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::ManageUsers).await?;
+
+    let mut new_config = chat_state.config.read().unwrap().clone();
+    new_config.apply(config);
+    new_config.validate()?;
+    // 提前检查能否创建后端，避免保存无法使用的配置
+    new_config.build_provider()?;
+
+    new_config.save(&app)?;
+
+    let view = new_config.view();
+    *chat_state.config.write().unwrap() = new_config;
+    Ok(view)
+}
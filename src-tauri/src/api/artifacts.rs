@@ -1,52 +1,189 @@
-use crate::db::models::{Artifact, ArtifactWithFavorite};
-use crate::error::Result;
-use crate::api::auth::{validate_token, AuthState};
-use serde::Deserialize;
-use sqlx::{SqlitePool, Row};
+use crate::db::models::{Artifact, ArtifactDetail, ArtifactWithFavorite};
+use crate::db::DbState;
+use crate::error::{Error, Result};
+use crate::chronology::{Chronology, DateRange};
+use crate::geo::{resolve_coordinates, Coordinates};
+use crate::validation::Validator;
+use crate::vocabulary::{subtree_condition, TermValue, Vocabularies, Vocabulary};
+use crate::storage::MediaStore;
+use crate::api::auth::{optional_user_id, require_session, AuthState};
+use crate::api::roles::{ensure_permission, Permission};
+use crate::api::media::{list_media, refresh_primary_image};
+use crate::api::search::TextQuery;
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
 use tauri::State;
-use std::path::{Path, PathBuf};
-use anyhow::anyhow;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 
-// 验证图片文件是否存在
-fn validate_image_path(image_path: &str) -> bool {
-    let full_path = format!("../public/images/{}", image_path);
-    Path::new(&full_path).exists()
+/// 文物表的基础列（表别名须为 a），配合 row_to_artifact 使用
+pub(crate) const ARTIFACT_COLUMNS: &str = "a.id, a.title, a.image_path, a.period, a.dynasty, a.location, \
+     a.description, a.detailed_description, a.material, a.dimensions, \
+     a.discovery_location, a.collection, a.category, a.latitude, a.longitude, \
+     a.location_uncertainty_m, a.date_from, a.date_to, a.created_at, a.updated_at";
+
+/// 主图文件是否缺失，与文物列一起查询（表别名须为 a）
+pub(crate) const IMAGE_MISSING_COLUMN: &str = "COALESCE((SELECT m.file_missing FROM artifact_media m \
+     WHERE m.artifact_id = a.id AND m.media_type = 'image' AND m.is_primary = 1), 0) AS image_missing";
+
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct SearchParams {
+    pub query: Option<String>,
+    pub category: Option<String>,
+    pub dynasty: Option<String>,
+    pub material: Option<String>,
+    pub collection: Option<String>,
+    /// 年份范围（公元前为负数），返回年代与之有交集的文物；没有年份的文物不参与
+    pub year_from: Option<i64>,
+    pub year_to: Option<i64>,
+    pub favorites_only: Option<bool>,
+    /// 每页条数，默认 50，最大 200
+    pub limit: Option<i64>,
+    /// 上一页返回的 next_cursor
+    pub cursor: Option<String>,
+    /// 未提供 cursor 时可按偏移量翻页
+    pub offset: Option<i64>,
+    pub sort: Option<SortField>,
+    pub order: Option<SortOrder>,
 }
 
-// 获取默认图片路径
-fn get_default_image_path() -> &'static str {
-    "bronze_ding.jpg" // 使用一个确定存在的图片作为默认
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortField {
+    Title,
+    /// 按年代先后排序（date_from）
+    Dynasty,
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    /// 按收藏数和浏览量排序
+    Popularity,
 }
 
-/// 校验管理员身份（通过邮箱）
-async fn ensure_admin(
-    pool: &SqlitePool,
-    auth_state: &AuthState,
-    token: &str,
-) -> Result<i64> {
-    let session = validate_token(&auth_state.sessions, token)
-        .ok_or_else(|| anyhow!("用户未登录"))?;
-    let user_id = session.user_id;
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
 
-    let row = sqlx::query("SELECT email FROM users WHERE id = ?")
-        .bind(user_id)
-        .fetch_one(pool)
-        .await?;
+/// 分页结果
+#[derive(Debug, Serialize)]
+pub struct ArtifactPage {
+    pub items: Vec<ArtifactWithFavorite>,
+    pub total: i64,
+    pub next_cursor: Option<String>,
+}
 
-    let email: String = row.get("email");
-    if email != "yi@example.com" {
-        return Err(anyhow!("无权限，需管理员账号").into());
+// 游标中保存的排序键
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum SortKey {
+    Int(i64),
+    Text(String),
+}
+
+// 游标内容：排序方式 + 上一页最后一条的排序键和ID
+#[derive(Debug, Serialize, Deserialize)]
+struct PageCursor {
+    sort: SortField,
+    order: SortOrder,
+    key: SortKey,
+    id: i64,
+}
+
+impl PageCursor {
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
     }
 
-    Ok(user_id)
+    fn decode(cursor: &str) -> Result<Self> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(cursor)
+            .map_err(|_| Error::invalid_field("cursor", "无效的分页游标"))?;
+        serde_json::from_slice(&bytes).map_err(|_| Error::invalid_field("cursor", "无效的分页游标"))
+    }
 }
 
-#[derive(Debug, Deserialize, Default)]
-pub struct SearchParams {
-    pub query: Option<String>,
-    pub category: Option<String>,
-    pub dynasty: Option<String>,
-    pub favorites_only: Option<bool>,
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 200;
+
+// 没有年份的文物排在最后
+const UNDATED_SORT_YEAR: i64 = 9999;
+
+impl SortField {
+    /// 排序键的 SQL 表达式
+    pub(crate) fn sql_expr(&self) -> String {
+        match self {
+            SortField::Title => "a.title".to_string(),
+            SortField::Dynasty => format!("COALESCE(a.date_from, {})", UNDATED_SORT_YEAR),
+            SortField::CreatedAt => "a.created_at".to_string(),
+            SortField::UpdatedAt => "a.updated_at".to_string(),
+            SortField::Popularity => r#"(
+                (SELECT COUNT(*) FROM user_favorites f WHERE f.artifact_id = a.id) * 3
+                + (SELECT COUNT(*) FROM browsing_history h WHERE h.artifact_id = a.id)
+            )"#
+            .to_string(),
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, SortField::Dynasty | SortField::Popularity)
+    }
+}
+
+/// 根据搜索参数生成过滤条件（查询需包含 artifacts a 和 user_favorites uf 两个别名）
+pub(crate) fn push_filter_conditions(
+    params: &SearchParams,
+    conditions: &mut Vec<String>,
+    bind_values: &mut Vec<String>,
+) {
+    // 关键词走全文索引
+    if let Some(text_query) = params.query.as_deref().and_then(TextQuery::parse) {
+        text_query.push_conditions(conditions, bind_values);
+    }
+
+    if let Some(category) = &params.category {
+        if category != "all" {
+            conditions.push(subtree_condition(Vocabulary::Category));
+            bind_values.push(category.clone());
+        }
+    }
+
+    if let Some(dynasty) = &params.dynasty {
+        if !dynasty.is_empty() {
+            conditions.push("a.dynasty = ?".to_string());
+            bind_values.push(dynasty.clone());
+        }
+    }
+
+    if let Some(material) = &params.material {
+        if !material.is_empty() {
+            conditions.push(subtree_condition(Vocabulary::Material));
+            bind_values.push(material.clone());
+        }
+    }
+
+    if let Some(collection) = &params.collection {
+        if !collection.is_empty() {
+            conditions.push(subtree_condition(Vocabulary::Collection));
+            bind_values.push(collection.clone());
+        }
+    }
+
+    if let Some(year_from) = params.year_from {
+        conditions.push("a.date_to >= ?".to_string());
+        bind_values.push(year_from.to_string());
+    }
+
+    if let Some(year_to) = params.year_to {
+        conditions.push("a.date_from <= ?".to_string());
+        bind_values.push(year_to.to_string());
+    }
+
+    if params.favorites_only == Some(true) {
+        conditions.push("uf.id IS NOT NULL".to_string());
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -64,85 +201,206 @@ pub struct ArtifactInput {
     pub discovery_location: String,
     pub collection: String,
     pub category: String,
+    /// 经纬度需同时提供；均为空时按出土地点或所在地的地名推断
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    #[serde(default)]
+    pub location_uncertainty_m: Option<f64>,
+    /// 起止年份需同时提供；均为空时按朝代和年代推断
+    #[serde(default)]
+    pub date_from: Option<i64>,
+    #[serde(default)]
+    pub date_to: Option<i64>,
+}
+
+impl ArtifactInput {
+    fn coordinates(&self) -> Result<Option<Coordinates>> {
+        resolve_coordinates(
+            self.latitude,
+            self.longitude,
+            self.location_uncertainty_m,
+            &self.discovery_location,
+            &self.location,
+        )
+    }
+
+    fn dates(&self, chronology: &Chronology) -> Result<Option<DateRange>> {
+        chronology.resolve(self.date_from, self.date_to, &self.dynasty, &self.period)
+    }
+
+    /// 校验全部字段，有错误时一并返回各字段的错误
+    async fn validate(&self, pool: &SqlitePool) -> Result<ValidatedArtifact> {
+        let vocabularies = Vocabularies::load(pool).await?;
+        let chronology = Chronology::load(pool).await?;
+
+        let mut validator = Validator::new();
+        for (field, value) in [
+            ("title", &self.title),
+            ("period", &self.period),
+            ("dynasty", &self.dynasty),
+            ("location", &self.location),
+            ("description", &self.description),
+            ("detailed_description", &self.detailed_description),
+            ("discovery_location", &self.discovery_location),
+        ] {
+            validator.text(field, value);
+        }
+        let image_path = validator.image_path(&self.image_path);
+        validator.dimensions(&self.dimensions);
+
+        let mut term = |vocabulary: Vocabulary, value: &str| {
+            validator
+                .check(vocabularies.resolve(vocabulary, value))
+                .map(Option::unwrap_or_default)
+        };
+        let category = term(Vocabulary::Category, &self.category)?;
+        let material = term(Vocabulary::Material, &self.material)?;
+        let collection = term(Vocabulary::Collection, &self.collection)?;
+        let coordinates = validator.check(self.coordinates())?.flatten();
+        let dates = validator.check(self.dates(&chronology))?.flatten();
+        validator.finish()?;
+
+        Ok(ValidatedArtifact {
+            image_path,
+            category,
+            material,
+            collection,
+            coordinates,
+            dates,
+        })
+    }
+}
+
+/// 校验和整理后写入数据库的字段
+struct ValidatedArtifact {
+    image_path: String,
+    category: TermValue,
+    material: TermValue,
+    collection: TermValue,
+    coordinates: Option<Coordinates>,
+    dates: Option<DateRange>,
+}
+
+async fn artifact_exists(pool: &SqlitePool, id: i64) -> Result<bool> {
+    let row = sqlx::query("SELECT 1 FROM artifacts WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.is_some())
+}
+
+/// 将查询结果行映射为 ArtifactWithFavorite（要求包含 is_favorite 列）
+pub(crate) fn row_to_artifact(row: &SqliteRow) -> ArtifactWithFavorite {
+    ArtifactWithFavorite {
+        id: row.get("id"),
+        title: row.get("title"),
+        image_path: row.get("image_path"),
+        period: row.get("period"),
+        dynasty: row.get("dynasty"),
+        location: row.get("location"),
+        description: row.get("description"),
+        detailed_description: row.get("detailed_description"),
+        material: row.get("material"),
+        dimensions: row.get("dimensions"),
+        discovery_location: row.get("discovery_location"),
+        collection: row.get("collection"),
+        category: row.get("category"),
+        latitude: row.get("latitude"),
+        longitude: row.get("longitude"),
+        location_uncertainty_m: row.get("location_uncertainty_m"),
+        date_from: row.get("date_from"),
+        date_to: row.get("date_to"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+        is_favorite: row.get("is_favorite"),
+        image_missing: row.get("image_missing"),
+    }
 }
 
 #[tauri::command]
 pub async fn get_artifacts(
-    pool: State<'_, SqlitePool>,
+    db: State<'_, DbState>,
     auth_state: State<'_, crate::api::auth::AuthState>,
     params: Option<SearchParams>,
     token: Option<String>,
-) -> Result<Vec<ArtifactWithFavorite>> {
+) -> Result<ArtifactPage> {
+    let pool = db.pool()?;
     let params = params.unwrap_or_default();
     
-    // 获取用户ID，如果没有token则使用默认值1（临时解决方案）
-    let user_id = if let Some(token_str) = token {
-        if let Some(session) = validate_token(&auth_state.sessions, &token_str) {
-            session.user_id
-        } else {
-            1 // 默认用户ID
-        }
+    // 未登录时 user_id 为 NULL，不匹配任何收藏
+    let user_id = optional_user_id(&pool, &auth_state, token.as_deref()).await?;
+
+    let sort = params.sort.unwrap_or_default();
+    let order = params.order.unwrap_or_default();
+    let limit = params.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let sort_expr = sort.sql_expr();
+    
+    let mut conditions = Vec::new();
+    let mut bind_values: Vec<String> = Vec::new();
+    push_filter_conditions(&params, &mut conditions, &mut bind_values);
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
     } else {
-        1 // 默认用户ID
+        format!(" WHERE {}", conditions.join(" AND "))
     };
 
-    println!("DEBUG: get_artifacts called with params: {:?}", params);
-    println!("DEBUG: user_id: {}, favorites_only: {:?}", user_id, params.favorites_only);
-    
-    let mut query = "
-        SELECT 
-            a.id, a.title, a.image_path, a.period, a.dynasty, a.location,
-            a.description, a.detailed_description, a.material, a.dimensions,
-            a.discovery_location, a.collection, a.category, a.created_at, a.updated_at,
-            CASE WHEN uf.id IS NOT NULL THEN 1 ELSE 0 END as is_favorite
+    // 总数（不受分页影响）
+    let count_query = format!(
+        r#"
+        SELECT COUNT(*) as total
         FROM artifacts a
         LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
-    ".to_string();
-    
-    let mut conditions = Vec::new();
-    let mut bind_values: Vec<String> = Vec::new();
-    
-    if let Some(query_str) = &params.query {
-        if !query_str.is_empty() {
-            conditions.push("(a.title LIKE ? OR a.description LIKE ? OR a.detailed_description LIKE ?)");
-            bind_values.push(format!("%{}%", query_str));
-            bind_values.push(format!("%{}%", query_str));
-            bind_values.push(format!("%{}%", query_str));
-        }
-    }
-    
-    if let Some(category) = &params.category {
-        if category != "all" {
-            conditions.push("a.category = ?");
-            bind_values.push(category.clone());
-        }
-    }
-    
-    if let Some(dynasty) = &params.dynasty {
-        if !dynasty.is_empty() {
-            conditions.push("a.dynasty = ?");
-            bind_values.push(dynasty.clone());
-        }
+        {}
+        "#,
+        where_clause
+    );
+    let mut count_sql = sqlx::query(&count_query).bind(user_id);
+    for value in &bind_values {
+        count_sql = count_sql.bind(value);
     }
-    
-    if let Some(favorites_only) = params.favorites_only {
-        if favorites_only {
-            println!("DEBUG: Adding favorites filter condition");
-            conditions.push("uf.id IS NOT NULL");
+    let total: i64 = count_sql.fetch_one(&pool).await?.get("total");
+
+    // 游标条件：排在上一页最后一条之后
+    let cursor = params.cursor.as_deref().map(PageCursor::decode).transpose()?;
+    if let Some(cursor) = &cursor {
+        if cursor.sort != sort || cursor.order != order {
+            return Err(Error::invalid_field("cursor", "分页游标与排序方式不一致"));
         }
-    } else {
-        println!("DEBUG: favorites_only is None or false");
+        let op = if order == SortOrder::Asc { ">" } else { "<" };
+        conditions.push(format!(
+            "({expr} {op} ? OR ({expr} = ? AND a.id {op} ?))",
+            expr = sort_expr,
+            op = op
+        ));
     }
+
+    let direction = if order == SortOrder::Asc { "ASC" } else { "DESC" };
+    let mut query = format!(
+        r#"
+        SELECT 
+            {},
+            CASE WHEN uf.id IS NOT NULL THEN 1 ELSE 0 END as is_favorite,
+            {},
+            {} as sort_key
+        FROM artifacts a
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        "#,
+        ARTIFACT_COLUMNS,
+        IMAGE_MISSING_COLUMN, sort_expr
+    );
     
     if !conditions.is_empty() {
         query.push_str(" WHERE ");
         query.push_str(&conditions.join(" AND "));
     }
     
-    query.push_str(" ORDER BY a.created_at DESC");
-    
-    println!("DEBUG: Final SQL query: {}", query);
-    println!("DEBUG: Conditions: {:?}", conditions);
+    query.push_str(&format!(" ORDER BY sort_key {dir}, a.id {dir} LIMIT ?", dir = direction));
+    if cursor.is_none() {
+        query.push_str(" OFFSET ?");
+    }
     
     // 构建动态查询
     let mut sql_query = sqlx::query(&query).bind(user_id);
@@ -150,88 +408,87 @@ pub async fn get_artifacts(
     for value in bind_values {
         sql_query = sql_query.bind(value);
     }
+
+    if let Some(cursor) = &cursor {
+        sql_query = match &cursor.key {
+            SortKey::Int(key) => sql_query.bind(*key).bind(*key),
+            SortKey::Text(key) => sql_query.bind(key.clone()).bind(key.clone()),
+        };
+        sql_query = sql_query.bind(cursor.id);
+    }
+
+    // 多取一条用于判断是否还有下一页
+    sql_query = sql_query.bind(limit + 1);
+    if cursor.is_none() {
+        sql_query = sql_query.bind(params.offset.unwrap_or(0).max(0));
+    }
     
-    let rows = sql_query.fetch_all(&*pool).await?;
+    let mut rows = sql_query.fetch_all(&pool).await?;
+
+    let has_more = rows.len() as i64 > limit;
+    rows.truncate(limit as usize);
+
+    let next_cursor = match rows.last() {
+        Some(row) if has_more => {
+            let key = if sort.is_numeric() {
+                SortKey::Int(row.get("sort_key"))
+            } else {
+                SortKey::Text(row.get("sort_key"))
+            };
+            Some(PageCursor { sort, order, key, id: row.get("id") }.encode())
+        }
+        _ => None,
+    };
     
     // 手动映射结果到结构体
-    let artifacts = rows.into_iter().map(|row| {
-        let mut image_path: String = row.get("image_path");
-        
-        // 验证图片文件是否存在，如果不存在则使用默认图片
-        if !validate_image_path(&image_path) {
-            println!("警告: 图片文件不存在: {}, 使用默认图片", image_path);
-            image_path = get_default_image_path().to_string();
-        }
-        
-        Ok(ArtifactWithFavorite {
-            id: row.get("id"),
-            title: row.get("title"),
-            image_path,
-            period: row.get("period"),
-            dynasty: row.get("dynasty"),
-            location: row.get("location"),
-            description: row.get("description"),
-            detailed_description: row.get("detailed_description"),
-            material: row.get("material"),
-            dimensions: row.get("dimensions"),
-            discovery_location: row.get("discovery_location"),
-            collection: row.get("collection"),
-            category: row.get("category"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-            is_favorite: row.get("is_favorite"),
-        })
-    }).collect::<Result<Vec<ArtifactWithFavorite>>>()?;
+    let items = rows.iter().map(row_to_artifact).collect();
     
-    Ok(artifacts)
+    Ok(ArtifactPage {
+        items,
+        total,
+        next_cursor,
+    })
 }
 
+/// 文物详情，包含全部图片及其他媒体
 #[tauri::command]
 pub async fn get_artifact_by_id(
-    pool: State<'_, SqlitePool>,
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
     id: i64,
-) -> Result<Option<ArtifactWithFavorite>> {
-    // TODO: Add user_id from session
-    let user_id = 1;
+    token: Option<String>,
+) -> Result<Option<ArtifactDetail>> {
+    let pool = db.pool()?;
+    let user_id = optional_user_id(&pool, &auth_state, token.as_deref()).await?;
     
-    let query = r#"
+    let query = format!(
+        r#"
         SELECT 
-            a.id, a.title, a.image_path, a.period, a.dynasty, a.location,
-            a.description, a.detailed_description, a.material, a.dimensions,
-            a.discovery_location, a.collection, a.category, a.created_at, a.updated_at,
-            CASE WHEN uf.id IS NOT NULL THEN 1 ELSE 0 END as is_favorite
+            {},
+            CASE WHEN uf.id IS NOT NULL THEN 1 ELSE 0 END as is_favorite,
+            {}
         FROM artifacts a
         LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
         WHERE a.id = ?
-    "#;
+        "#,
+        ARTIFACT_COLUMNS,
+        IMAGE_MISSING_COLUMN
+    );
     
-    let row = sqlx::query(query)
+    let row = sqlx::query(&query)
         .bind(user_id)
         .bind(id)
-        .fetch_optional(&*pool)
+        .fetch_optional(&pool)
         .await?;
     
-    let artifact = if let Some(row) = row {
-        Some(ArtifactWithFavorite {
-            id: row.get("id"),
-            title: row.get("title"),
-            image_path: row.get("image_path"),
-            period: row.get("period"),
-            dynasty: row.get("dynasty"),
-            location: row.get("location"),
-            description: row.get("description"),
-            detailed_description: row.get("detailed_description"),
-            material: row.get("material"),
-            dimensions: row.get("dimensions"),
-            discovery_location: row.get("discovery_location"),
-            collection: row.get("collection"),
-            category: row.get("category"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-            is_favorite: row.get("is_favorite"),
-        })
-    } else {
-        None
+    let artifact = match row {
+        Some(row) => {
+            Some(ArtifactDetail {
+                artifact: row_to_artifact(&row),
+                media: list_media(&pool, id).await?,
+            })
+        }
+        None => None,
     };
     
     Ok(artifact)
@@ -239,14 +496,14 @@ pub async fn get_artifact_by_id(
 
 #[tauri::command]
 pub async fn toggle_favorite(
-    pool: State<'_, SqlitePool>,
+    db: State<'_, DbState>,
     auth_state: State<'_, crate::api::auth::AuthState>,
     artifact_id: i64,
     token: String,
 ) -> Result<bool> {
+    let pool = db.pool()?;
     // 验证token并获取用户ID
-    let session = validate_token(&auth_state.sessions, &token)
-        .ok_or_else(|| anyhow::anyhow!("用户未登录"))?;
+    let session = require_session(&pool, &auth_state, &token).await?;
     let user_id = session.user_id;
     
     // Check if already favorited
@@ -254,7 +511,7 @@ pub async fn toggle_favorite(
     let existing = sqlx::query(query)
         .bind(user_id)
         .bind(artifact_id)
-        .fetch_optional(&*pool)
+        .fetch_optional(&pool)
         .await?;
     
     if existing.is_some() {
@@ -262,7 +519,7 @@ pub async fn toggle_favorite(
         sqlx::query("DELETE FROM user_favorites WHERE user_id = ? AND artifact_id = ?")
             .bind(user_id)
             .bind(artifact_id)
-            .execute(&*pool)
+            .execute(&pool)
             .await?;
         Ok(false)
     } else {
@@ -270,7 +527,7 @@ pub async fn toggle_favorite(
         sqlx::query("INSERT INTO user_favorites (user_id, artifact_id) VALUES (?, ?)")
             .bind(user_id)
             .bind(artifact_id)
-            .execute(&*pool)
+            .execute(&pool)
             .await?;
         Ok(true)
     }
@@ -279,91 +536,91 @@ pub async fn toggle_favorite(
 // 创建文物
 #[tauri::command]
 pub async fn create_artifact(
-    pool: State<'_, SqlitePool>,
+    db: State<'_, DbState>,
     auth_state: State<'_, AuthState>,
+    store: State<'_, MediaStore>,
     artifact: ArtifactInput,
     token: String,
 ) -> Result<ArtifactWithFavorite> {
-    // 仅管理员可操作
-    ensure_admin(&pool, &auth_state, &token).await?;
+    let pool = db.pool()?;
+    // 需要相应的文物管理权限
+    let user_id = ensure_permission(&pool, &auth_state, &token, Permission::CreateArtifact).await?;
 
     let insert_query = r#"
         INSERT INTO artifacts (
             title, image_path, period, dynasty, location, description,
             detailed_description, material, dimensions, discovery_location,
-            collection, category
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            collection, category, latitude, longitude, location_uncertainty_m,
+            date_from, date_to, category_id, material_id, collection_id
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
     "#;
+    let valid = artifact.validate(&pool).await?;
     
     // 先插入数据
     let result = sqlx::query(insert_query)
         .bind(&artifact.title)
-        .bind(&artifact.image_path)
+        .bind(&valid.image_path)
         .bind(&artifact.period)
         .bind(&artifact.dynasty)
         .bind(&artifact.location)
         .bind(&artifact.description)
         .bind(&artifact.detailed_description)
-        .bind(&artifact.material)
+        .bind(&valid.material.code)
         .bind(&artifact.dimensions)
         .bind(&artifact.discovery_location)
-        .bind(&artifact.collection)
-        .bind(&artifact.category)
-        .execute(&*pool)
+        .bind(&valid.collection.code)
+        .bind(&valid.category.code)
+        .bind(valid.coordinates.map(|c| c.point.latitude))
+        .bind(valid.coordinates.map(|c| c.point.longitude))
+        .bind(valid.coordinates.and_then(|c| c.uncertainty_m))
+        .bind(valid.dates.map(|d| d.from))
+        .bind(valid.dates.map(|d| d.to))
+        .bind(valid.category.id)
+        .bind(valid.material.id)
+        .bind(valid.collection.id)
+        .execute(&pool)
         .await?;
     
     // 获取最后插入的ID
     let id = result.last_insert_rowid();
+    refresh_primary_image(&mut *pool.acquire().await?, &store, id).await?;
     
     // 返回新创建的文物（包含收藏状态）
-    let user_id = 1; // TODO: 从会话获取
-    let query = r#"
+    let query = format!(
+        r#"
         SELECT 
-            a.id, a.title, a.image_path, a.period, a.dynasty, a.location,
-            a.description, a.detailed_description, a.material, a.dimensions,
-            a.discovery_location, a.collection, a.category, a.created_at, a.updated_at,
-            CASE WHEN uf.id IS NOT NULL THEN 1 ELSE 0 END as is_favorite
+            {},
+            CASE WHEN uf.id IS NOT NULL THEN 1 ELSE 0 END as is_favorite,
+            {}
         FROM artifacts a
         LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
         WHERE a.id = ?
-    "#;
+        "#,
+        ARTIFACT_COLUMNS,
+        IMAGE_MISSING_COLUMN
+    );
     
-    let row = sqlx::query(query)
+    let row = sqlx::query(&query)
         .bind(user_id)
         .bind(id)
-        .fetch_one(&*pool)
+        .fetch_one(&pool)
         .await?;
     
-    Ok(ArtifactWithFavorite {
-        id: row.get("id"),
-        title: row.get("title"),
-        image_path: row.get("image_path"),
-        period: row.get("period"),
-        dynasty: row.get("dynasty"),
-        location: row.get("location"),
-        description: row.get("description"),
-        detailed_description: row.get("detailed_description"),
-        material: row.get("material"),
-        dimensions: row.get("dimensions"),
-        discovery_location: row.get("discovery_location"),
-        collection: row.get("collection"),
-        category: row.get("category"),
-        created_at: row.get("created_at"),
-        updated_at: row.get("updated_at"),
-        is_favorite: row.get("is_favorite"),
-    })
+    Ok(row_to_artifact(&row))
 }
 
 // 更新文物
 #[tauri::command]
 pub async fn update_artifact(
-    pool: State<'_, SqlitePool>,
+    db: State<'_, DbState>,
     auth_state: State<'_, AuthState>,
+    store: State<'_, MediaStore>,
     artifact: ArtifactInput,
     token: String,
 ) -> Result<ArtifactWithFavorite> {
-    // 仅管理员可操作
-    ensure_admin(&pool, &auth_state, &token).await?;
+    let pool = db.pool()?;
+    // 需要相应的文物管理权限
+    ensure_permission(&pool, &auth_state, &token, Permission::UpdateArtifact).await?;
 
     let update_query = r#"
         UPDATE artifacts SET
@@ -379,79 +636,90 @@ pub async fn update_artifact(
             discovery_location = ?,
             collection = ?,
             category = ?,
+            latitude = ?,
+            longitude = ?,
+            location_uncertainty_m = ?,
+            date_from = ?,
+            date_to = ?,
+            category_id = ?,
+            material_id = ?,
+            collection_id = ?,
             updated_at = CURRENT_TIMESTAMP
         WHERE id = ?
     "#;
 
-    let artifact_id = artifact.id.ok_or_else(|| anyhow!("缺少文物ID"))?;
+    let artifact_id = artifact.id.ok_or_else(|| Error::invalid_field("id", "缺少文物ID"))?;
+    if !artifact_exists(&pool, artifact_id).await? {
+        return Err(Error::NotFound(format!("文物不存在: {}", artifact_id)));
+    }
+    let valid = artifact.validate(&pool).await?;
 
     sqlx::query(update_query)
         .bind(&artifact.title)
-        .bind(&artifact.image_path)
+        .bind(&valid.image_path)
         .bind(&artifact.period)
         .bind(&artifact.dynasty)
         .bind(&artifact.location)
         .bind(&artifact.description)
         .bind(&artifact.detailed_description)
-        .bind(&artifact.material)
+        .bind(&valid.material.code)
         .bind(&artifact.dimensions)
         .bind(&artifact.discovery_location)
-        .bind(&artifact.collection)
-        .bind(&artifact.category)
+        .bind(&valid.collection.code)
+        .bind(&valid.category.code)
+        .bind(valid.coordinates.map(|c| c.point.latitude))
+        .bind(valid.coordinates.map(|c| c.point.longitude))
+        .bind(valid.coordinates.and_then(|c| c.uncertainty_m))
+        .bind(valid.dates.map(|d| d.from))
+        .bind(valid.dates.map(|d| d.to))
+        .bind(valid.category.id)
+        .bind(valid.material.id)
+        .bind(valid.collection.id)
         .bind(artifact_id)
-        .execute(&*pool)
+        .execute(&pool)
         .await?;
+    refresh_primary_image(&mut *pool.acquire().await?, &store, artifact_id).await?;
 
-    let query = r#"
+    let query = format!(
+        r#"
         SELECT 
-            a.id, a.title, a.image_path, a.period, a.dynasty, a.location,
-            a.description, a.detailed_description, a.material, a.dimensions,
-            a.discovery_location, a.collection, a.category, a.created_at, a.updated_at,
-            0 as is_favorite
+            {},
+            0 as is_favorite,
+            {}
         FROM artifacts a
         WHERE a.id = ?
-    "#;
+        "#,
+        ARTIFACT_COLUMNS,
+        IMAGE_MISSING_COLUMN
+    );
 
-    let row = sqlx::query(query)
+    let row = sqlx::query(&query)
         .bind(artifact_id)
-        .fetch_one(&*pool)
+        .fetch_one(&pool)
         .await?;
 
-    Ok(ArtifactWithFavorite {
-        id: row.get("id"),
-        title: row.get("title"),
-        image_path: row.get("image_path"),
-        period: row.get("period"),
-        dynasty: row.get("dynasty"),
-        location: row.get("location"),
-        description: row.get("description"),
-        detailed_description: row.get("detailed_description"),
-        material: row.get("material"),
-        dimensions: row.get("dimensions"),
-        discovery_location: row.get("discovery_location"),
-        collection: row.get("collection"),
-        category: row.get("category"),
-        created_at: row.get("created_at"),
-        updated_at: row.get("updated_at"),
-        is_favorite: row.get("is_favorite"),
-    })
+    Ok(row_to_artifact(&row))
 }
 
 // 删除文物
 #[tauri::command]
 pub async fn delete_artifact(
-    pool: State<'_, SqlitePool>,
+    db: State<'_, DbState>,
     auth_state: State<'_, AuthState>,
     id: i64,
     token: String,
 ) -> Result<bool> {
-    // 仅管理员可操作
-    ensure_admin(&pool, &auth_state, &token).await?;
+    let pool = db.pool()?;
+    // 需要相应的文物管理权限
+    ensure_permission(&pool, &auth_state, &token, Permission::DeleteArtifact).await?;
 
-    sqlx::query("DELETE FROM artifacts WHERE id = ?")
+    let result = sqlx::query("DELETE FROM artifacts WHERE id = ?")
         .bind(id)
-        .execute(&*pool)
+        .execute(&pool)
         .await?;
+    if result.rows_affected() == 0 {
+        return Err(Error::NotFound(format!("文物不存在: {}", id)));
+    }
 
     Ok(true)
 }
\ No newline at end of file
@@ -1,7 +1,8 @@
 // src-tauri/src/api/auth.rs
-use anyhow;
 use crate::db::models::User;
-use crate::error::Result;
+use crate::api::roles::assign_default_roles;
+use crate::db::DbState;
+use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
 use sqlx::{SqlitePool,Row};
 use tauri::State;
@@ -10,6 +11,7 @@ use argon2::{
     Argon2,
 };
 use rand_core::OsRng;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::sync::Mutex;
 use uuid::Uuid;
@@ -17,13 +19,22 @@ use uuid::Uuid;
 // 会话管理
 #[derive(Debug, Clone)]
 pub struct Session {
+    pub id: i64,
     pub user_id: i64,
     pub username: String,
     pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
 }
 
+// 会话缓存，键为令牌的哈希值
 pub type Sessions = Mutex<HashMap<String, Session>>;
 
+// 会话有效期（天）
+const SESSION_TTL_DAYS: i64 = 7;
+
+// 最近活跃时间的刷新间隔（秒），避免每次请求都写数据库
+const LAST_SEEN_REFRESH_SECS: i64 = 300;
+
 // 会话状态管理
 #[derive(Debug, Default)]
 pub struct AuthState {
@@ -36,6 +47,15 @@ pub struct AuthResponse {
     pub token: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct SessionInfo {
+    pub id: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub is_current: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct LoginRequest {
     pub email: String,
@@ -56,61 +76,168 @@ pub fn init_auth_state() -> AuthState {
     }
 }
 
-// 验证会话令牌
-pub fn validate_token(sessions: &Sessions, token: &str) -> Option<Session> {
-    let sessions_map = sessions.lock().ok()?;
-    let session = sessions_map.get(token)?;
-    
-    // 检查会话是否过期
-    if session.expires_at < chrono::Utc::now() {
-        return None;
+// 令牌只以 SHA-256 哈希形式保存
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+// 验证会话令牌：优先读取内存缓存，未命中时查询数据库
+pub async fn validate_token(
+    pool: &SqlitePool,
+    auth_state: &AuthState,
+    token: &str,
+) -> Result<Option<Session>> {
+    let token_hash = hash_token(token);
+    let now = chrono::Utc::now();
+
+    let cached = {
+        let sessions_map = auth_state.sessions.lock().unwrap();
+        sessions_map.get(&token_hash).cloned()
+    };
+
+    if let Some(session) = cached {
+        if session.expires_at < now {
+            auth_state.sessions.lock().unwrap().remove(&token_hash);
+            return Ok(None);
+        }
+
+        if (now - session.last_seen_at).num_seconds() < LAST_SEEN_REFRESH_SECS {
+            return Ok(Some(session));
+        }
     }
-    
-    Some(session.clone())
+
+    // 过期判断在 SQL 中完成
+    let query = r#"
+        SELECT s.id, s.user_id, u.username, s.expires_at
+        FROM sessions s
+        INNER JOIN users u ON u.id = s.user_id
+        WHERE s.token_hash = ? AND s.expires_at > datetime('now')
+    "#;
+    let row = sqlx::query(query)
+        .bind(&token_hash)
+        .fetch_optional(pool)
+        .await?;
+
+    let Some(row) = row else {
+        auth_state.sessions.lock().unwrap().remove(&token_hash);
+        return Ok(None);
+    };
+
+    let session = Session {
+        id: row.get("id"),
+        user_id: row.get("user_id"),
+        username: row.get("username"),
+        expires_at: row.get("expires_at"),
+        last_seen_at: now,
+    };
+
+    sqlx::query("UPDATE sessions SET last_seen_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(session.id)
+        .execute(pool)
+        .await?;
+
+    auth_state
+        .sessions
+        .lock()
+        .unwrap()
+        .insert(token_hash, session.clone());
+
+    Ok(Some(session))
+}
+
+/// 验证会话令牌，未登录或会话已过期时返回 AUTH_REQUIRED 错误
+pub async fn require_session(pool: &SqlitePool, auth_state: &AuthState, token: &str) -> Result<Session> {
+    validate_token(pool, auth_state, token)
+        .await?
+        .ok_or_else(|| Error::AuthRequired("会话无效或已过期".to_string()))
+}
+
+/// 可选登录的接口使用：返回当前用户ID，未登录或会话无效时为 None
+pub async fn optional_user_id(
+    pool: &SqlitePool,
+    auth_state: &AuthState,
+    token: Option<&str>,
+) -> Result<Option<i64>> {
+    let Some(token) = token else {
+        return Ok(None);
+    };
+    Ok(validate_token(pool, auth_state, token).await?.map(|session| session.user_id))
 }
 
 // 创建新会话
-fn create_session(sessions: &Sessions, user: &User) -> String {
+async fn create_session(pool: &SqlitePool, auth_state: &AuthState, user: &User) -> Result<String> {
     let token = Uuid::new_v4().to_string();
+    let token_hash = hash_token(&token);
+
+    let query = r#"
+        INSERT INTO sessions (user_id, token_hash, expires_at)
+        VALUES (?, ?, datetime('now', ?))
+    "#;
+    let result = sqlx::query(query)
+        .bind(user.id)
+        .bind(&token_hash)
+        .bind(format!("+{} days", SESSION_TTL_DAYS))
+        .execute(pool)
+        .await?;
+
+    let now = chrono::Utc::now();
     let session = Session {
+        id: result.last_insert_rowid(),
         user_id: user.id,
         username: user.username.clone(),
-        expires_at: chrono::Utc::now() + chrono::Duration::days(7), // 7天有效期
+        expires_at: now + chrono::Duration::days(SESSION_TTL_DAYS),
+        last_seen_at: now,
     };
-    
-    let mut sessions_map = sessions.lock().unwrap();
-    sessions_map.insert(token.clone(), session);
-    
-    token
+
+    let mut sessions_map = auth_state.sessions.lock().unwrap();
+    sessions_map.insert(token_hash, session);
+
+    Ok(token)
 }
 
 // 删除会话
-fn remove_session(sessions: &Sessions, token: &str) {
-    let mut sessions_map = sessions.lock().unwrap();
-    sessions_map.remove(token);
+async fn remove_session(pool: &SqlitePool, auth_state: &AuthState, token: &str) -> Result<()> {
+    let token_hash = hash_token(token);
+
+    sqlx::query("DELETE FROM sessions WHERE token_hash = ?")
+        .bind(&token_hash)
+        .execute(pool)
+        .await?;
+
+    let mut sessions_map = auth_state.sessions.lock().unwrap();
+    sessions_map.remove(&token_hash);
+
+    Ok(())
 }
 
 // 清理过期会话
-fn cleanup_expired_sessions(sessions: &Sessions) {
+async fn cleanup_expired_sessions(pool: &SqlitePool, auth_state: &AuthState) -> Result<()> {
+    sqlx::query("DELETE FROM sessions WHERE expires_at <= datetime('now')")
+        .execute(pool)
+        .await?;
+
     let now = chrono::Utc::now();
-    let mut sessions_map = sessions.lock().unwrap();
+    let mut sessions_map = auth_state.sessions.lock().unwrap();
     sessions_map.retain(|_, session| session.expires_at > now);
+
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn login(
-    pool: State<'_, SqlitePool>,
+    db: State<'_, DbState>,
     auth_state: State<'_, AuthState>,
     request: LoginRequest,
 ) -> Result<AuthResponse> {
+    let pool = db.pool()?;
     // 查询用户
     let query = "SELECT id, username, email, password_hash, created_at FROM users WHERE email = ?";
     let row = sqlx::query(query)
         .bind(&request.email)
-        .fetch_optional(&*pool)
+        .fetch_optional(&pool)
         .await?;
 
-    let row = row.ok_or_else(|| anyhow::anyhow!("用户不存在"))?;
+    let row = row.ok_or_else(|| Error::InvalidCredentials("用户不存在".to_string()))?;
 
     let user = User {
         id: row.get("id"),
@@ -126,38 +253,39 @@ pub async fn login(
 
     Argon2::default()
         .verify_password(request.password.as_bytes(), &parsed_hash)
-        .map_err(|_| anyhow::anyhow!("密码错误"))?;
+        .map_err(|_| Error::InvalidCredentials("密码错误".to_string()))?;
 
     // 创建会话
-    let token = create_session(&auth_state.sessions, &user);
+    let token = create_session(&pool, &auth_state, &user).await?;
     
     // 清理过期会话
-    cleanup_expired_sessions(&auth_state.sessions);
+    cleanup_expired_sessions(&pool, &auth_state).await?;
 
     Ok(AuthResponse { user, token })
 }
 
 #[tauri::command]
 pub async fn register(
-    pool: State<'_, SqlitePool>,
+    db: State<'_, DbState>,
     auth_state: State<'_, AuthState>,
     request: RegisterRequest,
 ) -> Result<AuthResponse> {
+    let pool = db.pool()?;
     // 检查用户名是否已存在
     let query = "SELECT id FROM users WHERE username = ? OR email = ?";
     let existing_user = sqlx::query(query)
         .bind(&request.username)
         .bind(&request.email)
-        .fetch_optional(&*pool)
+        .fetch_optional(&pool)
         .await?;
 
     if existing_user.is_some() {
-        return Err(anyhow::anyhow!("用户名或邮箱已存在").into());
+        return Err(Error::Conflict("用户名或邮箱已存在".to_string()));
     }
 
     // 验证密码强度
     if request.password.len() < 6 {
-        return Err(anyhow::anyhow!("密码长度至少6位").into());
+        return Err(Error::invalid_field("password", "密码长度至少6位"));
     }
 
     // 哈希密码
@@ -174,16 +302,19 @@ pub async fn register(
         .bind(&request.username)
         .bind(&request.email)
         .bind(&password_hash)
-        .execute(&*pool)
+        .execute(&pool)
         .await?;
 
     let user_id = result.last_insert_rowid();
 
+    // 分配默认角色
+    assign_default_roles(&pool, user_id).await?;
+
     // 获取新创建的用户
     let query = "SELECT id, username, email, password_hash, created_at FROM users WHERE id = ?";
     let row = sqlx::query(query)
         .bind(user_id)
-        .fetch_one(&*pool)
+        .fetch_one(&pool)
         .await?;
 
     let user = User {
@@ -195,29 +326,30 @@ pub async fn register(
     };
 
     // 创建会话
-    let token = create_session(&auth_state.sessions, &user);
+    let token = create_session(&pool, &auth_state, &user).await?;
     
     // 清理过期会话
-    cleanup_expired_sessions(&auth_state.sessions);
+    cleanup_expired_sessions(&pool, &auth_state).await?;
 
     Ok(AuthResponse { user, token })
 }
 
 #[tauri::command]
 pub async fn get_current_user(
-    pool: State<'_, SqlitePool>,
+    db: State<'_, DbState>,
     auth_state: State<'_, AuthState>,
     token: String,
 ) -> Result<Option<User>> {
+    let pool = db.pool()?;
     // 验证会话令牌
-    let session = validate_token(&auth_state.sessions, &token);
+    let session = validate_token(&pool, &auth_state, &token).await?;
     
     if let Some(session) = session {
         // 从数据库获取最新的用户信息
         let query = "SELECT id, username, email, password_hash, created_at FROM users WHERE id = ?";
         let row = sqlx::query(query)
             .bind(session.user_id)
-            .fetch_optional(&*pool)
+            .fetch_optional(&pool)
             .await?;
 
         if let Some(row) = row {
@@ -237,24 +369,107 @@ pub async fn get_current_user(
 
 #[tauri::command]
 pub async fn logout(
+    db: State<'_, DbState>,
     auth_state: State<'_, AuthState>,
     token: String,
 ) -> Result<bool> {
-    remove_session(&auth_state.sessions, &token);
+    let pool = db.pool()?;
+    remove_session(&pool, &auth_state, &token).await?;
     Ok(true)
 }
 
+/// 列出当前用户的所有有效会话
+#[tauri::command]
+pub async fn list_sessions(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+) -> Result<Vec<SessionInfo>> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+
+    let query = r#"
+        SELECT id, created_at, last_seen_at, expires_at
+        FROM sessions
+        WHERE user_id = ? AND expires_at > datetime('now')
+        ORDER BY last_seen_at DESC
+    "#;
+    let rows = sqlx::query(query)
+        .bind(session.user_id)
+        .fetch_all(&pool)
+        .await?;
+
+    let sessions = rows.into_iter().map(|row| {
+        let id: i64 = row.get("id");
+        SessionInfo {
+            id,
+            created_at: row.get("created_at"),
+            last_seen_at: row.get("last_seen_at"),
+            expires_at: row.get("expires_at"),
+            is_current: id == session.id,
+        }
+    }).collect();
+
+    Ok(sessions)
+}
+
+/// 撤销当前用户的某个会话
+#[tauri::command]
+pub async fn revoke_session(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    session_id: i64,
+) -> Result<bool> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+
+    // 只能撤销自己的会话
+    let result = sqlx::query("DELETE FROM sessions WHERE id = ? AND user_id = ?")
+        .bind(session_id)
+        .bind(session.user_id)
+        .execute(&pool)
+        .await?;
+
+    let mut sessions_map = auth_state.sessions.lock().unwrap();
+    sessions_map.retain(|_, s| s.id != session_id);
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// 撤销当前用户除本会话以外的所有会话
+#[tauri::command]
+pub async fn revoke_other_sessions(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+) -> Result<u64> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+
+    let result = sqlx::query("DELETE FROM sessions WHERE user_id = ? AND id != ?")
+        .bind(session.user_id)
+        .bind(session.id)
+        .execute(&pool)
+        .await?;
+
+    let mut sessions_map = auth_state.sessions.lock().unwrap();
+    sessions_map.retain(|_, s| s.user_id != session.user_id || s.id == session.id);
+
+    Ok(result.rows_affected())
+}
+
 #[tauri::command]
 pub async fn update_profile(
-    pool: State<'_, SqlitePool>,
+    db: State<'_, DbState>,
     auth_state: State<'_, AuthState>,
     token: String,
     username: Option<String>,
     email: Option<String>,
 ) -> Result<User> {
+    let pool = db.pool()?;
     // 验证会话
-    let session = validate_token(&auth_state.sessions, &token)
-        .ok_or_else(|| anyhow::anyhow!("会话无效或已过期"))?;
+    let session = require_session(&pool, &auth_state, &token).await?;
 
     let mut updates = Vec::new();
     let mut params: Vec<String> = Vec::new();
@@ -265,11 +480,11 @@ pub async fn update_profile(
         let existing = sqlx::query(check_query)
             .bind(&username)
             .bind(session.user_id)
-            .fetch_optional(&*pool)
+            .fetch_optional(&pool)
             .await?;
         
         if existing.is_some() {
-            return Err(anyhow::anyhow!("用户名已被使用").into());
+            return Err(Error::Conflict("用户名已被使用".to_string()));
         }
         
         updates.push("username = ?");
@@ -282,11 +497,11 @@ pub async fn update_profile(
         let existing = sqlx::query(check_query)
             .bind(&email)
             .bind(session.user_id)
-            .fetch_optional(&*pool)
+            .fetch_optional(&pool)
             .await?;
         
         if existing.is_some() {
-            return Err(anyhow::anyhow!("邮箱已被使用").into());
+            return Err(Error::Conflict("邮箱已被使用".to_string()));
         }
         
         updates.push("email = ?");
@@ -294,7 +509,7 @@ pub async fn update_profile(
     }
 
     if updates.is_empty() {
-        return Err(anyhow::anyhow!("没有提供更新字段").into());
+        return Err(Error::validation("没有提供更新字段"));
     }
 
     let query = format!("UPDATE users SET {} WHERE id = ?", updates.join(", "));
@@ -305,13 +520,13 @@ pub async fn update_profile(
     }
     sql_query = sql_query.bind(session.user_id);
 
-    sql_query.execute(&*pool).await?;
+    sql_query.execute(&pool).await?;
 
     // 获取更新后的用户信息
     let query = "SELECT id, username, email, password_hash, created_at FROM users WHERE id = ?";
     let row = sqlx::query(query)
         .bind(session.user_id)
-        .fetch_one(&*pool)
+        .fetch_one(&pool)
         .await?;
 
     let user = User {
@@ -329,7 +544,7 @@ pub async fn update_profile(
 #[tauri::command]
 pub async fn validate_password_strength(password: String) -> Result<bool> {
     if password.len() < 6 {
-        return Err(anyhow::anyhow!("密码长度至少6位").into());
+        return Err(Error::invalid_field("password", "密码长度至少6位"));
     }
     
     // 可以添加更多密码强度规则
@@ -337,7 +552,7 @@ pub async fn validate_password_strength(password: String) -> Result<bool> {
     let has_digit = password.chars().any(|c| c.is_numeric());
     
     if !has_letter || !has_digit {
-        return Err(anyhow::anyhow!("密码应包含字母和数字").into());
+        return Err(Error::invalid_field("password", "密码应包含字母和数字"));
     }
     
     Ok(true)
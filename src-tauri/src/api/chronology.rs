@@ -0,0 +1,226 @@
+// src-tauri/src/api/chronology.rs
+use crate::api::artifacts::{push_filter_conditions, row_to_artifact, SearchParams, ARTIFACT_COLUMNS, IMAGE_MISSING_COLUMN};
+use crate::api::auth::{optional_user_id, AuthState};
+use crate::chronology::{Chronology, ChronologyPeriod, DateRange, PeriodKind};
+use crate::db::models::ArtifactWithFavorite;
+use crate::db::DbState;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use std::collections::BTreeMap;
+use tauri::State;
+
+const DEFAULT_BUCKET_ITEMS: usize = 20;
+const MAX_BUCKET_ITEMS: usize = 100;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineGrouping {
+    /// 按年份范围的中点所在世纪
+    #[default]
+    Century,
+    /// 按朝代，年号归入所属朝代
+    Dynasty,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimelineQuery {
+    #[serde(default)]
+    pub group_by: TimelineGrouping,
+    /// 与 get_artifacts 相同的筛选条件（忽略分页和排序）
+    #[serde(default)]
+    pub params: Option<SearchParams>,
+    /// 每组返回的文物数，默认 20，最大 100
+    pub limit_per_bucket: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimelineBucket {
+    pub label: String,
+    pub start_year: i64,
+    pub end_year: i64,
+    pub count: usize,
+    /// 按起始年份排列，至多 limit_per_bucket 件
+    pub items: Vec<ArtifactWithFavorite>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Timeline {
+    /// 按年代先后排列
+    pub buckets: Vec<TimelineBucket>,
+    /// 符合条件但没有年份的文物数量
+    pub undated: i64,
+    /// 按朝代分组时无法归入任何朝代的文物数量
+    pub unassigned: usize,
+}
+
+// 年份所在的世纪：(名称, 起始年, 终止年)
+fn century_of(year: i64) -> (String, i64, i64) {
+    if year > 0 {
+        let c = (year - 1) / 100 + 1;
+        (format!("公元{}世纪", c), (c - 1) * 100 + 1, c * 100)
+    } else if year < 0 {
+        let c = (-year - 1) / 100 + 1;
+        (format!("公元前{}世纪", c), -(c * 100), -((c - 1) * 100 + 1))
+    } else {
+        century_of(1)
+    }
+}
+
+// 文物所属的朝代：先按朝代和年代文本匹配，否则取包含年份中点的最短朝代
+fn dynasty_of<'a>(
+    chronology: &'a Chronology,
+    artifact: &ArtifactWithFavorite,
+    range: DateRange,
+) -> Option<&'a ChronologyPeriod> {
+    let matched = chronology.find(&artifact.dynasty).or_else(|| chronology.find(&artifact.period));
+    let matched = matched.or_else(|| {
+        let year = range.midpoint();
+        chronology
+            .periods()
+            .iter()
+            .filter(|p| p.kind == PeriodKind::Dynasty && p.start_year <= year && year <= p.end_year)
+            .min_by_key(|p| p.end_year - p.start_year)
+    })?;
+    Some(chronology.dynasty_of(matched))
+}
+
+/// 朝代、分期及年号的起止年份，按年代先后排列
+#[tauri::command]
+pub async fn get_chronology(db: State<'_, DbState>) -> Result<Vec<ChronologyPeriod>> {
+    let pool = db.pool()?;
+    Ok(Chronology::load(&pool).await?.periods().to_vec())
+}
+
+/// 按世纪或朝代分组返回文物，供时间轴展示
+#[tauri::command]
+pub async fn get_timeline(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    query: TimelineQuery,
+    token: Option<String>,
+) -> Result<Timeline> {
+    let pool = db.pool()?;
+    let user_id = optional_user_id(&pool, &auth_state, token.as_deref()).await?;
+    let limit = query.limit_per_bucket.unwrap_or(DEFAULT_BUCKET_ITEMS).clamp(1, MAX_BUCKET_ITEMS);
+
+    let mut conditions = Vec::new();
+    let mut bind_values: Vec<String> = Vec::new();
+    if let Some(params) = &query.params {
+        push_filter_conditions(params, &mut conditions, &mut bind_values);
+    }
+    let filter_count = conditions.len();
+    conditions.push("a.date_from IS NOT NULL AND a.date_to IS NOT NULL".to_string());
+
+    let sql = format!(
+        r#"
+        SELECT
+            {},
+            CASE WHEN uf.id IS NOT NULL THEN 1 ELSE 0 END as is_favorite,
+            {}
+        FROM artifacts a
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        WHERE {}
+        ORDER BY a.date_from, a.date_to, a.id
+        "#,
+        ARTIFACT_COLUMNS,
+        IMAGE_MISSING_COLUMN,
+        conditions.join(" AND ")
+    );
+    let mut sql_query = sqlx::query(&sql).bind(user_id);
+    for value in &bind_values {
+        sql_query = sql_query.bind(value);
+    }
+    let rows = sql_query.fetch_all(&pool).await?;
+
+    let chronology = match query.group_by {
+        TimelineGrouping::Dynasty => Some(Chronology::load(&pool).await?),
+        TimelineGrouping::Century => None,
+    };
+
+    // 以 (起始年, 终止年, 名称) 为键，保证按年代先后排列
+    let mut buckets: BTreeMap<(i64, i64, String), TimelineBucket> = BTreeMap::new();
+    let mut unassigned = 0;
+    for row in &rows {
+        let artifact = row_to_artifact(row);
+        let (Some(from), Some(to)) = (artifact.date_from, artifact.date_to) else {
+            continue;
+        };
+        let range = DateRange { from, to };
+        let (label, start_year, end_year) = match &chronology {
+            Some(chronology) => match dynasty_of(chronology, &artifact, range) {
+                Some(period) => (period.name.clone(), period.start_year, period.end_year),
+                None => {
+                    unassigned += 1;
+                    continue;
+                }
+            },
+            None => century_of(range.midpoint()),
+        };
+
+        let bucket = buckets
+            .entry((start_year, end_year, label.clone()))
+            .or_insert_with(|| TimelineBucket {
+                label,
+                start_year,
+                end_year,
+                count: 0,
+                items: Vec::new(),
+            });
+        bucket.count += 1;
+        if bucket.items.len() < limit {
+            bucket.items.push(artifact);
+        }
+    }
+
+    // 没有年份的文物只按筛选条件统计
+    let mut undated_conditions = conditions[..filter_count].to_vec();
+    undated_conditions.push("(a.date_from IS NULL OR a.date_to IS NULL)".to_string());
+    let undated_sql = format!(
+        r#"
+        SELECT COUNT(*) as total
+        FROM artifacts a
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        WHERE {}
+        "#,
+        undated_conditions.join(" AND ")
+    );
+    let mut count_query = sqlx::query(&undated_sql).bind(user_id);
+    for value in &bind_values {
+        count_query = count_query.bind(value);
+    }
+    let undated: i64 = count_query.fetch_one(&pool).await?.get("total");
+
+    Ok(Timeline {
+        buckets: buckets.into_values().collect(),
+        undated,
+        unassigned,
+    })
+}
+
+/// 为尚无年份的文物按朝代和年代推断年份，返回补全的数量
+pub async fn backfill_dates(pool: &SqlitePool) -> Result<usize> {
+    let chronology = Chronology::load(pool).await?;
+    let rows = sqlx::query("SELECT id, dynasty, period FROM artifacts WHERE date_from IS NULL OR date_to IS NULL")
+        .fetch_all(pool)
+        .await?;
+
+    let mut tx = pool.begin().await?;
+    let mut updated = 0;
+    for row in &rows {
+        let dynasty: String = row.get("dynasty");
+        let period: String = row.get("period");
+        let Some(range) = chronology.date_range(&dynasty, &period) else {
+            continue;
+        };
+        sqlx::query("UPDATE artifacts SET date_from = ?, date_to = ? WHERE id = ?")
+            .bind(range.from)
+            .bind(range.to)
+            .bind(row.get::<i64, _>("id"))
+            .execute(&mut *tx)
+            .await?;
+        updated += 1;
+    }
+    tx.commit().await?;
+    Ok(updated)
+}
@@ -0,0 +1,375 @@
+// src-tauri/src/api/conversations.rs
+use crate::api::ai::{prepare_completion, run_stream, ChatState};
+use crate::api::auth::{require_session, AuthState};
+use crate::api::rag::Citation;
+use crate::db::DbState;
+use crate::error::{Error, Result};
+use crate::llm::ChatMessage;
+use serde::Serialize;
+use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
+use tauri::{AppHandle, State};
+
+// 新建对话的默认标题，首条消息发送后替换为消息摘要
+const DEFAULT_TITLE: &str = "新对话";
+
+const MAX_TITLE_CHARS: usize = 100;
+
+// 自动生成标题时截取的字符数
+const AUTO_TITLE_CHARS: usize = 20;
+
+#[derive(Debug, Serialize)]
+pub struct Conversation {
+    pub id: i64,
+    pub title: String,
+    pub message_count: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConversationMessage {
+    pub id: i64,
+    pub role: String,
+    pub content: String,
+    pub citations: Vec<Citation>,
+    /// 回复因用户取消而不完整
+    pub cancelled: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConversationDetail {
+    #[serde(flatten)]
+    pub conversation: Conversation,
+    pub messages: Vec<ConversationMessage>,
+}
+
+/// 一轮对话中保存的用户消息和助手回复
+///
+/// 还未生成任何内容就被取消时不保存本轮，两条消息均为空
+#[derive(Debug, Serialize)]
+pub struct ConversationTurn {
+    pub user_message: Option<ConversationMessage>,
+    pub assistant_message: Option<ConversationMessage>,
+    /// 回复是否因用户取消而不完整
+    pub cancelled: bool,
+}
+
+fn normalize_title(title: &str) -> Result<String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(Error::invalid_field("title", "标题不能为空"));
+    }
+    if title.chars().count() > MAX_TITLE_CHARS {
+        return Err(Error::invalid_field(
+            "title",
+            format!("标题不能超过 {} 个字符", MAX_TITLE_CHARS),
+        ));
+    }
+    Ok(title.to_string())
+}
+
+// 用首条消息生成标题
+fn auto_title(message: &str) -> String {
+    let message = message.split_whitespace().collect::<Vec<_>>().join(" ");
+    if message.chars().count() <= AUTO_TITLE_CHARS {
+        return message;
+    }
+    let truncated: String = message.chars().take(AUTO_TITLE_CHARS).collect();
+    format!("{}…", truncated)
+}
+
+fn row_to_message(row: &SqliteRow) -> ConversationMessage {
+    let citations: Option<String> = row.get("citations");
+    ConversationMessage {
+        id: row.get("id"),
+        role: row.get("role"),
+        content: row.get("content"),
+        citations: citations
+            .and_then(|c| serde_json::from_str(&c).ok())
+            .unwrap_or_default(),
+        cancelled: row.get("cancelled"),
+        created_at: row.get("created_at"),
+    }
+}
+
+// 查询当前用户的对话；不存在或属于其他用户时返回 NOT_FOUND
+async fn fetch_conversation(pool: &SqlitePool, user_id: i64, conversation_id: i64) -> Result<Conversation> {
+    let row = sqlx::query(
+        r#"
+        SELECT c.id, c.title, c.created_at, c.updated_at,
+            (SELECT COUNT(*) FROM conversation_messages m WHERE m.conversation_id = c.id) as message_count
+        FROM conversations c
+        WHERE c.id = ? AND c.user_id = ?
+        "#,
+    )
+    .bind(conversation_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| Error::NotFound(format!("对话不存在: {}", conversation_id)))?;
+
+    Ok(Conversation {
+        id: row.get("id"),
+        title: row.get("title"),
+        message_count: row.get("message_count"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+async fn fetch_messages(pool: &SqlitePool, conversation_id: i64) -> Result<Vec<ConversationMessage>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT id, role, content, citations, cancelled, created_at
+        FROM conversation_messages
+        WHERE conversation_id = ?
+        ORDER BY id
+        "#,
+    )
+    .bind(conversation_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.iter().map(row_to_message).collect())
+}
+
+/// 新建对话
+#[tauri::command]
+pub async fn create_conversation(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    title: Option<String>,
+) -> Result<Conversation> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+
+    let title = match title {
+        Some(title) => normalize_title(&title)?,
+        None => DEFAULT_TITLE.to_string(),
+    };
+
+    let result = sqlx::query("INSERT INTO conversations (user_id, title) VALUES (?, ?)")
+        .bind(session.user_id)
+        .bind(&title)
+        .execute(&pool)
+        .await?;
+
+    fetch_conversation(&pool, session.user_id, result.last_insert_rowid()).await
+}
+
+/// 列出当前用户的对话，最近更新的在前
+#[tauri::command]
+pub async fn list_conversations(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+) -> Result<Vec<Conversation>> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT c.id, c.title, c.created_at, c.updated_at,
+            (SELECT COUNT(*) FROM conversation_messages m WHERE m.conversation_id = c.id) as message_count
+        FROM conversations c
+        WHERE c.user_id = ?
+        ORDER BY c.updated_at DESC, c.id DESC
+        "#,
+    )
+    .bind(session.user_id)
+    .fetch_all(&pool)
+    .await?;
+
+    let conversations = rows
+        .into_iter()
+        .map(|row| Conversation {
+            id: row.get("id"),
+            title: row.get("title"),
+            message_count: row.get("message_count"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+        .collect();
+
+    Ok(conversations)
+}
+
+/// 获取对话及其全部消息
+#[tauri::command]
+pub async fn get_conversation(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    conversation_id: i64,
+) -> Result<ConversationDetail> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+
+    let conversation = fetch_conversation(&pool, session.user_id, conversation_id).await?;
+    let messages = fetch_messages(&pool, conversation_id).await?;
+
+    Ok(ConversationDetail { conversation, messages })
+}
+
+/// 重命名对话
+#[tauri::command]
+pub async fn rename_conversation(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    conversation_id: i64,
+    title: String,
+) -> Result<Conversation> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+    let title = normalize_title(&title)?;
+
+    let result = sqlx::query(
+        "UPDATE conversations SET title = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ? AND user_id = ?",
+    )
+    .bind(&title)
+    .bind(conversation_id)
+    .bind(session.user_id)
+    .execute(&pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(Error::NotFound(format!("对话不存在: {}", conversation_id)));
+    }
+
+    fetch_conversation(&pool, session.user_id, conversation_id).await
+}
+
+/// 删除对话及其消息
+#[tauri::command]
+pub async fn delete_conversation(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    conversation_id: i64,
+) -> Result<bool> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+
+    let result = sqlx::query("DELETE FROM conversations WHERE id = ? AND user_id = ?")
+        .bind(conversation_id)
+        .bind(session.user_id)
+        .execute(&pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(Error::NotFound(format!("对话不存在: {}", conversation_id)));
+    }
+
+    Ok(true)
+}
+
+/// 在已有对话中继续提问，回复以流式事件推送
+///
+/// 历史消息由后端读取并按上下文窗口裁剪；生成完成后保存本轮的提问和回复，
+/// 被取消时只保存已生成的部分并标记为取消，尚未生成任何内容则不保存。
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn continue_conversation(
+    app: AppHandle,
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    chat_state: State<'_, ChatState>,
+    token: String,
+    conversation_id: i64,
+    request_id: String,
+    message: String,
+) -> Result<ConversationTurn> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+
+    let message = message.trim().to_string();
+    if message.is_empty() {
+        return Err(Error::invalid_field("message", "消息不能为空"));
+    }
+
+    let conversation = fetch_conversation(&pool, session.user_id, conversation_id).await?;
+    // 旧版本可能保存了被取消的空回复，不发送给模型
+    let history = fetch_messages(&pool, conversation_id)
+        .await?
+        .into_iter()
+        .filter(|m| !m.content.is_empty())
+        .map(|m| ChatMessage { role: m.role, content: m.content })
+        .collect();
+
+    let provider = chat_state.provider()?;
+    let completion = prepare_completion(&db, &chat_state, message.clone(), history).await;
+    let (response, cancelled) =
+        run_stream(&app, &chat_state, &request_id, provider.as_ref(), &completion).await?;
+
+    if cancelled && response.response.trim().is_empty() {
+        return Ok(ConversationTurn {
+            user_message: None,
+            assistant_message: None,
+            cancelled,
+        });
+    }
+
+    let citations = serde_json::to_string(&response.citations).map_err(anyhow::Error::from)?;
+
+    let mut tx = pool.begin().await?;
+
+    let user_message_id = sqlx::query(
+        "INSERT INTO conversation_messages (conversation_id, role, content) VALUES (?, 'user', ?)",
+    )
+    .bind(conversation_id)
+    .bind(&message)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    let assistant_message_id = sqlx::query(
+        "INSERT INTO conversation_messages (conversation_id, role, content, citations, cancelled) VALUES (?, 'assistant', ?, ?, ?)",
+    )
+    .bind(conversation_id)
+    .bind(&response.response)
+    .bind(&citations)
+    .bind(cancelled)
+    .execute(&mut *tx)
+    .await?
+    .last_insert_rowid();
+
+    // 首条消息发送后用其摘要替换默认标题
+    let title = if conversation.message_count == 0 && conversation.title == DEFAULT_TITLE {
+        auto_title(&message)
+    } else {
+        conversation.title
+    };
+    sqlx::query("UPDATE conversations SET title = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(&title)
+        .bind(conversation_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    let saved = sqlx::query(
+        r#"
+        SELECT id, role, content, citations, cancelled, created_at
+        FROM conversation_messages
+        WHERE id IN (?, ?)
+        ORDER BY id
+        "#,
+    )
+    .bind(user_message_id)
+    .bind(assistant_message_id)
+    .fetch_all(&pool)
+    .await?;
+
+    let mut saved = saved.iter().map(row_to_message);
+    match (saved.next(), saved.next()) {
+        (Some(user_message), Some(assistant_message)) => Ok(ConversationTurn {
+            user_message: Some(user_message),
+            assistant_message: Some(assistant_message),
+            cancelled,
+        }),
+        _ => Err(anyhow::anyhow!("保存对话消息失败").into()),
+    }
+}
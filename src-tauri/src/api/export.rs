@@ -0,0 +1,362 @@
+// src-tauri/src/api/export.rs
+use crate::api::artifacts::{push_filter_conditions, SearchParams, SortOrder, ARTIFACT_COLUMNS};
+use crate::api::auth::{require_session, AuthState};
+use crate::db::DbState;
+use crate::error::Result;
+use crate::storage::MediaStore;
+use crate::vocabulary::{Vocabularies, Vocabulary};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+use zip::write::SimpleFileOptions;
+
+// 导出文件名前缀
+const EXPORT_FILE_PREFIX: &str = "artifacts_export";
+
+// zip 包中存放图片的目录
+const ZIP_IMAGE_DIR: &str = "images";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    /// 每行一个 JSON 对象
+    Jsonl,
+    /// OAI-PMH 风格的 Dublin Core XML
+    DublinCore,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::DublinCore => "xml",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportRequest {
+    pub format: ExportFormat,
+    /// 与 get_artifacts 相同的筛选和排序条件（忽略分页）；不提供时导出全部文物
+    #[serde(default)]
+    pub params: Option<SearchParams>,
+    /// 是否把引用的图片一起打包为 zip
+    #[serde(default)]
+    pub include_images: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportResult {
+    /// 导出文件的完整路径
+    pub path: String,
+    pub count: usize,
+    /// 打包的图片数量
+    pub images: usize,
+    /// 找不到文件、未能打包的图片
+    pub missing_images: Vec<String>,
+}
+
+/// 导出的一条文物记录
+#[derive(Debug, Serialize)]
+struct ExportRecord {
+    id: i64,
+    accession_number: Option<String>,
+    title: String,
+    image_path: String,
+    period: String,
+    dynasty: String,
+    location: String,
+    description: String,
+    detailed_description: String,
+    material: String,
+    dimensions: String,
+    discovery_location: String,
+    collection: String,
+    category: String,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    location_uncertainty_m: Option<f64>,
+    date_from: Option<i64>,
+    date_to: Option<i64>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+async fn fetch_records(
+    pool: &SqlitePool,
+    user_id: i64,
+    params: Option<&SearchParams>,
+) -> Result<Vec<ExportRecord>> {
+    let mut conditions = Vec::new();
+    let mut bind_values: Vec<String> = Vec::new();
+
+    let order_by = match params {
+        Some(params) => {
+            push_filter_conditions(params, &mut conditions, &mut bind_values);
+            let direction = match params.order.unwrap_or_default() {
+                SortOrder::Asc => "ASC",
+                SortOrder::Desc => "DESC",
+            };
+            format!(
+                "{expr} {dir}, a.id {dir}",
+                expr = params.sort.unwrap_or_default().sql_expr(),
+                dir = direction
+            )
+        }
+        None => "a.id".to_string(),
+    };
+
+    let mut query = format!(
+        r#"
+        SELECT
+            {}, a.accession_number
+        FROM artifacts a
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        "#,
+        ARTIFACT_COLUMNS
+    );
+    if !conditions.is_empty() {
+        query.push_str(" WHERE ");
+        query.push_str(&conditions.join(" AND "));
+    }
+    query.push_str(&format!(" ORDER BY {}", order_by));
+
+    let mut sql_query = sqlx::query(&query).bind(user_id);
+    for value in bind_values {
+        sql_query = sql_query.bind(value);
+    }
+
+    let rows = sql_query.fetch_all(pool).await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| ExportRecord {
+            id: row.get("id"),
+            accession_number: row.get("accession_number"),
+            title: row.get("title"),
+            image_path: row.get("image_path"),
+            period: row.get("period"),
+            dynasty: row.get("dynasty"),
+            location: row.get("location"),
+            description: row.get("description"),
+            detailed_description: row.get("detailed_description"),
+            material: row.get("material"),
+            dimensions: row.get("dimensions"),
+            discovery_location: row.get("discovery_location"),
+            collection: row.get("collection"),
+            category: row.get("category"),
+            latitude: row.get("latitude"),
+            longitude: row.get("longitude"),
+            location_uncertainty_m: row.get("location_uncertainty_m"),
+            date_from: row.get("date_from"),
+            date_to: row.get("date_to"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+        .collect())
+}
+
+fn write_csv(records: &[ExportRecord]) -> Result<Vec<u8>> {
+    // 带 BOM，方便 Excel 直接打开中文内容
+    let mut writer = csv::Writer::from_writer("\u{feff}".as_bytes().to_vec());
+    for record in records {
+        writer.serialize(record).map_err(anyhow::Error::from)?;
+    }
+    Ok(writer.into_inner().map_err(|e| anyhow::anyhow!("写入 CSV 失败: {}", e))?)
+}
+
+fn write_jsonl(records: &[ExportRecord]) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    for record in records {
+        serde_json::to_writer(&mut output, record).map_err(anyhow::Error::from)?;
+        output.push(b'\n');
+    }
+    Ok(output)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// Dublin Core 映射：每件文物一个 oai_dc:dc 元素，空字段不输出
+fn write_dublin_core(records: &[ExportRecord], vocabularies: &Vocabularies) -> Vec<u8> {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <records xmlns:oai_dc=\"http://www.openarchives.org/OAI/2.0/oai_dc/\" \
+         xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n",
+    );
+
+    for record in records {
+        let identifier = record
+            .accession_number
+            .clone()
+            .unwrap_or_else(|| format!("artifact:{}", record.id));
+        let period = if record.period.is_empty() {
+            record.dynasty.clone()
+        } else {
+            format!("{}（{}）", record.dynasty, record.period)
+        };
+        // DCMI Point 格式的出土地点坐标
+        let point = match (record.latitude, record.longitude) {
+            (Some(latitude), Some(longitude)) => format!("east={}; north={}", longitude, latitude),
+            _ => String::new(),
+        };
+        // DCMI Period 格式的年份范围
+        let date = match (record.date_from, record.date_to) {
+            (Some(from), Some(to)) => format!("start={}; end={}", from, to),
+            _ => String::new(),
+        };
+        let source = if record.location.is_empty() {
+            record.collection.clone()
+        } else {
+            format!("{}，{}", record.collection, record.location)
+        };
+
+        let elements = [
+            ("dc:identifier", identifier.as_str()),
+            ("dc:title", record.title.as_str()),
+            ("dc:type", "PhysicalObject"),
+            ("dc:subject", vocabularies.label(Vocabulary::Category, &record.category)),
+            ("dc:description", record.description.as_str()),
+            ("dc:description", record.detailed_description.as_str()),
+            ("dc:coverage", period.as_str()),
+            ("dc:coverage", record.discovery_location.as_str()),
+            ("dc:coverage", point.as_str()),
+            ("dc:date", date.as_str()),
+            ("dc:format", record.material.as_str()),
+            ("dc:format", record.dimensions.as_str()),
+            ("dc:source", source.as_str()),
+            ("dc:relation", record.image_path.as_str()),
+        ];
+
+        xml.push_str("  <oai_dc:dc>\n");
+        for (name, value) in elements {
+            if !value.trim().is_empty() {
+                xml.push_str(&format!("    <{name}>{}</{name}>\n", escape_xml(value), name = name));
+            }
+        }
+        xml.push_str("  </oai_dc:dc>\n");
+    }
+
+    xml.push_str("</records>\n");
+    xml.into_bytes()
+}
+
+// 把数据文件和引用的图片写入 zip，返回打包的图片数量和缺失的图片
+fn write_zip(
+    store: &MediaStore,
+    path: &PathBuf,
+    data_name: &str,
+    data: &[u8],
+    image_paths: Vec<String>,
+) -> Result<(usize, Vec<String>)> {
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file(data_name, options).map_err(anyhow::Error::from)?;
+    zip.write_all(data)?;
+
+    // 图片本身已是压缩格式，直接存储
+    let stored = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let mut images = 0;
+    let mut missing = Vec::new();
+    for image_path in image_paths {
+        match std::fs::read(store.image_file(&image_path)) {
+            Ok(bytes) => {
+                zip.start_file(format!("{}/{}", ZIP_IMAGE_DIR, image_path), stored)
+                    .map_err(anyhow::Error::from)?;
+                zip.write_all(&bytes)?;
+                images += 1;
+            }
+            Err(_) => missing.push(image_path),
+        }
+    }
+
+    zip.finish().map_err(anyhow::Error::from)?;
+    Ok((images, missing))
+}
+
+// 导出到下载目录，无法获取时退回应用数据目录
+fn export_dir(app: &AppHandle) -> Result<PathBuf> {
+    let dir = match app.path().download_dir() {
+        Ok(dir) => dir,
+        Err(_) => app
+            .path()
+            .app_data_dir()
+            .map_err(|e| anyhow::anyhow!("无法获取导出目录: {}", e))?
+            .join("exports"),
+    };
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// 导出文物目录为 CSV、JSON Lines 或 Dublin Core XML，可选把图片一起打包为 zip
+///
+/// 文件写入下载目录，返回完整路径。
+#[tauri::command]
+pub async fn export_artifacts(
+    app: AppHandle,
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    request: ExportRequest,
+) -> Result<ExportResult> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+
+    let records = fetch_records(&pool, session.user_id, request.params.as_ref()).await?;
+
+    let data = match request.format {
+        ExportFormat::Csv => write_csv(&records)?,
+        ExportFormat::Jsonl => write_jsonl(&records)?,
+        ExportFormat::DublinCore => write_dublin_core(&records, &Vocabularies::load(&pool).await?),
+    };
+
+    let stem = format!(
+        "{}_{}",
+        EXPORT_FILE_PREFIX,
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    );
+    let data_name = format!("{}.{}", stem, request.format.extension());
+    let dir = export_dir(&app)?;
+
+    let (path, images, missing_images) = if request.include_images {
+        let mut seen = HashSet::new();
+        let image_paths: Vec<String> = records
+            .iter()
+            .filter(|r| !r.image_path.is_empty() && seen.insert(r.image_path.clone()))
+            .map(|r| r.image_path.clone())
+            .collect();
+
+        let path = dir.join(format!("{}.zip", stem));
+        let zip_path = path.clone();
+        let store = app.state::<MediaStore>().inner().clone();
+        // 打包图片涉及大量文件读写，放到阻塞线程执行
+        let (images, missing) = tokio::task::spawn_blocking(move || {
+            write_zip(&store, &zip_path, &data_name, &data, image_paths)
+        })
+        .await
+        .map_err(anyhow::Error::from)??;
+        (path, images, missing)
+    } else {
+        let path = dir.join(&data_name);
+        std::fs::write(&path, &data)?;
+        (path, 0, Vec::new())
+    };
+
+    Ok(ExportResult {
+        path: path.display().to_string(),
+        count: records.len(),
+        images,
+        missing_images,
+    })
+}
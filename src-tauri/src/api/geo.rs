@@ -0,0 +1,283 @@
+// src-tauri/src/api/geo.rs
+use crate::api::artifacts::{push_filter_conditions, row_to_artifact, SearchParams, ARTIFACT_COLUMNS, IMAGE_MISSING_COLUMN};
+use crate::api::auth::{optional_user_id, AuthState};
+use crate::db::models::ArtifactWithFavorite;
+use crate::db::DbState;
+use crate::error::{Error, Result};
+use crate::geo::cluster::{cluster_points, density_grid, ClusterPoint, DensityGrid, MapCluster};
+use crate::geo::{geocode_artifact, gazetteer, BoundingBox, GeoPoint, Place};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use tauri::State;
+
+const DEFAULT_MAP_LIMIT: usize = 500;
+const MAX_MAP_LIMIT: usize = 2000;
+const MAX_RADIUS_KM: f64 = 5000.0;
+const MAX_ZOOM: u8 = 22;
+const DEFAULT_HEATMAP_COLS: u32 = 64;
+const MAX_HEATMAP_COLS: u32 = 256;
+
+/// 用空间索引筛选范围内的文物
+fn push_bbox_condition(bbox: &BoundingBox, conditions: &mut Vec<String>, bind_values: &mut Vec<String>) {
+    conditions.push(
+        "a.id IN (SELECT id FROM artifacts_rtree WHERE max_lat >= ? AND min_lat <= ? AND max_lng >= ? AND min_lng <= ?)"
+            .to_string(),
+    );
+    bind_values.extend([bbox.south, bbox.north, bbox.west, bbox.east].map(|v| v.to_string()));
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MapQuery {
+    /// 只返回该范围内的文物
+    pub bbox: Option<BoundingBox>,
+    /// 与 radius_km 一起使用，按距离由近到远返回
+    pub center: Option<GeoPoint>,
+    pub radius_km: Option<f64>,
+    /// 与 get_artifacts 相同的筛选条件（忽略分页和排序）
+    #[serde(default)]
+    pub params: Option<SearchParams>,
+    /// 默认 500，最大 2000
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MapArtifact {
+    #[serde(flatten)]
+    pub artifact: ArtifactWithFavorite,
+    /// 与 center 的距离（公里）
+    pub distance_km: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MapArtifacts {
+    pub items: Vec<MapArtifact>,
+    /// 符合条件但超出 limit 未返回的数量
+    pub truncated: usize,
+    /// 符合筛选条件但没有坐标的文物数量
+    pub unlocated: i64,
+}
+
+/// 查询指定范围或半径内有坐标的文物，供地图展示
+#[tauri::command]
+pub async fn get_map_artifacts(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    query: MapQuery,
+    token: Option<String>,
+) -> Result<MapArtifacts> {
+    let pool = db.pool()?;
+    let user_id = optional_user_id(&pool, &auth_state, token.as_deref()).await?;
+
+    let radius = match (query.center, query.radius_km) {
+        (Some(center), Some(radius_km)) => {
+            center.validate("center")?;
+            if !(radius_km > 0.0 && radius_km <= MAX_RADIUS_KM) {
+                return Err(Error::invalid_field(
+                    "radius_km",
+                    format!("半径需大于 0 且不超过 {} 公里", MAX_RADIUS_KM),
+                ));
+            }
+            Some((center, radius_km))
+        }
+        (None, None) => None,
+        _ => return Err(Error::invalid_field("radius_km", "center 和 radius_km 需同时提供")),
+    };
+    if let Some(bbox) = &query.bbox {
+        bbox.validate()?;
+    }
+
+    let mut conditions = Vec::new();
+    let mut bind_values: Vec<String> = Vec::new();
+    if let Some(params) = &query.params {
+        push_filter_conditions(params, &mut conditions, &mut bind_values);
+    }
+    let (filter_count, filter_binds) = (conditions.len(), bind_values.len());
+
+    // 先按范围粗筛，半径查询再按实际距离过滤
+    let ranges = query.bbox.iter().copied().chain(radius.map(|(c, r)| BoundingBox::around(&c, r)));
+    for bbox in ranges {
+        push_bbox_condition(&bbox, &mut conditions, &mut bind_values);
+    }
+    conditions.push("a.latitude IS NOT NULL AND a.longitude IS NOT NULL".to_string());
+
+    let sql = format!(
+        r#"
+        SELECT
+            {},
+            CASE WHEN uf.id IS NOT NULL THEN 1 ELSE 0 END as is_favorite,
+            {}
+        FROM artifacts a
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        WHERE {}
+        ORDER BY a.id
+        "#,
+        ARTIFACT_COLUMNS,
+        IMAGE_MISSING_COLUMN,
+        conditions.join(" AND ")
+    );
+    let mut sql_query = sqlx::query(&sql).bind(user_id);
+    for value in &bind_values {
+        sql_query = sql_query.bind(value);
+    }
+    let rows = sql_query.fetch_all(&pool).await?;
+
+    let mut items: Vec<MapArtifact> = rows
+        .iter()
+        .map(|row| {
+            let artifact = row_to_artifact(row);
+            let distance_km = radius.and_then(|(center, _)| {
+                let point = GeoPoint {
+                    latitude: artifact.latitude?,
+                    longitude: artifact.longitude?,
+                };
+                Some(center.distance_km(&point))
+            });
+            MapArtifact { artifact, distance_km }
+        })
+        .collect();
+    if let Some((_, radius_km)) = radius {
+        items.retain(|item| item.distance_km.is_some_and(|d| d <= radius_km));
+        items.sort_by(|a, b| a.distance_km.unwrap_or(0.0).total_cmp(&b.distance_km.unwrap_or(0.0)));
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_MAP_LIMIT).clamp(1, MAX_MAP_LIMIT);
+    let truncated = items.len().saturating_sub(limit);
+    items.truncate(limit);
+
+    // 没有坐标的文物只按筛选条件统计
+    let mut unlocated_conditions = conditions[..filter_count].to_vec();
+    unlocated_conditions.push("(a.latitude IS NULL OR a.longitude IS NULL)".to_string());
+    let unlocated_sql = format!(
+        r#"
+        SELECT COUNT(*) as total
+        FROM artifacts a
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        WHERE {}
+        "#,
+        unlocated_conditions.join(" AND ")
+    );
+    let mut count_query = sqlx::query(&unlocated_sql).bind(user_id);
+    for value in &bind_values[..filter_binds] {
+        count_query = count_query.bind(value);
+    }
+    let unlocated: i64 = count_query.fetch_one(&pool).await?.get("total");
+
+    Ok(MapArtifacts { items, truncated, unlocated })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterQuery {
+    /// 当前视野
+    pub bbox: BoundingBox,
+    /// 地图缩放级别（0–22），决定聚合的粒度
+    pub zoom: u8,
+    #[serde(default)]
+    pub params: Option<SearchParams>,
+    /// 热力图的列数，默认 64，最大 256
+    pub heatmap_cols: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MapClusters {
+    pub clusters: Vec<MapCluster>,
+    pub heatmap: DensityGrid,
+    /// 视野内符合条件的文物总数
+    pub total: usize,
+}
+
+/// 按视野和缩放级别聚合文物，并返回热力图密度网格
+#[tauri::command]
+pub async fn get_map_clusters(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    query: ClusterQuery,
+    token: Option<String>,
+) -> Result<MapClusters> {
+    let pool = db.pool()?;
+    let user_id = optional_user_id(&pool, &auth_state, token.as_deref()).await?;
+
+    query.bbox.validate()?;
+    if query.zoom > MAX_ZOOM {
+        return Err(Error::invalid_field("zoom", format!("缩放级别需在 0 到 {} 之间", MAX_ZOOM)));
+    }
+    let heatmap_cols = query.heatmap_cols.unwrap_or(DEFAULT_HEATMAP_COLS).clamp(1, MAX_HEATMAP_COLS);
+
+    let mut conditions = Vec::new();
+    let mut bind_values: Vec<String> = Vec::new();
+    if let Some(params) = &query.params {
+        push_filter_conditions(params, &mut conditions, &mut bind_values);
+    }
+    push_bbox_condition(&query.bbox, &mut conditions, &mut bind_values);
+
+    let sql = format!(
+        r#"
+        SELECT a.id, a.title, a.latitude, a.longitude, a.category, a.dynasty
+        FROM artifacts a
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        WHERE {}
+        "#,
+        conditions.join(" AND ")
+    );
+    let mut sql_query = sqlx::query(&sql).bind(user_id);
+    for value in &bind_values {
+        sql_query = sql_query.bind(value);
+    }
+    let points: Vec<ClusterPoint> = sql_query
+        .fetch_all(&pool)
+        .await?
+        .iter()
+        .map(|row| ClusterPoint {
+            id: row.get("id"),
+            title: row.get("title"),
+            point: GeoPoint {
+                latitude: row.get("latitude"),
+                longitude: row.get("longitude"),
+            },
+            category: row.get("category"),
+            dynasty: row.get("dynasty"),
+        })
+        .collect();
+
+    Ok(MapClusters {
+        clusters: cluster_points(&points, query.zoom),
+        heatmap: density_grid(&points, &query.bbox, heatmap_cols),
+        total: points.len(),
+    })
+}
+
+/// 按内置地名表解析地点文本，如「河南安阳殷墟」；无法识别时返回空
+#[tauri::command]
+pub async fn geocode_location(text: String) -> Result<Option<Place>> {
+    Ok(gazetteer().geocode(&text).cloned())
+}
+
+/// 为尚无坐标的文物按地名补全坐标，返回补全的数量
+pub async fn backfill_coordinates(pool: &SqlitePool) -> Result<usize> {
+    let rows = sqlx::query(
+        "SELECT id, location, discovery_location FROM artifacts WHERE latitude IS NULL OR longitude IS NULL",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut tx = pool.begin().await?;
+    let mut updated = 0;
+    for row in &rows {
+        let discovery_location: String = row.get("discovery_location");
+        let location: String = row.get("location");
+        let Some(coordinates) = geocode_artifact(&discovery_location, &location) else {
+            continue;
+        };
+        sqlx::query(
+            "UPDATE artifacts SET latitude = ?, longitude = ?, location_uncertainty_m = ? WHERE id = ?",
+        )
+        .bind(coordinates.point.latitude)
+        .bind(coordinates.point.longitude)
+        .bind(coordinates.uncertainty_m)
+        .bind(row.get::<i64, _>("id"))
+        .execute(&mut *tx)
+        .await?;
+        updated += 1;
+    }
+    tx.commit().await?;
+    Ok(updated)
+}
@@ -0,0 +1,19 @@
+// src-tauri/src/api/iiif.rs
+use crate::db::DbState;
+use crate::error::Result;
+use crate::iiif::artifact_manifest;
+use crate::storage::MediaStore;
+use tauri::State;
+
+/// 获取文物的 IIIF Presentation 3.0 清单
+///
+/// 清单中的图像服务指向本地 `iiif` 协议，可直接交给 IIIF viewer 使用。
+#[tauri::command]
+pub async fn get_iiif_manifest(
+    db: State<'_, DbState>,
+    store: State<'_, MediaStore>,
+    artifact_id: i64,
+) -> Result<serde_json::Value> {
+    let pool = db.pool()?;
+    artifact_manifest(&pool, &store, artifact_id).await
+}
@@ -0,0 +1,840 @@
+// src-tauri/src/api/import.rs
+use crate::api::auth::AuthState;
+use crate::api::media::refresh_primary_image;
+use crate::api::roles::{ensure_permission, Permission};
+use crate::chronology::Chronology;
+use crate::db::models::NewArtifact;
+use crate::db::DbState;
+use crate::error::{Error, Result};
+use crate::geo::resolve_coordinates;
+use crate::storage::MediaStore;
+use crate::validation::Validator;
+use crate::vocabulary::{TermValue, Vocabularies, Vocabulary};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqliteConnection};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+// 单次导入的最大行数
+const MAX_IMPORT_ROWS: usize = 5000;
+
+const MAX_ACCESSION_CHARS: usize = 64;
+
+// 字段名及可识别的列名（不区分大小写），兼容常见的中文表头
+const COLUMN_ALIASES: &[(&str, &[&str])] = &[
+    ("accession_number", &["accession_number", "accession number", "accession_no", "藏品编号", "登录号"]),
+    ("title", &["title", "名称", "文物名称", "标题"]),
+    ("image_path", &["image_path", "image", "图片"]),
+    ("period", &["period", "时期", "年代"]),
+    ("dynasty", &["dynasty", "朝代"]),
+    ("location", &["location", "所在地"]),
+    ("description", &["description", "简介"]),
+    ("detailed_description", &["detailed_description", "详细介绍", "详细描述"]),
+    ("material", &["material", "材质"]),
+    ("dimensions", &["dimensions", "尺寸"]),
+    ("discovery_location", &["discovery_location", "出土地点", "发现地点"]),
+    ("collection", &["collection", "收藏机构", "现藏"]),
+    ("category", &["category", "类别", "分类"]),
+    ("latitude", &["latitude", "lat", "纬度"]),
+    ("longitude", &["longitude", "lng", "lon", "经度"]),
+    ("location_uncertainty_m", &["location_uncertainty_m", "uncertainty", "坐标误差"]),
+    ("date_from", &["date_from", "起始年份"]),
+    ("date_to", &["date_to", "终止年份"]),
+];
+
+// 缺少这些列时整个文件无法导入
+const REQUIRED_COLUMNS: &[&str] = &["accession_number", "title"];
+
+// 更新已有文物时可写入的列，只写入导入文件中出现的字段
+const UPDATE_COLUMNS: &[&str] = &[
+    "title",
+    "image_path",
+    "period",
+    "dynasty",
+    "location",
+    "description",
+    "detailed_description",
+    "material",
+    "dimensions",
+    "discovery_location",
+    "collection",
+    "category",
+    "latitude",
+    "longitude",
+    "location_uncertainty_m",
+    "date_from",
+    "date_to",
+    "category_id",
+    "material_id",
+    "collection_id",
+];
+
+// 坐标和年份各自作为一组写入，任一列出现即更新整组；
+// 只更新了推断所依据的地名或朝代、年代时，按新的文本重新推断
+const COORDINATE_COLUMNS: &[&str] = &["latitude", "longitude", "location_uncertainty_m"];
+const COORDINATE_SOURCES: &[&str] = &["location", "discovery_location"];
+const DATE_COLUMNS: &[&str] = &["date_from", "date_to"];
+const DATE_SOURCES: &[&str] = &["dynasty", "period"];
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportRequest {
+    pub format: ImportFormat,
+    /// 文件内容
+    pub content: String,
+    /// 只校验并预览，不写入数据库
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportAction {
+    Create,
+    Update,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowResult {
+    pub row: usize,
+    pub accession_number: String,
+    pub title: String,
+    pub action: ImportAction,
+    /// 预览时为已有文物的ID（新增为空），导入后为写入的文物ID
+    pub artifact_id: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportRowError {
+    /// CSV 为文件中的行号（表头为第 1 行），JSON 为数组中的序号（从 1 开始）
+    pub row: usize,
+    pub field: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// 是否已写入数据库；存在错误行时整个文件都不会写入
+    pub committed: bool,
+    pub total: usize,
+    pub created: usize,
+    pub updated: usize,
+    pub rows: Vec<ImportRowResult>,
+    pub errors: Vec<ImportRowError>,
+    /// 无法识别、被忽略的列
+    pub ignored_columns: Vec<String>,
+}
+
+// 一行原始数据：字段名 -> 文本值
+type RawRecord = HashMap<&'static str, String>;
+
+struct ParsedRows {
+    records: Vec<(usize, RawRecord)>,
+    errors: Vec<ImportRowError>,
+    ignored_columns: Vec<String>,
+}
+
+// 藏品编号对应的已有文物，保留推断坐标和年份所需的字段
+#[derive(Debug, Clone, Default)]
+struct ExistingArtifact {
+    id: i64,
+    dynasty: String,
+    period: String,
+    location: String,
+    discovery_location: String,
+}
+
+struct ImportedArtifact {
+    row: usize,
+    accession_number: String,
+    // 藏品编号对应的已有文物
+    existing: Option<i64>,
+    artifact: NewArtifact,
+    // 类别、材质、收藏机构的词条 id
+    term_ids: [Option<i64>; 3],
+    // 更新已有文物时要写入的列
+    columns: HashSet<&'static str>,
+}
+
+fn canonical_column(name: &str) -> Option<&'static str> {
+    let name = name.trim().to_lowercase();
+    COLUMN_ALIASES
+        .iter()
+        .find(|(_, aliases)| aliases.iter().any(|alias| *alias == name))
+        .map(|(field, _)| *field)
+}
+
+fn row_error(row: usize, field: Option<&str>, message: impl Into<String>) -> ImportRowError {
+    ImportRowError {
+        row,
+        field: field.map(str::to_string),
+        message: message.into(),
+    }
+}
+
+fn check_columns(columns: &[Option<&'static str>]) -> Result<()> {
+    for required in REQUIRED_COLUMNS {
+        if !columns.contains(&Some(*required)) {
+            return Err(Error::invalid_field("content", format!("缺少必需的列: {}", required)));
+        }
+    }
+    Ok(())
+}
+
+fn check_row_count(count: usize) -> Result<()> {
+    if count > MAX_IMPORT_ROWS {
+        return Err(Error::invalid_field(
+            "content",
+            format!("单次最多导入 {} 行，请拆分文件", MAX_IMPORT_ROWS),
+        ));
+    }
+    Ok(())
+}
+
+fn parse_csv(content: &str) -> Result<ParsedRows> {
+    // Excel 导出的 CSV 通常带有 BOM
+    let content = content.trim_start_matches('\u{feff}');
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| Error::invalid_field("content", format!("CSV 表头格式错误: {}", e)))?
+        .clone();
+    let columns: Vec<Option<&'static str>> = headers.iter().map(canonical_column).collect();
+    check_columns(&columns)?;
+
+    let ignored_columns = headers
+        .iter()
+        .zip(&columns)
+        .filter(|(_, column)| column.is_none())
+        .map(|(header, _)| header.to_string())
+        .collect();
+
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+    for (index, result) in reader.records().enumerate() {
+        check_row_count(index + 1)?;
+        match result {
+            Ok(record) => {
+                let row = record.position().map(|p| p.line() as usize).unwrap_or(index + 2);
+                let raw = columns
+                    .iter()
+                    .zip(record.iter())
+                    .filter_map(|(column, value)| column.map(|field| (field, value.to_string())))
+                    .collect();
+                records.push((row, raw));
+            }
+            Err(e) => {
+                let row = e.position().map(|p| p.line() as usize).unwrap_or(index + 2);
+                errors.push(row_error(row, None, format!("CSV 格式错误: {}", e)));
+            }
+        }
+    }
+
+    Ok(ParsedRows { records, errors, ignored_columns })
+}
+
+fn parse_json(content: &str) -> Result<ParsedRows> {
+    let items: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_str(content)
+        .map_err(|e| Error::invalid_field("content", format!("JSON 格式错误（应为对象数组）: {}", e)))?;
+    check_row_count(items.len())?;
+
+    let mut ignored_columns: Vec<String> = Vec::new();
+    let mut records = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, item) in items.into_iter().enumerate() {
+        let row = index + 1;
+        let mut raw = RawRecord::new();
+        let mut valid = true;
+
+        for (key, value) in item {
+            let Some(field) = canonical_column(&key) else {
+                if !ignored_columns.contains(&key) {
+                    ignored_columns.push(key);
+                }
+                continue;
+            };
+            // null 视为空值，与 CSV 中的空单元格相同
+            let text = match value {
+                serde_json::Value::Null => String::new(),
+                serde_json::Value::String(s) => s.trim().to_string(),
+                serde_json::Value::Number(n) => n.to_string(),
+                serde_json::Value::Bool(b) => b.to_string(),
+                _ => {
+                    errors.push(row_error(row, Some(field), format!("{} 必须是文本", field)));
+                    valid = false;
+                    continue;
+                }
+            };
+            raw.insert(field, text);
+        }
+
+        if valid {
+            records.push((row, raw));
+        }
+    }
+
+    Ok(ParsedRows { records, errors, ignored_columns })
+}
+
+// 坐标、年份、词条等校验错误转为行错误
+fn field_error(row: usize, error: Error) -> ImportRowError {
+    match error {
+        Error::Validation { message, field } => row_error(row, field.as_deref(), message),
+        e => row_error(row, None, e.to_string()),
+    }
+}
+
+// 校验一行数据，返回该行的全部错误
+//
+// 新增文物时校验全部字段；更新已有文物时只校验并写入文件中出现的字段，
+// 图片留空表示保留原有图片
+fn validate_record(
+    row: usize,
+    mut raw: RawRecord,
+    existing: Option<&ExistingArtifact>,
+    chronology: &Chronology,
+    vocabularies: &Vocabularies,
+) -> std::result::Result<ImportedArtifact, Vec<ImportRowError>> {
+    let present: HashSet<&'static str> = raw.keys().copied().collect();
+    let provided = |field: &str| existing.is_none() || present.contains(field);
+    // 文件中没有的朝代、年代和地名取已有的值，只用于重新推断年份和坐标，不会写入
+    if let Some(existing) = existing {
+        for (field, value) in [
+            ("dynasty", &existing.dynasty),
+            ("period", &existing.period),
+            ("location", &existing.location),
+            ("discovery_location", &existing.discovery_location),
+        ] {
+            raw.entry(field).or_insert_with(|| value.clone());
+        }
+    }
+    let mut take = |field: &str| raw.remove(field).unwrap_or_default();
+    let accession_number = take("accession_number");
+    let title = take("title");
+    let image_path = take("image_path");
+    let category = take("category");
+    let material = take("material");
+    let collection = take("collection");
+    let dynasty = take("dynasty");
+    let period = take("period");
+    let date_from = take("date_from");
+    let date_to = take("date_to");
+    let latitude = take("latitude");
+    let longitude = take("longitude");
+    let uncertainty = take("location_uncertainty_m");
+    let location = take("location");
+    let discovery_location = take("discovery_location");
+    let description = take("description");
+    let detailed_description = take("detailed_description");
+    let dimensions = take("dimensions");
+
+    let mut errors = Vec::new();
+
+    if accession_number.is_empty() {
+        errors.push(row_error(row, Some("accession_number"), "藏品编号不能为空"));
+    } else if accession_number.chars().count() > MAX_ACCESSION_CHARS {
+        errors.push(row_error(
+            row,
+            Some("accession_number"),
+            format!("藏品编号不能超过 {} 个字符", MAX_ACCESSION_CHARS),
+        ));
+    }
+
+    // 必填项、长度、图片文件名和尺寸与 create_artifact 的校验相同
+    let mut columns = HashSet::new();
+    let mut validator = Validator::new();
+    for (field, value) in [
+        ("title", &title),
+        ("period", &period),
+        ("dynasty", &dynasty),
+        ("location", &location),
+        ("description", &description),
+        ("detailed_description", &detailed_description),
+        ("discovery_location", &discovery_location),
+    ] {
+        if provided(field) {
+            validator.text(field, value);
+            columns.insert(field);
+        }
+    }
+    let image_path = if existing.is_none() || !image_path.trim().is_empty() {
+        columns.insert("image_path");
+        validator.image_path(&image_path)
+    } else {
+        String::new()
+    };
+    if provided("dimensions") {
+        validator.dimensions(&dimensions);
+        columns.insert("dimensions");
+    }
+    errors.extend(
+        validator
+            .into_errors()
+            .into_iter()
+            .map(|e| row_error(row, Some(&e.field), e.message)),
+    );
+
+    // 类别、材质、收藏机构需为词表中的词条
+    let mut resolve_term = |vocabulary: Vocabulary, value: &str| -> TermValue {
+        let (text_column, id_column) = vocabulary.columns();
+        if !provided(text_column) {
+            return TermValue::default();
+        }
+        columns.insert(text_column);
+        columns.insert(id_column);
+        vocabularies.resolve(vocabulary, value).unwrap_or_else(|e| {
+            errors.push(field_error(row, e));
+            TermValue::default()
+        })
+    };
+    let category = resolve_term(Vocabulary::Category, &category);
+    let material = resolve_term(Vocabulary::Material, &material);
+    let collection = resolve_term(Vocabulary::Collection, &collection);
+
+    let mut parse_number = |field: &str, value: &str| -> Option<f64> {
+        if value.is_empty() {
+            return None;
+        }
+        let number = value.parse().ok().filter(|n: &f64| n.is_finite());
+        if number.is_none() {
+            errors.push(row_error(row, Some(field), format!("「{}」不是有效的数字", value)));
+        }
+        number
+    };
+    let latitude = parse_number("latitude", &latitude);
+    let longitude = parse_number("longitude", &longitude);
+    let uncertainty = parse_number("location_uncertainty_m", &uncertainty);
+    let coordinates = if [COORDINATE_COLUMNS, COORDINATE_SOURCES].concat().iter().any(|c| provided(c)) {
+        columns.extend(COORDINATE_COLUMNS);
+        resolve_coordinates(latitude, longitude, uncertainty, &discovery_location, &location).unwrap_or_else(|e| {
+            errors.push(field_error(row, e));
+            None
+        })
+    } else {
+        None
+    };
+
+    let mut parse_year = |field: &str, value: &str| -> Option<i64> {
+        if value.is_empty() {
+            return None;
+        }
+        let year = value.parse().ok();
+        if year.is_none() {
+            errors.push(row_error(row, Some(field), format!("「{}」不是有效的年份", value)));
+        }
+        year
+    };
+    let date_from = parse_year("date_from", &date_from);
+    let date_to = parse_year("date_to", &date_to);
+    let dates = if [DATE_COLUMNS, DATE_SOURCES].concat().iter().any(|c| provided(c)) {
+        columns.extend(DATE_COLUMNS);
+        chronology.resolve(date_from, date_to, &dynasty, &period).unwrap_or_else(|e| {
+            errors.push(field_error(row, e));
+            None
+        })
+    } else {
+        None
+    };
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(ImportedArtifact {
+        row,
+        accession_number,
+        existing: existing.map(|e| e.id),
+        artifact: NewArtifact {
+            title,
+            image_path,
+            period,
+            dynasty,
+            location,
+            description,
+            detailed_description,
+            material: material.code,
+            dimensions,
+            discovery_location,
+            collection: collection.code,
+            category: category.code,
+            latitude: coordinates.map(|c| c.point.latitude),
+            longitude: coordinates.map(|c| c.point.longitude),
+            location_uncertainty_m: coordinates.and_then(|c| c.uncertainty_m),
+            date_from: dates.map(|d| d.from),
+            date_to: dates.map(|d| d.to),
+        },
+        term_ids: [category.id, material.id, collection.id],
+        columns,
+    })
+}
+
+async fn find_by_accession(conn: &mut SqliteConnection, accession_number: &str) -> Result<Option<ExistingArtifact>> {
+    let row = sqlx::query(
+        "SELECT id, dynasty, period, location, discovery_location FROM artifacts WHERE accession_number = ?",
+    )
+    .bind(accession_number)
+    .fetch_optional(conn)
+    .await?;
+    Ok(row.map(|row| ExistingArtifact {
+        id: row.get("id"),
+        dynasty: row.get("dynasty"),
+        period: row.get("period"),
+        location: row.get("location"),
+        discovery_location: row.get("discovery_location"),
+    }))
+}
+
+// 按藏品编号新增或更新，返回文物ID；更新时只写入文件中出现的列
+async fn upsert_artifact(conn: &mut SqliteConnection, item: &ImportedArtifact) -> Result<i64> {
+    let artifact = &item.artifact;
+    let [category_id, material_id, collection_id] = item.term_ids;
+
+    if let Some(id) = item.existing {
+        let columns: Vec<&str> = UPDATE_COLUMNS
+            .iter()
+            .copied()
+            .filter(|column| item.columns.contains(column))
+            .collect();
+        let assignments: String = columns.iter().map(|column| format!("{} = ?, ", column)).collect();
+        let sql = format!(
+            "UPDATE artifacts SET {}updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+            assignments
+        );
+
+        let mut query = sqlx::query(&sql);
+        for column in columns {
+            query = match column {
+                "title" => query.bind(&artifact.title),
+                "image_path" => query.bind(&artifact.image_path),
+                "period" => query.bind(&artifact.period),
+                "dynasty" => query.bind(&artifact.dynasty),
+                "location" => query.bind(&artifact.location),
+                "description" => query.bind(&artifact.description),
+                "detailed_description" => query.bind(&artifact.detailed_description),
+                "material" => query.bind(&artifact.material),
+                "dimensions" => query.bind(&artifact.dimensions),
+                "discovery_location" => query.bind(&artifact.discovery_location),
+                "collection" => query.bind(&artifact.collection),
+                "category" => query.bind(&artifact.category),
+                "latitude" => query.bind(artifact.latitude),
+                "longitude" => query.bind(artifact.longitude),
+                "location_uncertainty_m" => query.bind(artifact.location_uncertainty_m),
+                "date_from" => query.bind(artifact.date_from),
+                "date_to" => query.bind(artifact.date_to),
+                "category_id" => query.bind(category_id),
+                "material_id" => query.bind(material_id),
+                "collection_id" => query.bind(collection_id),
+                _ => unreachable!("未知的列: {}", column),
+            };
+        }
+        query.bind(id).execute(conn).await?;
+        return Ok(id);
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO artifacts (
+            accession_number, title, image_path, period, dynasty, location, description,
+            detailed_description, material, dimensions, discovery_location,
+            collection, category, latitude, longitude, location_uncertainty_m,
+            date_from, date_to, category_id, material_id, collection_id
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&item.accession_number)
+    .bind(&artifact.title)
+    .bind(&artifact.image_path)
+    .bind(&artifact.period)
+    .bind(&artifact.dynasty)
+    .bind(&artifact.location)
+    .bind(&artifact.description)
+    .bind(&artifact.detailed_description)
+    .bind(&artifact.material)
+    .bind(&artifact.dimensions)
+    .bind(&artifact.discovery_location)
+    .bind(&artifact.collection)
+    .bind(&artifact.category)
+    .bind(artifact.latitude)
+    .bind(artifact.longitude)
+    .bind(artifact.location_uncertainty_m)
+    .bind(artifact.date_from)
+    .bind(artifact.date_to)
+    .bind(category_id)
+    .bind(material_id)
+    .bind(collection_id)
+    .execute(conn)
+    .await?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// 从 CSV 或 JSON 目录批量导入文物，按藏品编号（accession_number）新增或更新
+///
+/// 逐行校验并报告错误；存在错误行或 `dry_run` 时不写入，只返回预览。全部写入在同一事务中完成。
+#[tauri::command]
+pub async fn import_artifacts(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    store: State<'_, MediaStore>,
+    token: String,
+    request: ImportRequest,
+) -> Result<ImportReport> {
+    let pool = db.pool()?;
+    // 导入会新增和覆盖文物，两项权限都需要
+    ensure_permission(&pool, &auth_state, &token, Permission::CreateArtifact).await?;
+    ensure_permission(&pool, &auth_state, &token, Permission::UpdateArtifact).await?;
+
+    let parsed = match request.format {
+        ImportFormat::Csv => parse_csv(&request.content)?,
+        ImportFormat::Json => parse_json(&request.content)?,
+    };
+    let total = parsed.records.len() + parsed.errors.len();
+    let mut errors = parsed.errors;
+
+    // 逐行校验，并检查文件内的藏品编号是否重复
+    let mut items = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let chronology = Chronology::load(&pool).await?;
+    let vocabularies = Vocabularies::load(&pool).await?;
+    let mut tx = pool.begin().await?;
+    for (row, raw) in parsed.records {
+        // 新增和更新的校验规则不同，先按藏品编号查找已有文物
+        let existing = match raw.get("accession_number").map(|a| a.trim()) {
+            Some(accession_number) if !accession_number.is_empty() => {
+                find_by_accession(&mut tx, accession_number).await?
+            }
+            _ => None,
+        };
+        match validate_record(row, raw, existing.as_ref(), &chronology, &vocabularies) {
+            Ok(item) => {
+                if let Some(first_row) = seen.get(&item.accession_number) {
+                    errors.push(row_error(
+                        row,
+                        Some("accession_number"),
+                        format!("藏品编号 {} 与第 {} 行重复", item.accession_number, first_row),
+                    ));
+                    continue;
+                }
+                seen.insert(item.accession_number.clone(), row);
+                items.push(item);
+            }
+            Err(row_errors) => errors.extend(row_errors),
+        }
+    }
+    errors.sort_by_key(|e| e.row);
+
+    let commit = !request.dry_run && errors.is_empty();
+    let mut rows = Vec::with_capacity(items.len());
+
+    for item in &items {
+        let action = if item.existing.is_some() { ImportAction::Update } else { ImportAction::Create };
+        let artifact_id = if commit {
+            let id = upsert_artifact(&mut tx, item).await?;
+            // 导入的图片路径可能尚未上传，同步主图的缺失标记
+            refresh_primary_image(&mut tx, &store, id).await?;
+            Some(id)
+        } else {
+            item.existing
+        };
+
+        rows.push(ImportRowResult {
+            row: item.row,
+            accession_number: item.accession_number.clone(),
+            title: item.artifact.title.clone(),
+            action,
+            artifact_id,
+        });
+    }
+
+    if commit {
+        tx.commit().await?;
+    }
+
+    let created = rows.iter().filter(|r| matches!(r.action, ImportAction::Create)).count();
+    Ok(ImportReport {
+        dry_run: request.dry_run,
+        committed: commit,
+        total,
+        created,
+        updated: rows.len() - created,
+        rows,
+        errors,
+        ignored_columns: parsed.ignored_columns,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::memory_pool;
+
+    async fn load() -> (Chronology, Vocabularies) {
+        let pool = memory_pool().await;
+        (Chronology::load(&pool).await.unwrap(), Vocabularies::load(&pool).await.unwrap())
+    }
+
+    fn record(fields: &[(&'static str, &str)]) -> RawRecord {
+        fields.iter().map(|(field, value)| (*field, value.to_string())).collect()
+    }
+
+    fn new_record() -> RawRecord {
+        record(&[
+            ("accession_number", "A-001"),
+            ("title", "后母戊鼎"),
+            ("image_path", "houmuwu.jpg"),
+            ("period", ""),
+            ("dynasty", "商"),
+            ("location", ""),
+            ("description", ""),
+            ("detailed_description", ""),
+            ("material", "青铜"),
+            ("dimensions", "高133cm"),
+            ("discovery_location", "河南安阳"),
+            ("collection", "中国国家博物馆"),
+            ("category", "青铜器"),
+        ])
+    }
+
+    fn existing() -> ExistingArtifact {
+        ExistingArtifact {
+            id: 7,
+            dynasty: "商".to_string(),
+            period: "".to_string(),
+            location: "".to_string(),
+            discovery_location: "河南安阳".to_string(),
+        }
+    }
+
+    fn error_fields(errors: &[ImportRowError]) -> Vec<&str> {
+        errors.iter().filter_map(|e| e.field.as_deref()).collect()
+    }
+
+    #[test]
+    fn parse_csv_maps_aliases_and_reports_lines() {
+        let content = "\u{feff}藏品编号,名称,备注\nA-001, 后母戊鼎 ,x\n\"A-002\",\"莲鹤方壶\",y,extra\nA-003,四羊方尊,z\n";
+        let parsed = parse_csv(content).unwrap();
+
+        assert_eq!(parsed.ignored_columns, ["备注"]);
+        assert_eq!(parsed.records.len(), 2);
+        let (row, raw) = &parsed.records[0];
+        assert_eq!(*row, 2);
+        assert_eq!(raw["accession_number"], "A-001");
+        assert_eq!(raw["title"], "后母戊鼎");
+        assert_eq!(parsed.records[1].0, 4);
+        // 列数不一致的行报告为格式错误
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].row, 3);
+    }
+
+    #[test]
+    fn parse_csv_requires_accession_and_title() {
+        let error = parse_csv("title,dynasty\n鼎,商\n").err().unwrap();
+        assert!(error.to_string().contains("accession_number"), "{}", error);
+    }
+
+    #[test]
+    fn parse_json_converts_values_to_text() {
+        let content = r#"[
+            {"accession_number": "A-001", "title": " 鼎 ", "latitude": 36.1, "date_from": null, "note": 1},
+            {"accession_number": "A-002", "title": ["鼎"]}
+        ]"#;
+        let parsed = parse_json(content).unwrap();
+
+        assert_eq!(parsed.ignored_columns, ["note"]);
+        assert_eq!(parsed.records.len(), 1);
+        let (row, raw) = &parsed.records[0];
+        assert_eq!(*row, 1);
+        assert_eq!(raw["title"], "鼎");
+        assert_eq!(raw["latitude"], "36.1");
+        // null 视为出现但为空
+        assert_eq!(raw["date_from"], "");
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!((parsed.errors[0].row, parsed.errors[0].field.as_deref()), (2, Some("title")));
+
+        assert!(parse_json(r#"{"title": "鼎"}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn validate_record_checks_every_field_of_new_rows() {
+        let (chronology, vocabularies) = load().await;
+
+        let item = validate_record(2, new_record(), None, &chronology, &vocabularies).unwrap();
+        assert_eq!(item.existing, None);
+        assert_eq!(item.artifact.category, "bronze");
+        assert_eq!((item.artifact.date_from, item.artifact.date_to), (Some(-1600), Some(-1047)));
+        assert!(item.artifact.latitude.is_some());
+        assert!(item.term_ids.iter().all(Option::is_some));
+
+        let mut raw = new_record();
+        raw.remove("image_path");
+        raw.insert("category", "未知".to_string());
+        raw.insert("dimensions", "很大".to_string());
+        raw.insert("date_from", "-500".to_string());
+        let errors = validate_record(2, raw, None, &chronology, &vocabularies).err().unwrap();
+        let mut fields = error_fields(&errors);
+        fields.sort();
+        assert_eq!(fields, ["category", "date_from", "dimensions", "image_path"]);
+        assert!(errors.iter().all(|e| e.row == 2));
+    }
+
+    #[tokio::test]
+    async fn validate_record_only_writes_columns_present_on_update() {
+        let (chronology, vocabularies) = load().await;
+        let existing = existing();
+
+        let raw = record(&[("accession_number", "A-001"), ("title", "后母戊方鼎"), ("image_path", "")]);
+        let item = validate_record(3, raw, Some(&existing), &chronology, &vocabularies).unwrap();
+        assert_eq!(item.existing, Some(7));
+        assert_eq!(item.columns, HashSet::from(["title"]));
+    }
+
+    #[tokio::test]
+    async fn validate_record_rederives_dates_when_dynasty_changes() {
+        let (chronology, vocabularies) = load().await;
+        let existing = ExistingArtifact {
+            period: "乾隆年间".to_string(),
+            ..existing()
+        };
+
+        let raw = record(&[("accession_number", "A-001"), ("dynasty", "清")]);
+        let item = validate_record(3, raw, Some(&existing), &chronology, &vocabularies).unwrap();
+        assert_eq!(item.columns, HashSet::from(["dynasty", "date_from", "date_to"]));
+        // 年代沿用已有的「乾隆年间」
+        assert_eq!((item.artifact.date_from, item.artifact.date_to), (Some(1736), Some(1795)));
+
+        // 明确给出的年份优先
+        let raw = record(&[("accession_number", "A-001"), ("dynasty", "清"), ("date_from", "1700"), ("date_to", "1710")]);
+        let item = validate_record(3, raw, Some(&existing), &chronology, &vocabularies).unwrap();
+        assert_eq!((item.artifact.date_from, item.artifact.date_to), (Some(1700), Some(1710)));
+    }
+
+    #[tokio::test]
+    async fn validate_record_rederives_coordinates_when_location_changes() {
+        let (chronology, vocabularies) = load().await;
+        let existing = existing();
+
+        let raw = record(&[("accession_number", "A-001"), ("discovery_location", "陕西西安")]);
+        let item = validate_record(3, raw, Some(&existing), &chronology, &vocabularies).unwrap();
+        let mut columns: Vec<&str> = item.columns.into_iter().collect();
+        columns.sort();
+        assert_eq!(columns, ["discovery_location", "latitude", "location_uncertainty_m", "longitude"]);
+        let latitude = item.artifact.latitude.unwrap();
+        assert!((latitude - 34.34).abs() < 0.5, "{}", latitude);
+
+        // 只有经度没有纬度
+        let raw = record(&[("accession_number", "A-001"), ("longitude", "108.9")]);
+        let errors = validate_record(3, raw, Some(&existing), &chronology, &vocabularies).err().unwrap();
+        assert_eq!(error_fields(&errors), ["latitude"]);
+    }
+}
@@ -0,0 +1,296 @@
+// src-tauri/src/api/maintenance.rs
+use crate::api::auth::AuthState;
+use crate::api::media::{media_file_path, row_to_media, set_primary, MEDIA_COLUMNS};
+use crate::api::roles::{ensure_permission, Permission};
+use crate::db::models::{ArtifactMedia, MediaType};
+use crate::db::DbState;
+use crate::error::Result;
+use crate::storage::scan::{generate_tiles, list_image_files, quarantine, ImageFile, ImageLocation};
+use crate::storage::MediaStore;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use tauri::State;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct ScanOptions {
+    /// 删除文件缺失的媒体记录（必要时改用其他图片作主图），重复图片的引用统一指向同一文件
+    #[serde(default)]
+    pub fix: bool,
+    /// 将未被引用的图片移入隔离目录
+    #[serde(default)]
+    pub quarantine: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MissingMedia {
+    pub media_id: i64,
+    pub artifact_id: i64,
+    pub title: String,
+    pub media_type: MediaType,
+    pub path: String,
+    pub is_primary: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateGroup {
+    pub hash: String,
+    /// 第一个为修复时保留的文件
+    pub files: Vec<ImageFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MediaScanReport {
+    pub media_count: usize,
+    pub file_count: usize,
+    pub missing: Vec<MissingMedia>,
+    /// 没有任何媒体引用的图片（修复后）
+    pub orphans: Vec<ImageFile>,
+    pub duplicates: Vec<DuplicateGroup>,
+    pub oversized: Vec<ImageFile>,
+    /// 已执行的修复和隔离操作
+    pub actions: Vec<String>,
+    pub quarantine_dir: Option<String>,
+}
+
+fn type_label(media_type: MediaType) -> &'static str {
+    match media_type {
+        MediaType::Image => "图片",
+        MediaType::Model3d => "3D 模型",
+        MediaType::Audio => "音频",
+        MediaType::Video => "视频",
+        MediaType::Document => "文档",
+    }
+}
+
+async fn load_media(pool: &SqlitePool) -> Result<Vec<ArtifactMedia>> {
+    Ok(sqlx::query(&format!(
+        "SELECT {} FROM artifact_media ORDER BY artifact_id, sort_order, id",
+        MEDIA_COLUMNS
+    ))
+    .fetch_all(pool)
+    .await?
+    .iter()
+    .map(row_to_media)
+    .collect())
+}
+
+// 写入有变化的文件缺失标记，返回更新的记录数
+async fn write_missing_flags(conn: &mut SqliteConnection, media: &[ArtifactMedia], exists: &[bool]) -> Result<u64> {
+    let mut updated = 0;
+    for (m, &exists) in media.iter().zip(exists) {
+        if m.file_missing == exists {
+            sqlx::query("UPDATE artifact_media SET file_missing = ? WHERE id = ?")
+                .bind(!exists)
+                .bind(m.id)
+                .execute(&mut *conn)
+                .await?;
+            updated += 1;
+        }
+    }
+    Ok(updated)
+}
+
+/// 只检查媒体文件是否存在并刷新缺失标记，不读取文件内容；启动时调用，返回更新的记录数
+pub async fn refresh_missing_flags(pool: &SqlitePool, store: &MediaStore) -> Result<u64> {
+    let media = load_media(pool).await?;
+    let paths: Vec<PathBuf> = media
+        .iter()
+        .map(|m| media_file_path(store, m.media_type, &m.path))
+        .collect();
+    let exists = tokio::task::spawn_blocking(move || paths.iter().map(|p| p.is_file()).collect::<Vec<bool>>())
+        .await
+        .map_err(anyhow::Error::from)?;
+
+    let mut tx = pool.begin().await?;
+    let updated = write_missing_flags(&mut tx, &media, &exists).await?;
+    tx.commit().await?;
+    Ok(updated)
+}
+
+/// 扫描全部媒体记录和图片文件，刷新文件缺失标记，并按选项修复或隔离
+///
+/// 会计算每张图片的哈希、读取尺寸并补齐切片，开销较大，只由 `scan_media` 命令调用。
+pub async fn scan_media_files(
+    pool: &SqlitePool,
+    store: &MediaStore,
+    options: ScanOptions,
+) -> Result<MediaScanReport> {
+    let media = load_media(pool).await?;
+    let titles: HashMap<i64, String> = sqlx::query("SELECT id, title FROM artifacts")
+        .fetch_all(pool)
+        .await?
+        .iter()
+        .map(|row| (row.get("id"), row.get("title")))
+        .collect();
+    let title = |artifact_id: i64| titles.get(&artifact_id).map(String::as_str).unwrap_or("");
+
+    // 检查文件和计算哈希都是阻塞操作
+    let paths: Vec<PathBuf> = media
+        .iter()
+        .map(|m| media_file_path(store, m.media_type, &m.path))
+        .collect();
+    let scan_store = store.clone();
+    let (exists, files) = tokio::task::spawn_blocking(move || -> Result<_> {
+        let exists: Vec<bool> = paths.iter().map(|p| p.is_file()).collect();
+        Ok((exists, list_image_files(&scan_store)?))
+    })
+    .await
+    .map_err(anyhow::Error::from)??;
+
+    let mut actions = Vec::new();
+    let mut tx = pool.begin().await?;
+    write_missing_flags(&mut tx, &media, &exists).await?;
+
+    let missing: Vec<&ArtifactMedia> = media
+        .iter()
+        .zip(&exists)
+        .filter(|(_, exists)| !**exists)
+        .map(|(m, _)| m)
+        .collect();
+    let mut deleted = HashSet::new();
+
+    if options.fix {
+        for m in &missing {
+            if m.is_primary {
+                let replacement = media.iter().zip(&exists).find(|(other, exists)| {
+                    **exists && other.artifact_id == m.artifact_id && other.media_type == m.media_type
+                });
+                match replacement {
+                    Some((other, _)) => {
+                        set_primary(&mut tx, other).await?;
+                        actions.push(format!(
+                            "《{}》的主{}改为 {}",
+                            title(m.artifact_id),
+                            type_label(m.media_type),
+                            other.path
+                        ));
+                    }
+                    // 文物至少保留一张图片，保留记录及缺失标记
+                    None if m.media_type == MediaType::Image => continue,
+                    None => {}
+                }
+            }
+            sqlx::query("DELETE FROM artifact_media WHERE id = ?")
+                .bind(m.id)
+                .execute(&mut *tx)
+                .await?;
+            deleted.insert(m.id);
+            actions.push(format!(
+                "删除《{}》缺失的{}记录: {}",
+                title(m.artifact_id),
+                type_label(m.media_type),
+                m.path
+            ));
+        }
+    }
+
+    // 被图片媒体引用的文件
+    let mut referenced: HashSet<PathBuf> = media
+        .iter()
+        .filter(|m| m.media_type == MediaType::Image && !deleted.contains(&m.id))
+        .map(|m| store.image_file(&m.path))
+        .collect();
+
+    let mut by_hash: HashMap<&str, Vec<&ImageFile>> = HashMap::new();
+    for file in &files {
+        by_hash.entry(file.hash.as_str()).or_default().push(file);
+    }
+    let mut duplicates = Vec::new();
+    for (hash, mut group) in by_hash.into_iter().filter(|(_, group)| group.len() > 1) {
+        // 优先保留有缩略图的上传图片，其次是已被引用的文件
+        group.sort_by_key(|f| {
+            (
+                f.location != ImageLocation::Stored,
+                !referenced.contains(&f.path),
+                f.name.clone(),
+            )
+        });
+        if options.fix {
+            let keep = group[0];
+            for file in &group[1..] {
+                if !referenced.remove(&file.path) {
+                    continue;
+                }
+                sqlx::query("UPDATE artifact_media SET path = ? WHERE media_type = 'image' AND path = ?")
+                    .bind(&keep.name)
+                    .bind(&file.name)
+                    .execute(&mut *tx)
+                    .await?;
+                referenced.insert(keep.path.clone());
+                actions.push(format!("{} 与 {} 内容相同，引用已改为后者", file.name, keep.name));
+            }
+        }
+        duplicates.push(DuplicateGroup {
+            hash: hash.to_string(),
+            files: group.into_iter().cloned().collect(),
+        });
+    }
+    duplicates.sort_by(|a, b| a.files[0].name.cmp(&b.files[0].name));
+
+    tx.commit().await?;
+
+    // 为被引用的图片补齐 IIIF 切片
+    let tiled: Vec<ImageFile> = files.iter().filter(|f| referenced.contains(&f.path)).cloned().collect();
+    let tile_store = store.clone();
+    let generated = tokio::task::spawn_blocking(move || generate_tiles(&tile_store, &tiled))
+        .await
+        .map_err(anyhow::Error::from)?;
+    if generated > 0 {
+        actions.push(format!("已为 {} 张图片生成 IIIF 切片", generated));
+    }
+
+    let orphans: Vec<&ImageFile> = files.iter().filter(|f| !referenced.contains(&f.path)).collect();
+    let mut quarantine_dir = None;
+    if options.quarantine && !orphans.is_empty() {
+        let store = store.clone();
+        let moved: Vec<ImageFile> = orphans.iter().map(|f| (*f).clone()).collect();
+        let dir = tokio::task::spawn_blocking(move || quarantine(&store, &moved))
+            .await
+            .map_err(anyhow::Error::from)??;
+        actions.extend(orphans.iter().map(|f| format!("已隔离未引用的图片: {}", f.name)));
+        quarantine_dir = Some(dir.to_string_lossy().into_owned());
+    }
+
+    Ok(MediaScanReport {
+        media_count: media.len(),
+        file_count: files.len(),
+        missing: missing
+            .iter()
+            .map(|m| MissingMedia {
+                media_id: m.id,
+                artifact_id: m.artifact_id,
+                title: title(m.artifact_id).to_string(),
+                media_type: m.media_type,
+                path: m.path.clone(),
+                is_primary: m.is_primary,
+            })
+            .collect(),
+        orphans: orphans.into_iter().cloned().collect(),
+        duplicates,
+        oversized: files.iter().filter(|f| f.is_oversized()).cloned().collect(),
+        actions,
+        quarantine_dir,
+    })
+}
+
+/// 媒体完整性扫描：文件缺失的媒体、未被引用的图片、内容重复及过大的图片
+///
+/// 每次扫描都会刷新媒体的文件缺失标记；`fix` 和 `quarantine` 会修改数据或移动文件，另需删除权限。
+#[tauri::command]
+pub async fn scan_media(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    store: State<'_, MediaStore>,
+    token: String,
+    options: Option<ScanOptions>,
+) -> Result<MediaScanReport> {
+    let pool = db.pool()?;
+    let options = options.unwrap_or_default();
+    ensure_permission(&pool, &auth_state, &token, Permission::UpdateArtifact).await?;
+    if options.fix || options.quarantine {
+        ensure_permission(&pool, &auth_state, &token, Permission::DeleteArtifact).await?;
+    }
+    scan_media_files(&pool, &store, options).await
+}
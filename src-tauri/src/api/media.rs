@@ -0,0 +1,406 @@
+// src-tauri/src/api/media.rs
+use crate::api::auth::AuthState;
+use crate::api::roles::{ensure_permission, Permission};
+use crate::db::models::{ArtifactMedia, MediaType};
+use crate::db::DbState;
+use crate::error::{Error, Result};
+use crate::storage::upload::MAX_UPLOAD_BYTES;
+use crate::storage::{store_upload, MediaStore, StoredImage};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqliteRow, Row, SqliteConnection, SqlitePool};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tauri::State;
+
+const MAX_CAPTION_CHARS: usize = 200;
+
+pub(crate) const MEDIA_COLUMNS: &str =
+    "id, artifact_id, media_type, path, caption, sort_order, is_primary, file_missing, created_at";
+
+/// 媒体文件的完整路径；除图片外的媒体随应用分发，位于资源目录的 public 下
+pub(crate) fn media_file_path(store: &MediaStore, media_type: MediaType, path: &str) -> PathBuf {
+    let dir = match media_type {
+        MediaType::Image => return store.image_file(path),
+        MediaType::Model3d => "3DModels",
+        MediaType::Audio => "media/audio",
+        MediaType::Video => "media/video",
+        MediaType::Document => "media/documents",
+    };
+    store.bundled_dir(dir).join(path)
+}
+
+pub(crate) fn row_to_media(row: &SqliteRow) -> ArtifactMedia {
+    let media_type: String = row.get("media_type");
+    ArtifactMedia {
+        id: row.get("id"),
+        artifact_id: row.get("artifact_id"),
+        // 表上有 CHECK 约束，不会出现其他取值
+        media_type: MediaType::parse(&media_type).unwrap_or(MediaType::Document),
+        path: row.get("path"),
+        caption: row.get("caption"),
+        sort_order: row.get("sort_order"),
+        is_primary: row.get("is_primary"),
+        file_missing: row.get("file_missing"),
+        created_at: row.get("created_at"),
+    }
+}
+
+/// 根据文件是否存在更新文物主图的缺失标记，在写入 image_path 后调用
+pub(crate) async fn refresh_primary_image(
+    conn: &mut SqliteConnection,
+    store: &MediaStore,
+    artifact_id: i64,
+) -> Result<()> {
+    let row = sqlx::query(
+        "SELECT id, path FROM artifact_media WHERE artifact_id = ? AND media_type = 'image' AND is_primary = 1",
+    )
+    .bind(artifact_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+    if let Some(row) = row {
+        let path: String = row.get("path");
+        sqlx::query("UPDATE artifact_media SET file_missing = ? WHERE id = ?")
+            .bind(!store.image_file(&path).exists())
+            .bind(row.get::<i64, _>("id"))
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
+
+/// 文物的全部媒体，按排序号排列
+pub async fn list_media(pool: &SqlitePool, artifact_id: i64) -> Result<Vec<ArtifactMedia>> {
+    let rows = sqlx::query(&format!(
+        "SELECT {} FROM artifact_media WHERE artifact_id = ? ORDER BY sort_order, id",
+        MEDIA_COLUMNS
+    ))
+    .bind(artifact_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.iter().map(row_to_media).collect())
+}
+
+async fn fetch_media(conn: &mut SqliteConnection, media_id: i64) -> Result<ArtifactMedia> {
+    let row = sqlx::query(&format!("SELECT {} FROM artifact_media WHERE id = ?", MEDIA_COLUMNS))
+        .bind(media_id)
+        .fetch_optional(conn)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("媒体不存在: {}", media_id)))?;
+    Ok(row_to_media(&row))
+}
+
+// 将某个媒体设为同类型的主媒体
+pub(crate) async fn set_primary(conn: &mut SqliteConnection, media: &ArtifactMedia) -> Result<()> {
+    sqlx::query(
+        "UPDATE artifact_media SET is_primary = 0 WHERE artifact_id = ? AND media_type = ? AND id != ?",
+    )
+    .bind(media.artifact_id)
+    .bind(media.media_type.as_str())
+    .bind(media.id)
+    .execute(&mut *conn)
+    .await?;
+    // 主图会由触发器同步到 artifacts.image_path
+    sqlx::query("UPDATE artifact_media SET is_primary = 1 WHERE id = ?")
+        .bind(media.id)
+        .execute(&mut *conn)
+        .await?;
+    Ok(())
+}
+
+fn validate_caption(caption: &str) -> Result<()> {
+    if caption.chars().count() > MAX_CAPTION_CHARS {
+        return Err(Error::invalid_field(
+            "caption",
+            format!("说明不能超过 {} 个字符", MAX_CAPTION_CHARS),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MediaInput {
+    pub artifact_id: i64,
+    pub media_type: MediaType,
+    /// 媒体目录下的文件名
+    pub path: String,
+    #[serde(default)]
+    pub caption: String,
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImageUpload {
+    /// base64 编码的文件内容
+    pub content: String,
+    pub artifact_id: Option<i64>,
+    #[serde(default)]
+    pub caption: String,
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UploadResult {
+    #[serde(flatten)]
+    pub image: StoredImage,
+    /// 添加到文物时为该文物的全部媒体，否则为空
+    pub media: Vec<ArtifactMedia>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MediaUpdate {
+    pub caption: Option<String>,
+    /// 只能设为 true；取消主媒体请把同类型的其他媒体设为主媒体
+    pub is_primary: Option<bool>,
+}
+
+/// 为文物添加媒体，返回该文物的全部媒体
+#[tauri::command]
+pub async fn add_artifact_media(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    store: State<'_, MediaStore>,
+    token: String,
+    media: MediaInput,
+) -> Result<Vec<ArtifactMedia>> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::UpdateArtifact).await?;
+
+    let path = media.path.trim();
+    if path.is_empty() {
+        return Err(Error::invalid_field("path", "文件名不能为空"));
+    }
+    if path.contains(['/', '\\']) || path.contains("..") {
+        return Err(Error::invalid_field("path", "只能填写文件名"));
+    }
+    if !media_file_path(&store, media.media_type, path).exists() {
+        return Err(Error::invalid_field("path", format!("文件不存在: {}", path)));
+    }
+    insert_media(&pool, &media).await
+}
+
+// 添加媒体记录，调用方已校验文件
+async fn insert_media(pool: &SqlitePool, media: &MediaInput) -> Result<Vec<ArtifactMedia>> {
+    let path = media.path.trim();
+    let caption = media.caption.trim();
+    validate_caption(caption)?;
+
+    let mut tx = pool.begin().await?;
+
+    let exists = sqlx::query("SELECT id FROM artifacts WHERE id = ?")
+        .bind(media.artifact_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    if exists.is_none() {
+        return Err(Error::NotFound(format!("文物不存在: {}", media.artifact_id)));
+    }
+
+    // 同类型的第一个媒体自动成为主媒体
+    let has_primary = sqlx::query(
+        "SELECT id FROM artifact_media WHERE artifact_id = ? AND media_type = ? AND is_primary = 1",
+    )
+    .bind(media.artifact_id)
+    .bind(media.media_type.as_str())
+    .fetch_optional(&mut *tx)
+    .await?
+    .is_some();
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO artifact_media (artifact_id, media_type, path, caption, sort_order, is_primary)
+        VALUES (?, ?, ?, ?, (SELECT COALESCE(MAX(sort_order), -1) + 1 FROM artifact_media WHERE artifact_id = ?), 0)
+        "#,
+    )
+    .bind(media.artifact_id)
+    .bind(media.media_type.as_str())
+    .bind(path)
+    .bind(caption)
+    .bind(media.artifact_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if media.is_primary || !has_primary {
+        let inserted = fetch_media(&mut tx, result.last_insert_rowid()).await?;
+        set_primary(&mut tx, &inserted).await?;
+    }
+
+    tx.commit().await?;
+    list_media(pool, media.artifact_id).await
+}
+
+/// 上传图片，返回保存后的文件名；提供 artifact_id 时同时添加为该文物的图片
+///
+/// `content` 为 base64 编码的文件内容，校验、去除 GPS 信息后按内容保存并生成缩略图。
+#[tauri::command]
+pub async fn upload_artifact_image(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    store: State<'_, MediaStore>,
+    token: String,
+    upload: ImageUpload,
+) -> Result<UploadResult> {
+    let pool = db.pool()?;
+    let permission = if upload.artifact_id.is_some() {
+        Permission::UpdateArtifact
+    } else {
+        Permission::CreateArtifact
+    };
+    ensure_permission(&pool, &auth_state, &token, permission).await?;
+
+    // 先按编码长度粗略判断，避免解码过大的内容
+    if upload.content.len() / 4 * 3 > MAX_UPLOAD_BYTES + 3 {
+        return Err(Error::invalid_field(
+            "content",
+            format!("图片不能超过 {} MB", MAX_UPLOAD_BYTES / 1024 / 1024),
+        ));
+    }
+    let bytes = STANDARD
+        .decode(upload.content.trim())
+        .map_err(|_| Error::invalid_field("content", "文件内容不是有效的 base64"))?;
+
+    let store = store.inner().clone();
+    let image = tokio::task::spawn_blocking(move || store_upload(&store, &bytes))
+        .await
+        .map_err(anyhow::Error::from)??;
+
+    let media = match upload.artifact_id {
+        Some(artifact_id) => {
+            let input = MediaInput {
+                artifact_id,
+                media_type: MediaType::Image,
+                path: image.path.clone(),
+                caption: upload.caption,
+                is_primary: upload.is_primary,
+            };
+            insert_media(&pool, &input).await?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(UploadResult { image, media })
+}
+
+/// 修改媒体说明或设为主媒体，返回该文物的全部媒体
+#[tauri::command]
+pub async fn update_artifact_media(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    media_id: i64,
+    update: MediaUpdate,
+) -> Result<Vec<ArtifactMedia>> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::UpdateArtifact).await?;
+
+    let mut tx = pool.begin().await?;
+    let media = fetch_media(&mut tx, media_id).await?;
+
+    if let Some(caption) = &update.caption {
+        let caption = caption.trim();
+        validate_caption(caption)?;
+        sqlx::query("UPDATE artifact_media SET caption = ? WHERE id = ?")
+            .bind(caption)
+            .bind(media_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    match update.is_primary {
+        Some(true) if !media.is_primary => set_primary(&mut tx, &media).await?,
+        Some(false) if media.is_primary => {
+            return Err(Error::invalid_field(
+                "is_primary",
+                "请将同类型的其他媒体设为主媒体",
+            ));
+        }
+        _ => {}
+    }
+
+    tx.commit().await?;
+    list_media(&pool, media.artifact_id).await
+}
+
+/// 按给定顺序重排文物的媒体，需包含该文物的全部媒体
+#[tauri::command]
+pub async fn reorder_artifact_media(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    artifact_id: i64,
+    media_ids: Vec<i64>,
+) -> Result<Vec<ArtifactMedia>> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::UpdateArtifact).await?;
+
+    let current: HashSet<i64> = list_media(&pool, artifact_id)
+        .await?
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+    let requested: HashSet<i64> = media_ids.iter().copied().collect();
+    if requested.len() != media_ids.len() || requested != current {
+        return Err(Error::invalid_field("media_ids", "需按新顺序列出该文物的全部媒体"));
+    }
+
+    let mut tx = pool.begin().await?;
+    for (index, media_id) in media_ids.iter().enumerate() {
+        sqlx::query("UPDATE artifact_media SET sort_order = ? WHERE id = ?")
+            .bind(index as i64)
+            .bind(media_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    list_media(&pool, artifact_id).await
+}
+
+/// 删除媒体记录（不删除文件），返回该文物的全部媒体
+///
+/// 删除主媒体时，同类型排在最前的媒体成为新的主媒体；文物至少保留一张图片。
+#[tauri::command]
+pub async fn delete_artifact_media(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    media_id: i64,
+) -> Result<Vec<ArtifactMedia>> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::UpdateArtifact).await?;
+
+    let mut tx = pool.begin().await?;
+    let media = fetch_media(&mut tx, media_id).await?;
+
+    let next = sqlx::query(
+        r#"
+        SELECT id FROM artifact_media
+        WHERE artifact_id = ? AND media_type = ? AND id != ?
+        ORDER BY sort_order, id
+        LIMIT 1
+        "#,
+    )
+    .bind(media.artifact_id)
+    .bind(media.media_type.as_str())
+    .bind(media_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if media.media_type == MediaType::Image && next.is_none() {
+        return Err(Error::validation("文物至少需要保留一张图片"));
+    }
+
+    sqlx::query("DELETE FROM artifact_media WHERE id = ?")
+        .bind(media_id)
+        .execute(&mut *tx)
+        .await?;
+
+    if let (true, Some(row)) = (media.is_primary, next) {
+        let promoted = fetch_media(&mut tx, row.get("id")).await?;
+        set_primary(&mut tx, &promoted).await?;
+    }
+
+    tx.commit().await?;
+    list_media(&pool, media.artifact_id).await
+}
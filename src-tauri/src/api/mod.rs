@@ -2,4 +2,17 @@
 pub mod artifacts;
 pub mod auth;
 pub mod ai;
+pub mod chronology;
+pub mod conversations;
+pub mod export;
+pub mod geo;
 pub mod history;
+pub mod iiif;
+pub mod import;
+pub mod maintenance;
+pub mod media;
+pub mod rag;
+pub mod roles;
+pub mod search;
+pub mod system;
+pub mod vocabulary;
@@ -0,0 +1,329 @@
+// src-tauri/src/api/rag.rs
+use crate::api::search::BM25_WEIGHTS;
+use crate::db::models::Artifact;
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use std::collections::HashSet;
+
+// 每次对话最多注入的文物数量
+pub const DEFAULT_CONTEXT_ARTIFACTS: i64 = 3;
+
+// 与 trigram 分词器一致，问题按 3 字切分后以 OR 组合检索
+const GRAM_CHARS: usize = 3;
+
+// 检索词上限，避免超长问题生成过大的 MATCH 表达式
+const MAX_GRAMS: usize = 64;
+
+// 只保留得分不低于最佳结果一定比例的文物，过滤偶然命中常用词的结果
+const RELEVANCE_RATIO: f64 = 0.25;
+
+// 针对单件文物提问时附带的相关藏品数量
+pub const DEFAULT_RELATED_ARTIFACTS: i64 = 3;
+
+// 注入提示词的详细描述最大字符数
+const MAX_DESCRIPTION_CHARS: usize = 400;
+
+/// 检索到的文物，只包含回答问题需要的字段
+#[derive(Debug, Clone)]
+pub struct RetrievedArtifact {
+    pub id: i64,
+    pub title: String,
+    pub dynasty: String,
+    pub period: String,
+    pub material: String,
+    pub dimensions: String,
+    pub collection: String,
+    pub detailed_description: String,
+}
+
+impl From<&Artifact> for RetrievedArtifact {
+    fn from(artifact: &Artifact) -> Self {
+        RetrievedArtifact {
+            id: artifact.id,
+            title: artifact.title.clone(),
+            dynasty: artifact.dynasty.clone(),
+            period: artifact.period.clone(),
+            material: artifact.material.clone(),
+            dimensions: artifact.dimensions.clone(),
+            collection: artifact.collection.clone(),
+            detailed_description: artifact.detailed_description.clone(),
+        }
+    }
+}
+
+/// 回答中引用的文物，前端据此生成链接
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Citation {
+    pub artifact_id: i64,
+    pub title: String,
+}
+
+// 把问题切成 trigram 短语，标点和空白作为分隔
+fn question_grams(question: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut grams = Vec::new();
+
+    for run in question.split(|c: char| !c.is_alphanumeric()) {
+        let chars: Vec<char> = run.chars().collect();
+        for window in chars.windows(GRAM_CHARS) {
+            let gram: String = window.iter().collect();
+            if seen.insert(gram.clone()) {
+                grams.push(gram);
+            }
+            if grams.len() >= MAX_GRAMS {
+                return grams;
+            }
+        }
+    }
+
+    grams
+}
+
+/// 根据用户问题检索相关文物，按 bm25 相关度排序
+pub async fn retrieve_artifacts(
+    pool: &SqlitePool,
+    question: &str,
+    limit: i64,
+) -> Result<Vec<RetrievedArtifact>> {
+    let grams = question_grams(question);
+    if grams.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // 以短语形式引用，避免问题文本被当作 FTS5 语法
+    let match_expr = grams
+        .iter()
+        .map(|gram| format!("\"{}\"", gram.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+
+    let query = format!(
+        r#"
+        SELECT
+            a.id, a.title, a.dynasty, a.period, a.material, a.dimensions,
+            a.collection, a.detailed_description,
+            bm25(artifacts_fts, {}) as score
+        FROM artifacts_fts
+        INNER JOIN artifacts a ON a.id = artifacts_fts.rowid
+        WHERE artifacts_fts MATCH ?
+        ORDER BY score
+        LIMIT ?
+        "#,
+        BM25_WEIGHTS
+    );
+
+    let rows = sqlx::query(&query)
+        .bind(match_expr)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+    // bm25 得分为负数，越小越相关
+    let best: f64 = match rows.first() {
+        Some(row) => row.get("score"),
+        None => return Ok(Vec::new()),
+    };
+
+    let artifacts = rows
+        .into_iter()
+        .filter(|row| row.get::<f64, _>("score") <= best * RELEVANCE_RATIO)
+        .map(|row| RetrievedArtifact {
+            id: row.get("id"),
+            title: row.get("title"),
+            dynasty: row.get("dynasty"),
+            period: row.get("period"),
+            material: row.get("material"),
+            dimensions: row.get("dimensions"),
+            collection: row.get("collection"),
+            detailed_description: row.get("detailed_description"),
+        })
+        .collect();
+
+    Ok(artifacts)
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars).collect();
+    format!("{}…", truncated)
+}
+
+/// 生成注入系统提示的馆藏资料，要求模型用 [#id] 标注引用
+pub fn build_context_prompt(artifacts: &[RetrievedArtifact]) -> Option<String> {
+    if artifacts.is_empty() {
+        return None;
+    }
+
+    let mut prompt = String::from(
+        "以下是本馆与用户问题相关的馆藏资料。回答涉及这些文物时请以资料为准，不要编造资料中没有的细节，\
+         并在提到某件文物时紧跟其编号标注，例如 [#12]。\n",
+    );
+
+    for artifact in artifacts {
+        prompt.push_str(&format!(
+            "\n[#{}] {}\n- 年代：{}（{}）\n- 材质：{}\n- 尺寸：{}\n- 收藏：{}\n- 介绍：{}\n",
+            artifact.id,
+            artifact.title,
+            artifact.dynasty,
+            artifact.period,
+            artifact.material,
+            artifact.dimensions,
+            artifact.collection,
+            truncate_chars(&artifact.detailed_description, MAX_DESCRIPTION_CHARS),
+        ));
+    }
+
+    Some(prompt)
+}
+
+/// 找出回答中引用的文物：包含 [#id] 标注或直接提到文物名称
+pub fn extract_citations(answer: &str, artifacts: &[RetrievedArtifact]) -> Vec<Citation> {
+    artifacts
+        .iter()
+        .filter(|artifact| {
+            answer.contains(&format!("[#{}]", artifact.id))
+                || answer.contains(&artifact.title.replace(['《', '》'], ""))
+        })
+        .map(|artifact| Citation {
+            artifact_id: artifact.id,
+            title: artifact.title.clone(),
+        })
+        .collect()
+}
+
+/// 模拟模式下直接用检索到的资料组织回答
+pub fn simulate_grounded_answer(artifacts: &[RetrievedArtifact]) -> Option<String> {
+    if artifacts.is_empty() {
+        return None;
+    }
+
+    let mut answer = String::from("根据本馆馆藏资料：\n");
+    for artifact in artifacts {
+        answer.push_str(&format!(
+            "\n{} [#{}]：{}{}，现藏于{}。{}\n",
+            artifact.title,
+            artifact.id,
+            artifact.dynasty,
+            artifact.material,
+            artifact.collection,
+            truncate_chars(&artifact.detailed_description, 120),
+        ));
+    }
+
+    Some(answer)
+}
+
+/// 读取完整的文物记录
+pub async fn fetch_artifact(pool: &SqlitePool, artifact_id: i64) -> Result<Artifact> {
+    let row = sqlx::query(
+        r#"
+        SELECT id, title, image_path, period, dynasty, location, description,
+            detailed_description, material, dimensions, discovery_location,
+            collection, category, created_at, updated_at
+        FROM artifacts
+        WHERE id = ?
+        "#,
+    )
+    .bind(artifact_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| Error::NotFound(format!("文物不存在: {}", artifact_id)))?;
+
+    Ok(Artifact {
+        id: row.get("id"),
+        title: row.get("title"),
+        image_path: row.get("image_path"),
+        period: row.get("period"),
+        dynasty: row.get("dynasty"),
+        location: row.get("location"),
+        description: row.get("description"),
+        detailed_description: row.get("detailed_description"),
+        material: row.get("material"),
+        dimensions: row.get("dimensions"),
+        discovery_location: row.get("discovery_location"),
+        collection: row.get("collection"),
+        category: row.get("category"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    })
+}
+
+/// 同朝代或同类别的其他文物，朝代和类别都相同的排在前面
+pub async fn related_artifacts(
+    pool: &SqlitePool,
+    artifact: &Artifact,
+    limit: i64,
+) -> Result<Vec<RetrievedArtifact>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT id, title, dynasty, period, material, dimensions, collection, detailed_description
+        FROM artifacts
+        WHERE id != ? AND (dynasty = ? OR category = ?)
+        ORDER BY (dynasty = ?) + (category = ?) DESC, id
+        LIMIT ?
+        "#,
+    )
+    .bind(artifact.id)
+    .bind(&artifact.dynasty)
+    .bind(&artifact.category)
+    .bind(&artifact.dynasty)
+    .bind(&artifact.category)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| RetrievedArtifact {
+            id: row.get("id"),
+            title: row.get("title"),
+            dynasty: row.get("dynasty"),
+            period: row.get("period"),
+            material: row.get("material"),
+            dimensions: row.get("dimensions"),
+            collection: row.get("collection"),
+            detailed_description: row.get("detailed_description"),
+        })
+        .collect())
+}
+
+/// 生成针对单件文物的资料提示：当前文物的完整信息和相关藏品摘要
+pub fn build_artifact_prompt(artifact: &Artifact, related: &[RetrievedArtifact]) -> String {
+    let mut prompt = format!(
+        "用户正在查看本馆藏品 [#{}] {}，请围绕这件文物回答，以下资料为准，不要编造资料中没有的细节。\n\
+         \n- 类别：{}\n- 年代：{}（{}）\n- 材质：{}\n- 尺寸：{}\n- 出土地点：{}\n- 现藏：{}（{}）\n- 简介：{}\n- 详细介绍：{}\n",
+        artifact.id,
+        artifact.title,
+        artifact.category,
+        artifact.dynasty,
+        artifact.period,
+        artifact.material,
+        artifact.dimensions,
+        artifact.discovery_location,
+        artifact.collection,
+        artifact.location,
+        artifact.description,
+        artifact.detailed_description,
+    );
+
+    if !related.is_empty() {
+        prompt.push_str("\n本馆同朝代或同类别的相关藏品，提到时紧跟编号标注，例如 [#12]：\n");
+        for item in related {
+            prompt.push_str(&format!(
+                "\n[#{}] {}\n- 年代：{}（{}）\n- 材质：{}\n- 尺寸：{}\n- 介绍：{}\n",
+                item.id,
+                item.title,
+                item.dynasty,
+                item.period,
+                item.material,
+                item.dimensions,
+                truncate_chars(&item.detailed_description, MAX_DESCRIPTION_CHARS),
+            ));
+        }
+    }
+
+    prompt
+}
@@ -0,0 +1,431 @@
+// src-tauri/src/api/roles.rs
+use crate::api::auth::{require_session, AuthState};
+use crate::db::DbState;
+use crate::error::{Error, Result};
+use serde::Serialize;
+use sqlx::{Row, SqlitePool};
+use tauri::State;
+
+pub const ROLE_VIEWER: &str = "viewer";
+pub const ROLE_ADMIN: &str = "admin";
+
+/// 权限点，对应 role_permissions 表中的 permission 字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    CreateArtifact,
+    UpdateArtifact,
+    DeleteArtifact,
+    ManageUsers,
+    ManageVocabulary,
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::CreateArtifact => "artifact:create",
+            Permission::UpdateArtifact => "artifact:update",
+            Permission::DeleteArtifact => "artifact:delete",
+            Permission::ManageUsers => "user:manage",
+            Permission::ManageVocabulary => "vocabulary:manage",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RoleInfo {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserPermissions {
+    pub user_id: i64,
+    pub roles: Vec<String>,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UserWithRoles {
+    pub id: i64,
+    pub username: String,
+    pub email: String,
+    pub roles: Vec<String>,
+}
+
+/// 判断用户是否拥有某项权限
+pub async fn has_permission(pool: &SqlitePool, user_id: i64, permission: Permission) -> Result<bool> {
+    let query = r#"
+        SELECT 1
+        FROM user_roles ur
+        INNER JOIN role_permissions rp ON rp.role_id = ur.role_id
+        WHERE ur.user_id = ? AND rp.permission = ?
+        LIMIT 1
+    "#;
+
+    let row = sqlx::query(query)
+        .bind(user_id)
+        .bind(permission.as_str())
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row.is_some())
+}
+
+/// 校验会话并检查权限，返回当前用户ID
+pub async fn ensure_permission(
+    pool: &SqlitePool,
+    auth_state: &AuthState,
+    token: &str,
+    permission: Permission,
+) -> Result<i64> {
+    let session = require_session(pool, auth_state, token).await?;
+
+    if !has_permission(pool, session.user_id, permission).await? {
+        return Err(Error::Forbidden(format!("无权限执行该操作（需要 {} 权限）", permission.as_str())));
+    }
+
+    Ok(session.user_id)
+}
+
+async fn get_roles_of_user(pool: &SqlitePool, user_id: i64) -> Result<Vec<String>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT r.name
+        FROM user_roles ur
+        INNER JOIN roles r ON r.id = ur.role_id
+        WHERE ur.user_id = ?
+        ORDER BY r.id
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.get("name")).collect())
+}
+
+async fn count_admins(pool: &SqlitePool) -> Result<i64> {
+    let row = sqlx::query(
+        r#"
+        SELECT COUNT(*) as count
+        FROM user_roles ur
+        INNER JOIN roles r ON r.id = ur.role_id
+        WHERE r.name = ?
+        "#,
+    )
+    .bind(ROLE_ADMIN)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.get("count"))
+}
+
+async fn insert_user_role(
+    pool: &SqlitePool,
+    user_id: i64,
+    role: &str,
+    granted_by: Option<i64>,
+) -> Result<()> {
+    let result = sqlx::query(
+        r#"
+        INSERT OR IGNORE INTO user_roles (user_id, role_id, granted_by)
+        SELECT ?, id, ? FROM roles WHERE name = ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(granted_by)
+    .bind(role)
+    .execute(pool)
+    .await?;
+
+    // 角色不存在时 SELECT 无结果；已拥有该角色时 INSERT OR IGNORE 同样不插入
+    if result.rows_affected() == 0 {
+        let exists = sqlx::query("SELECT id FROM roles WHERE name = ?")
+            .bind(role)
+            .fetch_optional(pool)
+            .await?;
+        if exists.is_none() {
+            return Err(Error::NotFound(format!("角色不存在: {}", role)));
+        }
+    }
+
+    Ok(())
+}
+
+/// 撤销角色；最后一个管理员的检查与删除在同一条语句中完成，避免并发撤销时删光管理员
+async fn delete_user_role(pool: &SqlitePool, user_id: i64, role: &str) -> Result<()> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM user_roles
+        WHERE user_id = ? AND role_id = (SELECT id FROM roles WHERE name = ?)
+          AND (? <> ? OR (
+              SELECT COUNT(*)
+              FROM user_roles ur
+              INNER JOIN roles r ON r.id = ur.role_id
+              WHERE r.name = ?
+          ) > 1)
+        "#,
+    )
+    .bind(user_id)
+    .bind(role)
+    .bind(role)
+    .bind(ROLE_ADMIN)
+    .bind(ROLE_ADMIN)
+    .execute(pool)
+    .await?;
+
+    // 未删除时区分“本就没有该角色”和“是最后一个管理员”
+    if result.rows_affected() == 0 && role == ROLE_ADMIN {
+        let roles = get_roles_of_user(pool, user_id).await?;
+        if roles.iter().any(|r| r == ROLE_ADMIN) {
+            return Err(Error::Conflict("不能撤销最后一个管理员".to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// 为新注册用户分配默认角色；系统中还没有管理员时，首个用户成为管理员
+pub async fn assign_default_roles(pool: &SqlitePool, user_id: i64) -> Result<()> {
+    insert_user_role(pool, user_id, ROLE_VIEWER, None).await?;
+
+    // 检查与插入放在同一条语句中，避免并发注册时多个用户同时成为管理员
+    let result = sqlx::query(
+        r#"
+        INSERT OR IGNORE INTO user_roles (user_id, role_id, granted_by)
+        SELECT ?, r.id, NULL FROM roles r
+        WHERE r.name = ?
+          AND NOT EXISTS (
+              SELECT 1 FROM user_roles ur
+              INNER JOIN roles ar ON ar.id = ur.role_id
+              WHERE ar.name = ?
+          )
+        "#,
+    )
+    .bind(user_id)
+    .bind(ROLE_ADMIN)
+    .bind(ROLE_ADMIN)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() > 0 {
+        println!("首个用户 {} 已被设为管理员", user_id);
+    }
+
+    Ok(())
+}
+
+/// 首次启动引导：若没有任何管理员，则将最早注册的用户设为管理员
+pub async fn bootstrap_admin(pool: &SqlitePool) -> Result<()> {
+    if count_admins(pool).await? > 0 {
+        return Ok(());
+    }
+
+    let first_user = sqlx::query("SELECT id FROM users ORDER BY id LIMIT 1")
+        .fetch_optional(pool)
+        .await?;
+
+    match first_user {
+        Some(row) => {
+            let user_id: i64 = row.get("id");
+            insert_user_role(pool, user_id, ROLE_VIEWER, None).await?;
+            insert_user_role(pool, user_id, ROLE_ADMIN, None).await?;
+            println!("未找到管理员，已将用户 {} 设为初始管理员", user_id);
+        }
+        None => println!("尚无用户，首个注册的用户将成为管理员"),
+    }
+
+    Ok(())
+}
+
+/// 获取当前用户的角色和权限
+#[tauri::command]
+pub async fn get_my_permissions(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+) -> Result<UserPermissions> {
+    let pool = db.pool()?;
+    let session = require_session(&pool, &auth_state, &token).await?;
+
+    let roles = get_roles_of_user(&pool, session.user_id).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT DISTINCT rp.permission
+        FROM user_roles ur
+        INNER JOIN role_permissions rp ON rp.role_id = ur.role_id
+        WHERE ur.user_id = ?
+        ORDER BY rp.permission
+        "#,
+    )
+    .bind(session.user_id)
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(UserPermissions {
+        user_id: session.user_id,
+        roles,
+        permissions: rows.into_iter().map(|row| row.get("permission")).collect(),
+    })
+}
+
+/// 列出所有角色及其权限
+#[tauri::command]
+pub async fn list_roles(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+) -> Result<Vec<RoleInfo>> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::ManageUsers).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT r.id, r.name, r.description, GROUP_CONCAT(rp.permission) as permissions
+        FROM roles r
+        LEFT JOIN role_permissions rp ON rp.role_id = r.id
+        GROUP BY r.id
+        ORDER BY r.id
+        "#,
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    let roles = rows
+        .into_iter()
+        .map(|row| {
+            let permissions: Option<String> = row.get("permissions");
+            RoleInfo {
+                id: row.get("id"),
+                name: row.get("name"),
+                description: row.get("description"),
+                permissions: permissions
+                    .map(|p| p.split(',').map(|s| s.to_string()).collect())
+                    .unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    Ok(roles)
+}
+
+/// 列出所有用户及其角色
+#[tauri::command]
+pub async fn list_users_with_roles(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+) -> Result<Vec<UserWithRoles>> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::ManageUsers).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT u.id, u.username, u.email, GROUP_CONCAT(r.name) as roles
+        FROM users u
+        LEFT JOIN user_roles ur ON ur.user_id = u.id
+        LEFT JOIN roles r ON r.id = ur.role_id
+        GROUP BY u.id
+        ORDER BY u.id
+        "#,
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    let users = rows
+        .into_iter()
+        .map(|row| {
+            let roles: Option<String> = row.get("roles");
+            UserWithRoles {
+                id: row.get("id"),
+                username: row.get("username"),
+                email: row.get("email"),
+                roles: roles
+                    .map(|r| r.split(',').map(|s| s.to_string()).collect())
+                    .unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    Ok(users)
+}
+
+/// 授予用户角色
+#[tauri::command]
+pub async fn grant_role(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    user_id: i64,
+    role: String,
+) -> Result<Vec<String>> {
+    let pool = db.pool()?;
+    let admin_id = ensure_permission(&pool, &auth_state, &token, Permission::ManageUsers).await?;
+
+    let user = sqlx::query("SELECT id FROM users WHERE id = ?")
+        .bind(user_id)
+        .fetch_optional(&pool)
+        .await?;
+    if user.is_none() {
+        return Err(Error::NotFound("用户不存在".to_string()));
+    }
+
+    insert_user_role(&pool, user_id, &role, Some(admin_id)).await?;
+
+    get_roles_of_user(&pool, user_id).await
+}
+
+/// 撤销用户角色
+#[tauri::command]
+pub async fn revoke_role(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    user_id: i64,
+    role: String,
+) -> Result<Vec<String>> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::ManageUsers).await?;
+
+    delete_user_role(&pool, user_id, &role).await?;
+
+    get_roles_of_user(&pool, user_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::memory_pool;
+
+    async fn create_user(pool: &SqlitePool, name: &str) -> i64 {
+        sqlx::query("INSERT INTO users (username, email, password_hash) VALUES (?, ?, 'x')")
+            .bind(name)
+            .bind(format!("{}@example.com", name))
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid()
+    }
+
+    #[tokio::test]
+    async fn keeps_the_last_admin() {
+        let pool = memory_pool().await;
+        let first = create_user(&pool, "first").await;
+        let second = create_user(&pool, "second").await;
+        insert_user_role(&pool, first, ROLE_ADMIN, None).await.unwrap();
+        insert_user_role(&pool, second, ROLE_ADMIN, None).await.unwrap();
+
+        delete_user_role(&pool, first, ROLE_ADMIN).await.unwrap();
+        assert!(matches!(
+            delete_user_role(&pool, second, ROLE_ADMIN).await,
+            Err(Error::Conflict(_))
+        ));
+        assert_eq!(count_admins(&pool).await.unwrap(), 1);
+
+        // 撤销未拥有的角色不报错
+        delete_user_role(&pool, first, ROLE_ADMIN).await.unwrap();
+    }
+}
@@ -0,0 +1,417 @@
+// src-tauri/src/api/search.rs
+use crate::api::artifacts::{
+    push_filter_conditions, row_to_artifact, SearchParams, ARTIFACT_COLUMNS, IMAGE_MISSING_COLUMN,
+};
+use crate::api::auth::{optional_user_id, AuthState};
+use crate::db::models::ArtifactWithFavorite;
+use crate::db::DbState;
+use crate::error::Result;
+use crate::vocabulary::Vocabulary;
+use serde::Serialize;
+use sqlx::{Row, SqlitePool};
+use tauri::State;
+
+// trigram 分词器要求检索词至少 3 个字符，更短的词退回 LIKE 匹配
+const MIN_FTS_TERM_CHARS: usize = 3;
+
+// 默认返回条数
+const DEFAULT_SEARCH_LIMIT: i64 = 50;
+
+// 各列的 bm25 权重，顺序与 artifacts_fts 的列定义一致
+pub(crate) const BM25_WEIGHTS: &str = "10.0, 4.0, 1.0, 2.0, 2.0, 2.0, 1.0, 1.0, 1.0";
+
+#[derive(Debug, Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub artifact: ArtifactWithFavorite,
+    /// 带 <mark> 高亮的摘要
+    pub snippet: Option<String>,
+    /// bm25 得分，越小越相关
+    pub score: f64,
+}
+
+/// 解析后的关键词检索条件
+#[derive(Debug, Default)]
+pub struct TextQuery {
+    /// FTS5 MATCH 表达式（由长度足够的词组成）
+    pub match_expr: Option<String>,
+    /// 过短、只能走 LIKE 的词
+    pub short_terms: Vec<String>,
+}
+
+impl TextQuery {
+    pub fn parse(query: &str) -> Option<Self> {
+        let mut phrases = Vec::new();
+        let mut short_terms = Vec::new();
+
+        for term in query.split_whitespace() {
+            if term.chars().count() >= MIN_FTS_TERM_CHARS {
+                // 以短语形式引用，避免用户输入被当作 FTS5 语法
+                phrases.push(format!("\"{}\"", term.replace('"', "\"\"")));
+            } else {
+                short_terms.push(term.to_string());
+            }
+        }
+
+        if phrases.is_empty() && short_terms.is_empty() {
+            return None;
+        }
+
+        Some(TextQuery {
+            match_expr: if phrases.is_empty() { None } else { Some(phrases.join(" ")) },
+            short_terms,
+        })
+    }
+
+    /// 生成针对 artifacts 表（别名 a）的过滤条件和绑定值
+    pub fn push_conditions(&self, conditions: &mut Vec<String>, bind_values: &mut Vec<String>) {
+        if let Some(match_expr) = &self.match_expr {
+            conditions.push(
+                "a.id IN (SELECT rowid FROM artifacts_fts WHERE artifacts_fts MATCH ?)".to_string(),
+            );
+            bind_values.push(match_expr.clone());
+        }
+
+        self.push_short_term_conditions(conditions, bind_values);
+    }
+
+    fn push_short_term_conditions(&self, conditions: &mut Vec<String>, bind_values: &mut Vec<String>) {
+        for term in &self.short_terms {
+            conditions.push(
+                "(a.title LIKE ? OR a.description LIKE ? OR a.detailed_description LIKE ?)".to_string(),
+            );
+            let pattern = format!("%{}%", term);
+            bind_values.push(pattern.clone());
+            bind_values.push(pattern.clone());
+            bind_values.push(pattern);
+        }
+    }
+}
+
+// 在文本中找到第一个短词并截取上下文，作为无法使用 snippet() 时的摘要
+fn make_snippet(text: &str, term: &str) -> Option<String> {
+    let start = text.find(term)?;
+    let before: String = {
+        let chars: Vec<char> = text[..start].chars().collect();
+        let skip = chars.len().saturating_sub(12);
+        let prefix: String = chars[skip..].iter().collect();
+        if skip > 0 { format!("…{}", prefix) } else { prefix }
+    };
+    let after_start = start + term.len();
+    let after: String = {
+        let chars: Vec<char> = text[after_start..].chars().collect();
+        let suffix: String = chars.iter().take(16).collect();
+        if chars.len() > 16 { format!("{}…", suffix) } else { suffix }
+    };
+    Some(format!("{}<mark>{}</mark>{}", before, term, after))
+}
+
+/// 全文检索文物，按 bm25 相关度排序并返回高亮摘要
+#[tauri::command]
+pub async fn search_artifacts(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    query: String,
+    limit: Option<i64>,
+    token: Option<String>,
+) -> Result<Vec<SearchHit>> {
+    let pool = db.pool()?;
+    let Some(text_query) = TextQuery::parse(&query) else {
+        return Ok(Vec::new());
+    };
+
+    let user_id = optional_user_id(&pool, &auth_state, token.as_deref()).await?;
+
+    let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, 200);
+
+    let mut conditions = Vec::new();
+    let mut bind_values: Vec<String> = Vec::new();
+    let mut score_bind: Option<String> = None;
+
+    let (score_expr, snippet_expr) = if let Some(match_expr) = &text_query.match_expr {
+        conditions.push("artifacts_fts MATCH ?".to_string());
+        bind_values.push(match_expr.clone());
+        (
+            format!("bm25(artifacts_fts, {})", BM25_WEIGHTS),
+            "snippet(artifacts_fts, -1, '<mark>', '</mark>', '…', 24)".to_string(),
+        )
+    } else {
+        // 只有短词时无法计算 bm25，标题命中优先
+        score_bind = Some(format!("%{}%", text_query.short_terms[0]));
+        (
+            "CASE WHEN a.title LIKE ? THEN -1.0 ELSE 0.0 END".to_string(),
+            "NULL".to_string(),
+        )
+    };
+
+    text_query.push_short_term_conditions(&mut conditions, &mut bind_values);
+
+    let sql = format!(
+        r#"
+        SELECT
+            {},
+            CASE WHEN uf.id IS NOT NULL THEN 1 ELSE 0 END as is_favorite,
+            {},
+            {} as score,
+            {} as snippet
+        FROM artifacts_fts
+        INNER JOIN artifacts a ON a.id = artifacts_fts.rowid
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        WHERE {}
+        ORDER BY score, a.created_at DESC
+        LIMIT ?
+        "#,
+        ARTIFACT_COLUMNS,
+        IMAGE_MISSING_COLUMN,
+        score_expr,
+        snippet_expr,
+        conditions.join(" AND ")
+    );
+
+    // 绑定顺序：score 表达式中的参数 -> user_id -> WHERE 条件 -> LIMIT
+    let mut sql_query = sqlx::query(&sql);
+    if let Some(pattern) = score_bind {
+        sql_query = sql_query.bind(pattern);
+    }
+    sql_query = sql_query.bind(user_id);
+    for value in bind_values {
+        sql_query = sql_query.bind(value);
+    }
+    sql_query = sql_query.bind(limit);
+
+    let rows = sql_query.fetch_all(&pool).await?;
+
+    let hits = rows
+        .iter()
+        .map(|row| {
+            let artifact = row_to_artifact(row);
+            let snippet: Option<String> = row.get("snippet");
+            let snippet = snippet.or_else(|| {
+                let term = text_query.short_terms.first()?;
+                make_snippet(&artifact.title, term)
+                    .or_else(|| make_snippet(&artifact.description, term))
+                    .or_else(|| make_snippet(&artifact.detailed_description, term))
+            });
+            SearchHit {
+                artifact,
+                snippet,
+                score: row.get("score"),
+            }
+        })
+        .collect();
+
+    Ok(hits)
+}
+
+#[derive(Debug, Serialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArtifactFacets {
+    pub total: i64,
+    pub category: Vec<FacetCount>,
+    pub dynasty: Vec<FacetCount>,
+    pub material: Vec<FacetCount>,
+    pub collection: Vec<FacetCount>,
+    pub discovery_location: Vec<FacetCount>,
+}
+
+// 统计某一列在筛选结果中的分布
+async fn count_facet(
+    pool: &SqlitePool,
+    user_id: Option<i64>,
+    params: &SearchParams,
+    column: &str,
+) -> Result<Vec<FacetCount>> {
+    let mut conditions = vec![format!("a.{} != ''", column)];
+    let mut bind_values: Vec<String> = Vec::new();
+    push_filter_conditions(params, &mut conditions, &mut bind_values);
+
+    let query = format!(
+        r#"
+        SELECT a.{col} as value, COUNT(*) as count
+        FROM artifacts a
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        WHERE {conditions}
+        GROUP BY a.{col}
+        ORDER BY count DESC, value
+        "#,
+        col = column,
+        conditions = conditions.join(" AND ")
+    );
+
+    let mut sql_query = sqlx::query(&query).bind(user_id);
+    for value in bind_values {
+        sql_query = sql_query.bind(value);
+    }
+
+    let rows = sql_query.fetch_all(pool).await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| FacetCount {
+            value: row.get("value"),
+            count: row.get("count"),
+        })
+        .collect())
+}
+
+// 统计词表字段的分布：文物计入其词条及各级上级词条，
+// 与按上级词条筛选时包含下级词条的结果一致
+async fn count_term_facet(
+    pool: &SqlitePool,
+    user_id: Option<i64>,
+    params: &SearchParams,
+    vocabulary: Vocabulary,
+) -> Result<Vec<FacetCount>> {
+    let (_, id_column) = vocabulary.columns();
+    let mut conditions = Vec::new();
+    let mut bind_values: Vec<String> = Vec::new();
+    push_filter_conditions(params, &mut conditions, &mut bind_values);
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let query = format!(
+        r#"
+        WITH RECURSIVE ancestors(term_id, ancestor_id) AS (
+            SELECT id, id FROM vocabulary_terms WHERE vocabulary = '{vocabulary}'
+            UNION ALL
+            SELECT an.term_id, t.parent_id
+            FROM ancestors an
+            INNER JOIN vocabulary_terms t ON t.id = an.ancestor_id
+            WHERE t.parent_id IS NOT NULL
+        )
+        SELECT v.code as value, COUNT(*) as count
+        FROM artifacts a
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        INNER JOIN ancestors an ON an.term_id = a.{id_column}
+        INNER JOIN vocabulary_terms v ON v.id = an.ancestor_id
+        {where_clause}
+        GROUP BY v.id
+        ORDER BY count DESC, value
+        "#,
+        vocabulary = vocabulary.as_str(),
+        id_column = id_column,
+        where_clause = where_clause
+    );
+
+    let mut sql_query = sqlx::query(&query).bind(user_id);
+    for value in bind_values {
+        sql_query = sql_query.bind(value);
+    }
+
+    let rows = sql_query.fetch_all(pool).await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| FacetCount {
+            value: row.get("value"),
+            count: row.get("count"),
+        })
+        .collect())
+}
+
+/// 分面统计：按当前筛选条件返回各分类、朝代、材质、收藏机构和出土地的文物数量
+///
+/// 统计某一维度时不应用该维度自身的筛选，便于前端在已选中的维度内切换。
+#[tauri::command]
+pub async fn get_artifact_facets(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    params: Option<SearchParams>,
+    token: Option<String>,
+) -> Result<ArtifactFacets> {
+    let pool = db.pool()?;
+    let params = params.unwrap_or_default();
+
+    let user_id = optional_user_id(&pool, &auth_state, token.as_deref()).await?;
+
+    let mut conditions = Vec::new();
+    let mut bind_values: Vec<String> = Vec::new();
+    push_filter_conditions(&params, &mut conditions, &mut bind_values);
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+    let count_query = format!(
+        r#"
+        SELECT COUNT(*) as total
+        FROM artifacts a
+        LEFT JOIN user_favorites uf ON a.id = uf.artifact_id AND uf.user_id = ?
+        {}
+        "#,
+        where_clause
+    );
+    let mut count_sql = sqlx::query(&count_query).bind(user_id);
+    for value in bind_values {
+        count_sql = count_sql.bind(value);
+    }
+    let total: i64 = count_sql.fetch_one(&pool).await?.get("total");
+
+    let category_params = SearchParams { category: None, ..params.clone() };
+    let dynasty_params = SearchParams { dynasty: None, ..params.clone() };
+    let material_params = SearchParams { material: None, ..params.clone() };
+    let collection_params = SearchParams { collection: None, ..params.clone() };
+
+    Ok(ArtifactFacets {
+        total,
+        category: count_term_facet(&pool, user_id, &category_params, Vocabulary::Category).await?,
+        dynasty: count_facet(&pool, user_id, &dynasty_params, "dynasty").await?,
+        material: count_term_facet(&pool, user_id, &material_params, Vocabulary::Material).await?,
+        collection: count_term_facet(&pool, user_id, &collection_params, Vocabulary::Collection).await?,
+        discovery_location: count_facet(&pool, user_id, &params, "discovery_location").await?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::memory_pool;
+
+    async fn filtered_count(pool: &SqlitePool, params: &SearchParams) -> i64 {
+        let mut conditions = vec!["1 = 1".to_string()];
+        let mut bind_values: Vec<String> = Vec::new();
+        push_filter_conditions(params, &mut conditions, &mut bind_values);
+        let query = format!("SELECT COUNT(*) as count FROM artifacts a WHERE {}", conditions.join(" AND "));
+        let mut sql_query = sqlx::query(&query);
+        for value in bind_values {
+            sql_query = sql_query.bind(value);
+        }
+        sql_query.fetch_one(pool).await.unwrap().get("count")
+    }
+
+    #[tokio::test]
+    async fn category_facets_include_descendant_terms() {
+        let pool = memory_pool().await;
+        // 一件文物归入 鼎 → 礼器 → 青铜器
+        sqlx::query(
+            "UPDATE artifacts SET category = 'ding', category_id = (SELECT id FROM vocabulary_terms WHERE vocabulary = 'category' AND code = 'ding') WHERE id = 1",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let facets = count_term_facet(&pool, None, &SearchParams::default(), Vocabulary::Category)
+            .await
+            .unwrap();
+        let count_of = |code: &str| facets.iter().find(|f| f.value == code).map(|f| f.count);
+        assert_eq!(count_of("ding"), Some(1));
+        assert_eq!(count_of("ritual_vessel"), Some(1));
+
+        for facet in &facets {
+            let params = SearchParams {
+                category: Some(facet.value.clone()),
+                ..SearchParams::default()
+            };
+            assert_eq!(facet.count, filtered_count(&pool, &params).await, "{}", facet.value);
+        }
+        assert!(count_of("bronze").unwrap() > 1);
+    }
+}
@@ -0,0 +1,10 @@
+// src-tauri/src/api/system.rs
+use crate::db::{DbState, DbStatus};
+use crate::error::Result;
+use tauri::State;
+
+/// 查询数据库就绪状态，前端可在启动时轮询或监听 database-status 事件
+#[tauri::command]
+pub async fn get_database_status(db: State<'_, DbState>) -> Result<DbStatus> {
+    Ok(db.status())
+}
@@ -0,0 +1,306 @@
+// src-tauri/src/api/vocabulary.rs
+use crate::api::auth::AuthState;
+use crate::api::roles::{ensure_permission, Permission};
+use crate::db::DbState;
+use crate::error::{Error, Result};
+use crate::vocabulary::{row_to_term, Vocabulary, VocabularyTerm, TERM_COLUMNS};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use tauri::State;
+
+const MAX_CODE_CHARS: usize = 64;
+const MAX_LABEL_CHARS: usize = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct VocabularyTermInput {
+    /// 修改词条时忽略，词条不能移到其他词表
+    pub vocabulary: Vocabulary,
+    pub code: String,
+    pub label_zh: String,
+    #[serde(default)]
+    pub label_en: String,
+    pub parent_id: Option<i64>,
+    #[serde(default)]
+    pub sort_order: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VocabularyTermWithUsage {
+    #[serde(flatten)]
+    pub term: VocabularyTerm,
+    /// 使用该词条的文物数量（不含下级词条）
+    pub usage_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergeResult {
+    pub target: VocabularyTerm,
+    /// 改为使用目标词条的文物数量
+    pub moved: u64,
+}
+
+async fn fetch_term(pool: &SqlitePool, id: i64) -> Result<VocabularyTerm> {
+    let row = sqlx::query(&format!("SELECT {} FROM vocabulary_terms WHERE id = ?", TERM_COLUMNS))
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("词条不存在: {}", id)))?;
+    Ok(row_to_term(&row))
+}
+
+async fn usage_count(pool: &SqlitePool, term: &VocabularyTerm) -> Result<i64> {
+    let (_, id_column) = term.vocabulary.columns();
+    let row = sqlx::query(&format!("SELECT COUNT(*) as count FROM artifacts WHERE {} = ?", id_column))
+        .bind(term.id)
+        .fetch_one(pool)
+        .await?;
+    Ok(row.get("count"))
+}
+
+// descendant 是否为 ancestor 本身或其下级词条
+async fn is_within(pool: &SqlitePool, ancestor: i64, descendant: i64) -> Result<bool> {
+    let row = sqlx::query(
+        r#"
+        WITH RECURSIVE ancestors(id, parent_id) AS (
+            SELECT id, parent_id FROM vocabulary_terms WHERE id = ?
+            UNION ALL
+            SELECT t.id, t.parent_id FROM vocabulary_terms t INNER JOIN ancestors a ON t.id = a.parent_id
+        )
+        SELECT 1 FROM ancestors WHERE id = ? LIMIT 1
+        "#,
+    )
+    .bind(descendant)
+    .bind(ancestor)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.is_some())
+}
+
+// 校验输入并返回整理后的 (code, label_zh, label_en)；id 为修改的词条
+async fn validate_input(
+    pool: &SqlitePool,
+    vocabulary: Vocabulary,
+    input: &VocabularyTermInput,
+    id: Option<i64>,
+) -> Result<(String, String, String)> {
+    let code = input.code.trim().to_string();
+    let label_zh = input.label_zh.trim().to_string();
+    let label_en = input.label_en.trim().to_string();
+
+    if code.is_empty() {
+        return Err(Error::invalid_field("code", "代码不能为空"));
+    }
+    if code.chars().count() > MAX_CODE_CHARS {
+        return Err(Error::invalid_field("code", format!("代码不能超过 {} 个字符", MAX_CODE_CHARS)));
+    }
+    if label_zh.is_empty() {
+        return Err(Error::invalid_field("label_zh", "中文名称不能为空"));
+    }
+    if label_zh.chars().count() > MAX_LABEL_CHARS || label_en.chars().count() > MAX_LABEL_CHARS {
+        return Err(Error::validation(format!("名称不能超过 {} 个字符", MAX_LABEL_CHARS)));
+    }
+
+    let duplicate = sqlx::query("SELECT id FROM vocabulary_terms WHERE vocabulary = ? AND code = ? AND id IS NOT ?")
+        .bind(vocabulary.as_str())
+        .bind(&code)
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    if duplicate.is_some() {
+        return Err(Error::Conflict(format!("{}中已存在代码「{}」", vocabulary.label(), code)));
+    }
+
+    if let Some(parent_id) = input.parent_id {
+        let parent = fetch_term(pool, parent_id).await?;
+        if parent.vocabulary != vocabulary {
+            return Err(Error::invalid_field("parent_id", "上级词条须属于同一词表"));
+        }
+        if let Some(id) = id {
+            if is_within(pool, id, parent_id).await? {
+                return Err(Error::invalid_field("parent_id", "上级词条不能是该词条本身或其下级词条"));
+            }
+        }
+    }
+
+    Ok((code, label_zh, label_en))
+}
+
+/// 获取词表，不指定时返回全部词表；按词表、排序值排列，上下级关系由 parent_id 表示
+#[tauri::command]
+pub async fn get_vocabulary(
+    db: State<'_, DbState>,
+    vocabulary: Option<Vocabulary>,
+) -> Result<Vec<VocabularyTermWithUsage>> {
+    let pool = db.pool()?;
+    // 词条 id 在各词表间不重复，可同时匹配三个外键列
+    let sql = r#"
+        SELECT t.id, t.vocabulary, t.code, t.label_zh, t.label_en, t.parent_id, t.sort_order,
+            (SELECT COUNT(*) FROM artifacts a
+             WHERE a.category_id = t.id OR a.material_id = t.id OR a.collection_id = t.id) as usage_count
+        FROM vocabulary_terms t
+        WHERE ? IS NULL OR t.vocabulary = ?
+        ORDER BY t.vocabulary, t.sort_order, t.id
+    "#;
+    let vocabulary = vocabulary.map(|v| v.as_str());
+    let rows = sqlx::query(sql)
+        .bind(vocabulary)
+        .bind(vocabulary)
+        .fetch_all(&pool)
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| VocabularyTermWithUsage {
+            term: row_to_term(row),
+            usage_count: row.get("usage_count"),
+        })
+        .collect())
+}
+
+/// 新增词条
+#[tauri::command]
+pub async fn create_vocabulary_term(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    term: VocabularyTermInput,
+) -> Result<VocabularyTerm> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::ManageVocabulary).await?;
+
+    let (code, label_zh, label_en) = validate_input(&pool, term.vocabulary, &term, None).await?;
+    let result = sqlx::query(
+        r#"
+        INSERT INTO vocabulary_terms (vocabulary, code, label_zh, label_en, parent_id, sort_order)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(term.vocabulary.as_str())
+    .bind(&code)
+    .bind(&label_zh)
+    .bind(&label_en)
+    .bind(term.parent_id)
+    .bind(term.sort_order)
+    .execute(&pool)
+    .await?;
+
+    fetch_term(&pool, result.last_insert_rowid()).await
+}
+
+/// 修改词条，代码变更会同步到使用该词条的文物
+#[tauri::command]
+pub async fn update_vocabulary_term(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    id: i64,
+    term: VocabularyTermInput,
+) -> Result<VocabularyTerm> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::ManageVocabulary).await?;
+
+    let existing = fetch_term(&pool, id).await?;
+    let (code, label_zh, label_en) = validate_input(&pool, existing.vocabulary, &term, Some(id)).await?;
+    sqlx::query(
+        r#"
+        UPDATE vocabulary_terms
+        SET code = ?, label_zh = ?, label_en = ?, parent_id = ?, sort_order = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(&code)
+    .bind(&label_zh)
+    .bind(&label_en)
+    .bind(term.parent_id)
+    .bind(term.sort_order)
+    .bind(id)
+    .execute(&pool)
+    .await?;
+
+    fetch_term(&pool, id).await
+}
+
+/// 删除词条，仍被文物使用或有下级词条时拒绝
+#[tauri::command]
+pub async fn delete_vocabulary_term(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    id: i64,
+) -> Result<()> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::ManageVocabulary).await?;
+
+    let term = fetch_term(&pool, id).await?;
+    let used = usage_count(&pool, &term).await?;
+    if used > 0 {
+        return Err(Error::Conflict(format!(
+            "词条「{}」仍被 {} 件文物使用，请先合并到其他词条",
+            term.label_zh, used
+        )));
+    }
+    let child = sqlx::query("SELECT 1 FROM vocabulary_terms WHERE parent_id = ? LIMIT 1")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?;
+    if child.is_some() {
+        return Err(Error::Conflict(format!("词条「{}」有下级词条，不能删除", term.label_zh)));
+    }
+
+    sqlx::query("DELETE FROM vocabulary_terms WHERE id = ?")
+        .bind(id)
+        .execute(&pool)
+        .await?;
+    Ok(())
+}
+
+/// 把重复的词条合并到目标词条：文物改用目标词条，下级词条移到目标词条下，然后删除原词条
+#[tauri::command]
+pub async fn merge_vocabulary_terms(
+    db: State<'_, DbState>,
+    auth_state: State<'_, AuthState>,
+    token: String,
+    source_id: i64,
+    target_id: i64,
+) -> Result<MergeResult> {
+    let pool = db.pool()?;
+    ensure_permission(&pool, &auth_state, &token, Permission::ManageVocabulary).await?;
+
+    if source_id == target_id {
+        return Err(Error::validation("不能把词条合并到自身"));
+    }
+    let source = fetch_term(&pool, source_id).await?;
+    let target = fetch_term(&pool, target_id).await?;
+    if source.vocabulary != target.vocabulary {
+        return Err(Error::validation("只能合并同一词表中的词条"));
+    }
+    // 目标是原词条的下级时，移动下级词条会形成环
+    if is_within(&pool, source_id, target_id).await? {
+        return Err(Error::validation("不能合并到原词条的下级词条"));
+    }
+
+    let (text_column, id_column) = source.vocabulary.columns();
+    let mut tx = pool.begin().await?;
+    let moved = sqlx::query(&format!(
+        "UPDATE artifacts SET {} = ?, {} = ?, updated_at = CURRENT_TIMESTAMP WHERE {} = ?",
+        id_column, text_column, id_column
+    ))
+    .bind(target.id)
+    .bind(&target.code)
+    .bind(source.id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    sqlx::query("UPDATE vocabulary_terms SET parent_id = ? WHERE parent_id = ?")
+        .bind(target.id)
+        .bind(source.id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM vocabulary_terms WHERE id = ?")
+        .bind(source.id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(MergeResult { target, moved })
+}
@@ -0,0 +1,303 @@
+// src-tauri/src/chronology.rs
+//! 文物年代：把朝代和年代文本换算为年份范围（公元前为负数）
+//!
+//! 年代文本中有明确的年份或世纪时（如「公元前13-11世纪」「1736-1795年」）直接换算；
+//! 否则按 `chronology_periods` 表匹配朝代或年号，并按「早期」「晚期」等取其三分之一。
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+
+/// 接受的年份范围
+const MIN_YEAR: i64 = -10000;
+const MAX_YEAR: i64 = 2100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeriodKind {
+    Dynasty,
+    /// 年号
+    Reign,
+}
+
+/// 朝代、分期或年号
+#[derive(Debug, Clone, Serialize)]
+pub struct ChronologyPeriod {
+    pub id: i64,
+    pub name: String,
+    pub start_year: i64,
+    pub end_year: i64,
+    pub parent: Option<String>,
+    pub kind: PeriodKind,
+}
+
+/// 年份范围（含两端）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DateRange {
+    pub from: i64,
+    pub to: i64,
+}
+
+impl DateRange {
+    fn new(from: i64, to: i64) -> Option<Self> {
+        let valid = from <= to && from >= MIN_YEAR && to <= MAX_YEAR;
+        valid.then_some(DateRange { from, to })
+    }
+
+    /// 按分期取三分之一
+    fn narrow(self, phase: Option<Phase>) -> Self {
+        let Some(phase) = phase else {
+            return self;
+        };
+        let span = self.to - self.from + 1;
+        let third = span / 3;
+        if third == 0 {
+            return self;
+        }
+        match phase {
+            Phase::Early => DateRange { from: self.from, to: self.from + third - 1 },
+            Phase::Middle => DateRange { from: self.from + third, to: self.to - third },
+            Phase::Late => DateRange { from: self.to - third + 1, to: self.to },
+        }
+    }
+
+    pub fn midpoint(&self) -> i64 {
+        self.from + (self.to - self.from) / 2
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Early,
+    Middle,
+    Late,
+}
+
+fn parse_phase(text: &str) -> Option<Phase> {
+    const EARLY: &[&str] = &["早期", "前期", "初期", "初"];
+    const MIDDLE: &[&str] = &["中期", "中叶"];
+    const LATE: &[&str] = &["晚期", "后期", "末期", "末", "晚"];
+    if EARLY.iter().any(|w| text.contains(w)) {
+        Some(Phase::Early)
+    } else if MIDDLE.iter().any(|w| text.contains(w)) {
+        Some(Phase::Middle)
+    } else if LATE.iter().any(|w| text.contains(w)) {
+        Some(Phase::Late)
+    } else {
+        None
+    }
+}
+
+/// 文本中的一个数字及其前面的纪年标记（Some(true) 为公元前，Some(false) 为公元）
+fn scan_numbers(text: &str) -> Vec<(i64, Option<bool>)> {
+    let mut numbers = Vec::new();
+    let mut era = None;
+    let mut digits = String::new();
+    let mut rest = text;
+    while let Some(ch) = rest.chars().next() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+        } else {
+            if let Ok(value) = digits.parse() {
+                numbers.push((value, era.take()));
+            }
+            digits.clear();
+            if rest.starts_with("公元前") || rest.starts_with("前") {
+                era = Some(true);
+            } else if rest.starts_with("公元") && era.is_none() {
+                era = Some(false);
+            }
+        }
+        rest = &rest[ch.len_utf8()..];
+    }
+    if let Ok(value) = digits.parse() {
+        numbers.push((value, era));
+    }
+    numbers
+}
+
+/// 解析年代文本中的年份或世纪，如「公元前13-11世纪」「公元前206-220年」「约1113年」
+pub fn parse_period(text: &str) -> Option<DateRange> {
+    let text: String = text
+        .split_whitespace()
+        .collect::<String>()
+        .replace(['－', '—', '–', '~', '～', '至', '到'], "-");
+    let numbers = scan_numbers(&text);
+    let (&(first, first_era), &(last, last_era)) = (numbers.first()?, numbers.last()?);
+    let first_bce = first_era == Some(true);
+    // 第二个数字未标注时：公元前的年代递减，递增则说明已跨入公元
+    let last_bce = last_era.unwrap_or(first_bce && last <= first);
+
+    let (start, end) = if text.contains("世纪") {
+        let century = |c: i64, bce: bool| -> (i64, i64) {
+            if bce {
+                (-(c * 100), -((c - 1) * 100 + 1))
+            } else {
+                ((c - 1) * 100 + 1, c * 100)
+            }
+        };
+        if first == 0 || last == 0 {
+            return None;
+        }
+        let (a, b) = (century(first, first_bce), century(last, last_bce));
+        let range = DateRange::new(a.0.min(b.0), a.1.max(b.1))?;
+        // 「公元前5世纪晚期」只取该世纪的后三分之一
+        let range = range.narrow(text.split("世纪").nth(1).and_then(parse_phase));
+        (range.from, range.to)
+    } else {
+        let year = |y: i64, bce: bool| if bce { -y } else { y };
+        let (a, b) = (year(first, first_bce), year(last, last_bce));
+        (a.min(b), a.max(b))
+    };
+    DateRange::new(start, end)
+}
+
+/// 朝代年表
+pub struct Chronology {
+    periods: Vec<ChronologyPeriod>,
+}
+
+impl Chronology {
+    pub async fn load(pool: &SqlitePool) -> Result<Self> {
+        let periods = sqlx::query(
+            "SELECT id, name, start_year, end_year, parent, kind FROM chronology_periods ORDER BY start_year, end_year DESC, id",
+        )
+        .fetch_all(pool)
+        .await?
+        .iter()
+        .map(|row| ChronologyPeriod {
+            id: row.get("id"),
+            name: row.get("name"),
+            start_year: row.get("start_year"),
+            end_year: row.get("end_year"),
+            parent: row.get("parent"),
+            kind: match row.get::<String, _>("kind").as_str() {
+                "reign" => PeriodKind::Reign,
+                _ => PeriodKind::Dynasty,
+            },
+        })
+        .collect();
+        Ok(Chronology { periods })
+    }
+
+    /// 按年份先后排列的全部朝代和年号
+    pub fn periods(&self) -> &[ChronologyPeriod] {
+        &self.periods
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ChronologyPeriod> {
+        self.periods.iter().find(|p| p.name == name)
+    }
+
+    /// 文本中提到的最具体（年份跨度最小）的朝代或年号，如「五代南唐」为南唐
+    pub fn find(&self, text: &str) -> Option<&ChronologyPeriod> {
+        // 「公元」「公元前」中的「元」不是朝代
+        let text = text.replace("公元前", "").replace("公元", "");
+        self.periods
+            .iter()
+            .filter(|p| text.contains(&p.name))
+            .min_by(|a, b| {
+                (a.end_year - a.start_year)
+                    .cmp(&(b.end_year - b.start_year))
+                    .then(b.name.chars().count().cmp(&a.name.chars().count()))
+            })
+    }
+
+    /// 年号归入所属朝代
+    pub fn dynasty_of<'a>(&'a self, period: &'a ChronologyPeriod) -> &'a ChronologyPeriod {
+        match (period.kind, &period.parent) {
+            (PeriodKind::Reign, Some(parent)) => self.get(parent).unwrap_or(period),
+            _ => period,
+        }
+    }
+
+    /// 根据年代文本中的年份推断年份范围，没有年份时取朝代或年代文本中最具体的朝代、年号
+    pub fn date_range(&self, dynasty: &str, period: &str) -> Option<DateRange> {
+        if let Some(range) = parse_period(period) {
+            return Some(range);
+        }
+        [dynasty, period]
+            .into_iter()
+            .filter_map(|text| {
+                let matched = self.find(text)?;
+                DateRange::new(matched.start_year, matched.end_year).map(|range| range.narrow(parse_phase(text)))
+            })
+            .min_by_key(|range| range.to - range.from)
+    }
+
+    /// 整理输入的年份：起止年份需同时提供，均未提供时按朝代和年代推断
+    pub fn resolve(
+        &self,
+        date_from: Option<i64>,
+        date_to: Option<i64>,
+        dynasty: &str,
+        period: &str,
+    ) -> Result<Option<DateRange>> {
+        match (date_from, date_to) {
+            (Some(from), Some(to)) => DateRange::new(from, to).map(Some).ok_or_else(|| {
+                Error::invalid_field(
+                    "date_from",
+                    format!("起始年份不能晚于终止年份，且需在 {} 到 {} 之间", MIN_YEAR, MAX_YEAR),
+                )
+            }),
+            (None, None) => Ok(self.date_range(dynasty, period)),
+            _ => Err(Error::invalid_field("date_from", "起始年份和终止年份需同时填写")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::memory_pool;
+
+    async fn seed_chronology() -> Chronology {
+        Chronology::load(&memory_pool().await).await.unwrap()
+    }
+
+    fn range(from: i64, to: i64) -> Option<DateRange> {
+        Some(DateRange { from, to })
+    }
+
+    #[test]
+    fn parse_period_reads_years_and_centuries() {
+        assert_eq!(parse_period("公元前13-11世纪"), range(-1300, -1001));
+        assert_eq!(parse_period("公元前433年"), range(-433, -433));
+        assert_eq!(parse_period("公元前206-220年"), range(-206, 220));
+        assert_eq!(parse_period("1736-1795年"), range(1736, 1795));
+        assert_eq!(parse_period("公元前5世纪晚期"), range(-433, -401));
+        assert_eq!(parse_period("五代南唐"), None);
+        assert_eq!(parse_period("清代乾隆"), None);
+    }
+
+    #[tokio::test]
+    async fn find_ignores_yuan_in_gongyuan() {
+        let chronology = seed_chronology().await;
+        assert!(chronology.find("公元前13-11世纪").is_none());
+        assert!(chronology.find("公元前433年").is_none());
+        assert!(chronology.find("公元1世纪").is_none());
+        assert_eq!(chronology.find("元代").map(|p| p.name.as_str()), Some("元"));
+        assert_eq!(chronology.find("公元1300年 元").map(|p| p.name.as_str()), Some("元"));
+    }
+
+    #[tokio::test]
+    async fn find_prefers_most_specific_period() {
+        let chronology = seed_chronology().await;
+
+        let southern_tang = chronology.find("五代南唐").unwrap();
+        assert_eq!(southern_tang.name, "南唐");
+        assert_eq!(chronology.dynasty_of(southern_tang).name, "南唐");
+
+        let qianlong = chronology.find("清代乾隆").unwrap();
+        assert_eq!(qianlong.name, "乾隆");
+        assert_eq!(chronology.dynasty_of(qianlong).name, "清");
+    }
+
+    #[tokio::test]
+    async fn date_range_prefers_explicit_years() {
+        let chronology = seed_chronology().await;
+        assert_eq!(chronology.date_range("商", "公元前13-11世纪"), range(-1300, -1001));
+        assert_eq!(chronology.date_range("战国", "公元前433年"), range(-433, -433));
+        assert_eq!(chronology.date_range("清", "清代乾隆"), range(1736, 1795));
+        assert_eq!(chronology.date_range("", "公元前"), None);
+    }
+}
@@ -0,0 +1,110 @@
+// src-tauri/src/db/config.rs
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tauri::{path::BaseDirectory, AppHandle, Manager};
+use anyhow::Result;
+
+// 覆盖数据库文件路径的环境变量
+pub const DB_PATH_ENV: &str = "CULTURAL_HERITAGE_DB_PATH";
+
+// 应用配置目录下的数据库配置文件
+pub const CONFIG_FILE_NAME: &str = "database.json";
+
+// 数据库文件名，同时也是打包进资源目录的种子数据库
+pub const DB_FILE_NAME: &str = "cultural_heritage.sqlite";
+
+/// 数据库配置，可通过应用配置目录下的 database.json 覆盖
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    /// 数据库文件路径，未设置时使用应用数据目录
+    pub path: Option<PathBuf>,
+    pub max_connections: u32,
+    /// 是否启用 WAL 日志模式
+    pub wal: bool,
+    /// 数据库被锁定时的等待时间（毫秒）
+    pub busy_timeout_ms: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        DatabaseConfig {
+            path: None,
+            max_connections: 5,
+            wal: true,
+            busy_timeout_ms: 5000,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    /// 读取配置：配置文件 -> 环境变量（路径）
+    pub fn load(app_handle: &AppHandle) -> Result<Self> {
+        let mut config = match app_handle.path().app_config_dir() {
+            Ok(dir) => Self::from_file(&dir.join(CONFIG_FILE_NAME))?,
+            Err(_) => DatabaseConfig::default(),
+        };
+
+        if let Ok(path) = std::env::var(DB_PATH_ENV) {
+            if !path.is_empty() {
+                config.path = Some(PathBuf::from(path));
+            }
+        }
+
+        Ok(config)
+    }
+
+    fn from_file(file: &Path) -> Result<Self> {
+        if !file.exists() {
+            return Ok(DatabaseConfig::default());
+        }
+
+        let content = std::fs::read_to_string(file)?;
+        let config = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("数据库配置文件 {} 格式错误: {}", file.display(), e))?;
+        println!("已加载数据库配置: {}", file.display());
+        Ok(config)
+    }
+
+    /// 解析数据库文件路径，默认位于应用数据目录
+    pub fn resolve_path(&self, app_handle: &AppHandle) -> Result<PathBuf> {
+        if let Some(path) = &self.path {
+            return Ok(path.clone());
+        }
+
+        let data_dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| anyhow::anyhow!("无法获取应用数据目录: {}", e))?;
+        Ok(data_dir.join(DB_FILE_NAME))
+    }
+}
+
+/// 首次运行时将打包的种子数据库复制到目标位置
+///
+/// 找不到种子数据库时不做处理，稍后由迁移创建空库。
+pub fn copy_seed_database(app_handle: &AppHandle, target: &Path) -> Result<()> {
+    if target.exists() {
+        return Ok(());
+    }
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let seed = app_handle
+        .path()
+        .resolve(format!("data/{}", DB_FILE_NAME), BaseDirectory::Resource)
+        .ok()
+        .filter(|p| p.exists());
+
+    match seed {
+        Some(seed) => {
+            std::fs::copy(&seed, target)?;
+            println!("已从 {} 复制种子数据库", seed.display());
+        }
+        None => println!("未找到种子数据库，将创建新的数据库"),
+    }
+
+    Ok(())
+}
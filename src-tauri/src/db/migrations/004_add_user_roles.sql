@@ -0,0 +1,61 @@
+CREATE TABLE IF NOT EXISTS roles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT UNIQUE NOT NULL,
+    description TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+
+CREATE TABLE IF NOT EXISTS role_permissions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    role_id INTEGER NOT NULL,
+    permission TEXT NOT NULL,
+    FOREIGN KEY (role_id) REFERENCES roles (id) ON DELETE CASCADE,
+    UNIQUE(role_id, permission)
+);
+
+
+CREATE TABLE IF NOT EXISTS user_roles (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL,
+    role_id INTEGER NOT NULL,
+    granted_by INTEGER,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (role_id) REFERENCES roles (id) ON DELETE CASCADE,
+    FOREIGN KEY (granted_by) REFERENCES users (id) ON DELETE SET NULL,
+    UNIQUE(user_id, role_id)
+);
+
+
+INSERT OR IGNORE INTO roles (name, description) VALUES
+    ('viewer', '普通用户，可浏览和收藏文物'),
+    ('curator', '策展人，可新增和编辑文物'),
+    ('admin', '管理员，拥有全部权限');
+
+
+INSERT OR IGNORE INTO role_permissions (role_id, permission)
+SELECT r.id, p.permission
+FROM roles r
+JOIN (
+    SELECT 'curator' AS role, 'artifact:create' AS permission
+    UNION ALL SELECT 'curator', 'artifact:update'
+    UNION ALL SELECT 'admin', 'artifact:create'
+    UNION ALL SELECT 'admin', 'artifact:update'
+    UNION ALL SELECT 'admin', 'artifact:delete'
+    UNION ALL SELECT 'admin', 'user:manage'
+) p ON p.role = r.name;
+
+
+-- 已有用户默认为普通用户
+INSERT OR IGNORE INTO user_roles (user_id, role_id)
+SELECT u.id, r.id FROM users u, roles r WHERE r.name = 'viewer';
+
+-- 沿用原先硬编码的管理员账号
+INSERT OR IGNORE INTO user_roles (user_id, role_id)
+SELECT u.id, r.id FROM users u, roles r
+WHERE u.email = 'yi@example.com' AND r.name = 'admin';
+
+
+CREATE INDEX IF NOT EXISTS idx_user_roles_user_id ON user_roles(user_id);
+CREATE INDEX IF NOT EXISTS idx_role_permissions_role_id ON role_permissions(role_id);
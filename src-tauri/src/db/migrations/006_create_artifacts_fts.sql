@@ -0,0 +1,58 @@
+-- 文物全文索引，trigram 分词可对中文做子串匹配
+CREATE VIRTUAL TABLE IF NOT EXISTS artifacts_fts USING fts5(
+    title,
+    description,
+    detailed_description,
+    dynasty,
+    period,
+    material,
+    location,
+    discovery_location,
+    collection,
+    content = 'artifacts',
+    content_rowid = 'id',
+    tokenize = 'trigram'
+);
+
+
+INSERT INTO artifacts_fts(artifacts_fts) VALUES ('rebuild');
+
+
+CREATE TRIGGER IF NOT EXISTS artifacts_fts_ai AFTER INSERT ON artifacts BEGIN
+    INSERT INTO artifacts_fts (
+        rowid, title, description, detailed_description, dynasty, period,
+        material, location, discovery_location, collection
+    ) VALUES (
+        new.id, new.title, new.description, new.detailed_description, new.dynasty, new.period,
+        new.material, new.location, new.discovery_location, new.collection
+    );
+END;
+
+
+CREATE TRIGGER IF NOT EXISTS artifacts_fts_ad AFTER DELETE ON artifacts BEGIN
+    INSERT INTO artifacts_fts (
+        artifacts_fts, rowid, title, description, detailed_description, dynasty, period,
+        material, location, discovery_location, collection
+    ) VALUES (
+        'delete', old.id, old.title, old.description, old.detailed_description, old.dynasty, old.period,
+        old.material, old.location, old.discovery_location, old.collection
+    );
+END;
+
+
+CREATE TRIGGER IF NOT EXISTS artifacts_fts_au AFTER UPDATE ON artifacts BEGIN
+    INSERT INTO artifacts_fts (
+        artifacts_fts, rowid, title, description, detailed_description, dynasty, period,
+        material, location, discovery_location, collection
+    ) VALUES (
+        'delete', old.id, old.title, old.description, old.detailed_description, old.dynasty, old.period,
+        old.material, old.location, old.discovery_location, old.collection
+    );
+    INSERT INTO artifacts_fts (
+        rowid, title, description, detailed_description, dynasty, period,
+        material, location, discovery_location, collection
+    ) VALUES (
+        new.id, new.title, new.description, new.detailed_description, new.dynasty, new.period,
+        new.material, new.location, new.discovery_location, new.collection
+    );
+END;
@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS idx_artifacts_created_at ON artifacts(created_at, id);
+CREATE INDEX IF NOT EXISTS idx_artifacts_updated_at ON artifacts(updated_at, id);
@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS idx_artifacts_material ON artifacts(material);
+CREATE INDEX IF NOT EXISTS idx_artifacts_collection ON artifacts(collection);
@@ -0,0 +1,5 @@
+-- 外部登录号（藏品编号），批量导入时据此判断新增或更新
+ALTER TABLE artifacts ADD COLUMN accession_number TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_artifacts_accession_number
+    ON artifacts(accession_number) WHERE accession_number IS NOT NULL;
@@ -0,0 +1,55 @@
+-- 文物的多张图片及其他媒体（3D 模型、语音导览、视频、文档）
+-- path 为对应媒体目录下的文件名；每种类型最多一个主媒体
+CREATE TABLE IF NOT EXISTS artifact_media (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    artifact_id INTEGER NOT NULL,
+    media_type TEXT NOT NULL CHECK (media_type IN ('image', 'model_3d', 'audio', 'video', 'document')),
+    path TEXT NOT NULL,
+    caption TEXT NOT NULL DEFAULT '',
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    is_primary BOOLEAN NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (artifact_id) REFERENCES artifacts (id) ON DELETE CASCADE
+);
+
+
+CREATE INDEX IF NOT EXISTS idx_artifact_media_artifact ON artifact_media(artifact_id, sort_order, id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_artifact_media_primary
+    ON artifact_media(artifact_id, media_type) WHERE is_primary = 1;
+
+
+-- 原有的 image_path 作为主图
+INSERT INTO artifact_media (artifact_id, media_type, path, sort_order, is_primary)
+SELECT id, 'image', image_path, 0, 1 FROM artifacts WHERE image_path != '';
+
+
+-- artifacts.image_path 与主图保持一致：列表、导出等仍直接读取 image_path
+CREATE TRIGGER IF NOT EXISTS artifact_media_artifact_ai AFTER INSERT ON artifacts
+WHEN new.image_path != '' BEGIN
+    INSERT INTO artifact_media (artifact_id, media_type, path, sort_order, is_primary)
+    VALUES (new.id, 'image', new.image_path, 0, 1);
+END;
+
+
+CREATE TRIGGER IF NOT EXISTS artifact_media_artifact_au AFTER UPDATE OF image_path ON artifacts
+WHEN new.image_path != old.image_path AND new.image_path != '' BEGIN
+    UPDATE artifact_media SET path = new.image_path
+    WHERE artifact_id = new.id AND media_type = 'image' AND is_primary = 1 AND path != new.image_path;
+    INSERT INTO artifact_media (artifact_id, media_type, path, sort_order, is_primary)
+    SELECT new.id, 'image', new.image_path, 0, 1
+    WHERE NOT EXISTS (
+        SELECT 1 FROM artifact_media WHERE artifact_id = new.id AND media_type = 'image' AND is_primary = 1
+    );
+END;
+
+
+CREATE TRIGGER IF NOT EXISTS artifact_media_primary_ai AFTER INSERT ON artifact_media
+WHEN new.media_type = 'image' AND new.is_primary = 1 BEGIN
+    UPDATE artifacts SET image_path = new.path WHERE id = new.artifact_id AND image_path != new.path;
+END;
+
+
+CREATE TRIGGER IF NOT EXISTS artifact_media_primary_au AFTER UPDATE OF path, is_primary ON artifact_media
+WHEN new.media_type = 'image' AND new.is_primary = 1 BEGIN
+    UPDATE artifacts SET image_path = new.path WHERE id = new.artifact_id AND image_path != new.path;
+END;
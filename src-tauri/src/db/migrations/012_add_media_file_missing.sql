@@ -0,0 +1,4 @@
+-- 媒体文件缺失标记，由媒体完整性扫描及写入图片路径时维护，查询时不再检查文件
+ALTER TABLE artifact_media ADD COLUMN file_missing BOOLEAN NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_artifact_media_missing ON artifact_media(file_missing) WHERE file_missing = 1;
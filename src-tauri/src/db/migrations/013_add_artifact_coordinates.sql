@@ -0,0 +1,7 @@
+-- 出土地点坐标（WGS84）及不确定半径（米）；未定位的文物为 NULL
+ALTER TABLE artifacts ADD COLUMN latitude REAL CHECK (latitude BETWEEN -90 AND 90);
+ALTER TABLE artifacts ADD COLUMN longitude REAL CHECK (longitude BETWEEN -180 AND 180);
+ALTER TABLE artifacts ADD COLUMN location_uncertainty_m REAL CHECK (location_uncertainty_m >= 0);
+
+CREATE INDEX IF NOT EXISTS idx_artifacts_coordinates
+    ON artifacts(latitude, longitude) WHERE latitude IS NOT NULL;
@@ -0,0 +1,32 @@
+-- 文物坐标的空间索引，供地图按范围查询和聚合；只收录有坐标的文物
+CREATE VIRTUAL TABLE IF NOT EXISTS artifacts_rtree USING rtree(
+    id,
+    min_lat, max_lat,
+    min_lng, max_lng
+);
+
+
+INSERT INTO artifacts_rtree (id, min_lat, max_lat, min_lng, max_lng)
+SELECT id, latitude, latitude, longitude, longitude
+FROM artifacts
+WHERE latitude IS NOT NULL AND longitude IS NOT NULL;
+
+
+CREATE TRIGGER IF NOT EXISTS artifacts_rtree_ai AFTER INSERT ON artifacts
+WHEN new.latitude IS NOT NULL AND new.longitude IS NOT NULL BEGIN
+    INSERT INTO artifacts_rtree (id, min_lat, max_lat, min_lng, max_lng)
+    VALUES (new.id, new.latitude, new.latitude, new.longitude, new.longitude);
+END;
+
+
+CREATE TRIGGER IF NOT EXISTS artifacts_rtree_ad AFTER DELETE ON artifacts BEGIN
+    DELETE FROM artifacts_rtree WHERE id = old.id;
+END;
+
+
+CREATE TRIGGER IF NOT EXISTS artifacts_rtree_au AFTER UPDATE OF latitude, longitude ON artifacts BEGIN
+    DELETE FROM artifacts_rtree WHERE id = old.id;
+    INSERT INTO artifacts_rtree (id, min_lat, max_lat, min_lng, max_lng)
+    SELECT new.id, new.latitude, new.latitude, new.longitude, new.longitude
+    WHERE new.latitude IS NOT NULL AND new.longitude IS NOT NULL;
+END;
@@ -0,0 +1,74 @@
+-- 朝代、分期及年号的起止年份（公元前为负数），用于把朝代和年代文本换算为年份范围
+CREATE TABLE IF NOT EXISTS chronology_periods (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    start_year INTEGER NOT NULL,
+    end_year INTEGER NOT NULL,
+    -- 所属的上一级朝代，如西汉属于汉、乾隆属于清
+    parent TEXT REFERENCES chronology_periods (name),
+    kind TEXT NOT NULL DEFAULT 'dynasty' CHECK (kind IN ('dynasty', 'reign')),
+    CHECK (start_year <= end_year)
+);
+
+
+INSERT OR IGNORE INTO chronology_periods (name, start_year, end_year, parent, kind) VALUES
+    ('新石器', -7000, -2071, NULL, 'dynasty'),
+    ('夏', -2070, -1601, NULL, 'dynasty'),
+    ('商', -1600, -1047, NULL, 'dynasty'),
+    ('周', -1046, -256, NULL, 'dynasty'),
+    ('西周', -1046, -771, '周', 'dynasty'),
+    ('东周', -770, -256, '周', 'dynasty'),
+    ('春秋', -770, -476, '东周', 'dynasty'),
+    ('战国', -475, -222, '东周', 'dynasty'),
+    ('秦', -221, -207, NULL, 'dynasty'),
+    ('汉', -202, 220, NULL, 'dynasty'),
+    ('西汉', -202, 8, '汉', 'dynasty'),
+    ('东汉', 25, 220, '汉', 'dynasty'),
+    ('三国', 220, 280, NULL, 'dynasty'),
+    ('晋', 266, 420, NULL, 'dynasty'),
+    ('西晋', 266, 316, '晋', 'dynasty'),
+    ('东晋', 317, 420, '晋', 'dynasty'),
+    ('南北朝', 420, 589, NULL, 'dynasty'),
+    ('北魏', 386, 534, '南北朝', 'dynasty'),
+    ('隋', 581, 618, NULL, 'dynasty'),
+    ('唐', 618, 907, NULL, 'dynasty'),
+    ('五代', 907, 960, NULL, 'dynasty'),
+    ('南唐', 937, 975, '五代', 'dynasty'),
+    ('辽', 916, 1125, NULL, 'dynasty'),
+    ('宋', 960, 1279, NULL, 'dynasty'),
+    ('北宋', 960, 1127, '宋', 'dynasty'),
+    ('南宋', 1127, 1279, '宋', 'dynasty'),
+    ('西夏', 1038, 1227, NULL, 'dynasty'),
+    ('金', 1115, 1234, NULL, 'dynasty'),
+    ('元', 1271, 1368, NULL, 'dynasty'),
+    ('明', 1368, 1644, NULL, 'dynasty'),
+    ('清', 1644, 1911, NULL, 'dynasty'),
+    ('民国', 1912, 1949, NULL, 'dynasty'),
+    ('宣和', 1119, 1125, '北宋', 'reign'),
+    ('洪武', 1368, 1398, '明', 'reign'),
+    ('永乐', 1403, 1424, '明', 'reign'),
+    ('宣德', 1426, 1435, '明', 'reign'),
+    ('成化', 1465, 1487, '明', 'reign'),
+    ('弘治', 1488, 1505, '明', 'reign'),
+    ('正德', 1506, 1521, '明', 'reign'),
+    ('嘉靖', 1522, 1566, '明', 'reign'),
+    ('隆庆', 1567, 1572, '明', 'reign'),
+    ('万历', 1573, 1620, '明', 'reign'),
+    ('崇祯', 1628, 1644, '明', 'reign'),
+    ('顺治', 1644, 1661, '清', 'reign'),
+    ('康熙', 1662, 1722, '清', 'reign'),
+    ('雍正', 1723, 1735, '清', 'reign'),
+    ('乾隆', 1736, 1795, '清', 'reign'),
+    ('嘉庆', 1796, 1820, '清', 'reign'),
+    ('道光', 1821, 1850, '清', 'reign'),
+    ('咸丰', 1851, 1861, '清', 'reign'),
+    ('同治', 1862, 1874, '清', 'reign'),
+    ('光绪', 1875, 1908, '清', 'reign'),
+    ('宣统', 1909, 1911, '清', 'reign');
+
+
+-- 文物的年份范围，由朝代和年代文本推断或手动填写；未能确定时为 NULL
+ALTER TABLE artifacts ADD COLUMN date_from INTEGER;
+ALTER TABLE artifacts ADD COLUMN date_to INTEGER CHECK (date_to >= date_from);
+
+CREATE INDEX IF NOT EXISTS idx_artifacts_dates ON artifacts(date_from, date_to);
@@ -0,0 +1,99 @@
+-- 类别、材质、收藏机构的受控词表；code 为写入 artifacts 对应文本列的值
+CREATE TABLE IF NOT EXISTS vocabulary_terms (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    vocabulary TEXT NOT NULL CHECK (vocabulary IN ('category', 'material', 'collection')),
+    code TEXT NOT NULL,
+    label_zh TEXT NOT NULL,
+    label_en TEXT NOT NULL DEFAULT '',
+    -- 上级词条，须属于同一词表，如 鼎 → 礼器 → 青铜器
+    parent_id INTEGER REFERENCES vocabulary_terms (id),
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (vocabulary, code)
+);
+
+CREATE INDEX IF NOT EXISTS idx_vocabulary_terms_parent ON vocabulary_terms(parent_id);
+
+
+INSERT OR IGNORE INTO vocabulary_terms (vocabulary, code, label_zh, label_en, sort_order) VALUES
+    ('category', 'bronze', '青铜器', 'Bronze', 1),
+    ('category', 'ceramics', '陶瓷器', 'Ceramics', 2),
+    ('category', 'jade', '玉器', 'Jade', 3),
+    ('category', 'calligraphy', '书画', 'Calligraphy and Painting', 4),
+    ('category', 'sculpture', '雕塑', 'Sculpture', 5),
+    ('material', '青铜', '青铜', 'Bronze', 1),
+    ('material', '金', '金', 'Gold', 2),
+    ('material', '银', '银', 'Silver', 3),
+    ('material', '铁', '铁', 'Iron', 4),
+    ('material', '陶', '陶', 'Earthenware', 5),
+    ('material', '瓷', '瓷', 'Porcelain', 6),
+    ('material', '玉', '玉', 'Jade', 7),
+    ('material', '石', '石', 'Stone', 8),
+    ('material', '漆木', '漆木', 'Lacquered wood', 9),
+    ('material', '纸本', '纸本', 'Ink on paper', 10),
+    ('material', '绢本', '绢本', 'Ink on silk', 11),
+    ('material', '丝织品', '丝织品', 'Silk textile', 12),
+    ('collection', '故宫博物院', '故宫博物院', 'The Palace Museum', 1),
+    ('collection', '台北故宫博物院', '台北故宫博物院', 'National Palace Museum, Taipei', 2),
+    ('collection', '中国国家博物馆', '中国国家博物馆', 'National Museum of China', 3),
+    ('collection', '上海博物馆', '上海博物馆', 'Shanghai Museum', 4),
+    ('collection', '南京博物院', '南京博物院', 'Nanjing Museum', 5),
+    ('collection', '陕西历史博物馆', '陕西历史博物馆', 'Shaanxi History Museum', 6),
+    ('collection', '河南博物院', '河南博物院', 'Henan Museum', 7),
+    ('collection', '湖北省博物馆', '湖北省博物馆', 'Hubei Provincial Museum', 8),
+    ('collection', '湖南博物院', '湖南博物院', 'Hunan Museum', 9),
+    ('collection', '河北博物院', '河北博物院', 'Hebei Museum', 10),
+    ('collection', '浙江省博物馆', '浙江省博物馆', 'Zhejiang Provincial Museum', 11),
+    ('collection', '甘肃省博物馆', '甘肃省博物馆', 'Gansu Provincial Museum', 12),
+    ('collection', '三星堆博物馆', '三星堆博物馆', 'Sanxingdui Museum', 13);
+
+
+INSERT OR IGNORE INTO vocabulary_terms (vocabulary, code, label_zh, label_en, parent_id, sort_order)
+SELECT 'category', 'ritual_vessel', '礼器', 'Ritual vessel', id, 1
+FROM vocabulary_terms WHERE vocabulary = 'category' AND code = 'bronze';
+
+INSERT OR IGNORE INTO vocabulary_terms (vocabulary, code, label_zh, label_en, parent_id, sort_order)
+SELECT 'category', 'ding', '鼎', 'Ding', id, 1
+FROM vocabulary_terms WHERE vocabulary = 'category' AND code = 'ritual_vessel';
+
+INSERT OR IGNORE INTO vocabulary_terms (vocabulary, code, label_zh, label_en, parent_id, sort_order)
+SELECT 'material', '和田玉', '和田玉', 'Hetian jade', id, 1
+FROM vocabulary_terms WHERE vocabulary = 'material' AND code = '玉';
+
+
+-- 已有数据中的其他取值先原样收入词表，之后可在管理界面合并
+INSERT OR IGNORE INTO vocabulary_terms (vocabulary, code, label_zh)
+SELECT DISTINCT 'category', category, category FROM artifacts WHERE category <> '';
+
+INSERT OR IGNORE INTO vocabulary_terms (vocabulary, code, label_zh)
+SELECT DISTINCT 'material', material, material FROM artifacts WHERE material <> '';
+
+INSERT OR IGNORE INTO vocabulary_terms (vocabulary, code, label_zh)
+SELECT DISTINCT 'collection', collection, collection FROM artifacts WHERE collection <> '';
+
+
+-- 文本列保留词条的 code，供筛选和全文检索使用；外键指向词条
+ALTER TABLE artifacts ADD COLUMN category_id INTEGER REFERENCES vocabulary_terms (id);
+ALTER TABLE artifacts ADD COLUMN material_id INTEGER REFERENCES vocabulary_terms (id);
+ALTER TABLE artifacts ADD COLUMN collection_id INTEGER REFERENCES vocabulary_terms (id);
+
+UPDATE artifacts SET
+    category_id = (SELECT t.id FROM vocabulary_terms t WHERE t.vocabulary = 'category' AND t.code = artifacts.category),
+    material_id = (SELECT t.id FROM vocabulary_terms t WHERE t.vocabulary = 'material' AND t.code = artifacts.material),
+    collection_id = (SELECT t.id FROM vocabulary_terms t WHERE t.vocabulary = 'collection' AND t.code = artifacts.collection);
+
+CREATE INDEX IF NOT EXISTS idx_artifacts_category_id ON artifacts(category_id);
+CREATE INDEX IF NOT EXISTS idx_artifacts_material_id ON artifacts(material_id);
+CREATE INDEX IF NOT EXISTS idx_artifacts_collection_id ON artifacts(collection_id);
+
+
+-- 词条 code 修改后同步文物的文本列
+CREATE TRIGGER IF NOT EXISTS vocabulary_terms_au AFTER UPDATE OF code ON vocabulary_terms BEGIN
+    UPDATE artifacts SET category = new.code WHERE new.vocabulary = 'category' AND category_id = new.id;
+    UPDATE artifacts SET material = new.code WHERE new.vocabulary = 'material' AND material_id = new.id;
+    UPDATE artifacts SET collection = new.code WHERE new.vocabulary = 'collection' AND collection_id = new.id;
+END;
+
+
+INSERT OR IGNORE INTO role_permissions (role_id, permission)
+SELECT id, 'vocabulary:manage' FROM roles WHERE name IN ('curator', 'admin');
@@ -0,0 +1,2 @@
+-- 因用户取消而不完整的助手回复
+ALTER TABLE conversation_messages ADD COLUMN cancelled BOOLEAN NOT NULL DEFAULT 0;
@@ -1,5 +1,61 @@
 // src-tauri/src/db/migrations/mod.rs
-use sqlx::migrate::Migrator;
+use sqlx::{migrate::Migrator, SqlitePool};
 
 // 定义迁移目录
-pub static MIGRATOR: Migrator = sqlx::migrate!("./src/db/migrations");
\ No newline at end of file
+//
+// 迁移按版本号顺序执行，空库可由此建出完整的表结构、索引和种子数据。
+// 已应用的迁移文件不可再修改（sqlx 会校验校验和），结构变更请新增迁移文件。
+pub static MIGRATOR: Migrator = sqlx::migrate!("./src/db/migrations");
+
+// 001-003 最早是在其他环境中执行的，已有数据库里记录的校验和与仓库中的文件不一致
+// （表结构相同），导致后续迁移在校验阶段就失败。
+// 这里列出旧数据库中记录的校验和（SHA-384 十六进制），只修正这些已知的值，
+// 其他不一致仍交给 sqlx 报错，以便发现对已应用迁移文件的修改
+const LEGACY_CHECKSUMS: &[(i64, &str)] = &[
+    (
+        1,
+        "E587F5D3478539925598479E35B476023224EBCFDF006675EBFC772201EDFEF08DED8E732047DB1A2DE3AE17A7E5C082",
+    ),
+    (
+        2,
+        "45B921D1E820B7502DF8B9B0F820306A363D5DEB57A1B7E3E17F3ECB211B8925BB32391DDED76828FFA4C53F67818044",
+    ),
+    (
+        3,
+        "A491EF395A458031257A4B1404C7680807385D8848D281646832092EF83741D41E01F3522602CA63160E33FC991B709A",
+    ),
+];
+
+/// 修正旧数据库中 001-003 迁移的已知旧校验和，使新增迁移可以继续执行
+pub async fn repair_legacy_checksums(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+    // 全新数据库还没有迁移记录表
+    let table = sqlx::query(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'",
+    )
+    .fetch_optional(pool)
+    .await?;
+    if table.is_none() {
+        return Ok(0);
+    }
+
+    let mut repaired = 0;
+    for &(version, legacy_checksum) in LEGACY_CHECKSUMS {
+        let Some(migration) = MIGRATOR
+            .iter()
+            .find(|m| m.version == version && !m.migration_type.is_down_migration())
+        else {
+            continue;
+        };
+        let result = sqlx::query(
+            "UPDATE _sqlx_migrations SET checksum = ? WHERE version = ? AND success = 1 AND hex(checksum) = ?",
+        )
+        .bind(migration.checksum.as_ref())
+        .bind(version)
+        .bind(legacy_checksum)
+        .execute(pool)
+        .await?;
+        repaired += result.rows_affected();
+    }
+
+    Ok(repaired)
+}
@@ -1,38 +1,168 @@
 // src-tauri/src/db/mod.rs
+pub mod config;
 pub mod models;
 pub mod migrations;
+pub mod state;
 
-use sqlx::{sqlite::SqlitePoolOptions, SqlitePool};
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+    SqlitePool,
+};
+use std::str::FromStr;
+use std::time::Duration;
 use tauri::AppHandle;
-use anyhow::Result;
+
+use config::{copy_seed_database, DatabaseConfig};
+use migrations::{repair_legacy_checksums, MIGRATOR};
+pub use state::{DbState, DbStatus, InitError, InitStage, DB_STATUS_EVENT};
+
+pub type InitResult<T> = std::result::Result<T, InitError>;
 
 pub struct Database;
 
 impl Database {
-    pub async fn init(_app_handle: &AppHandle) -> Result<SqlitePool> {
-        // 使用项目根目录下的data目录
-        let database_path = "../data/cultural_heritage.sqlite";
-        let database_url = format!("sqlite:{}", database_path);
+    pub async fn init(app_handle: &AppHandle) -> InitResult<SqlitePool> {
+        let config = DatabaseConfig::load(app_handle)
+            .map_err(|e| InitError::new(InitStage::Config, e))?;
+        let database_path = config
+            .resolve_path(app_handle)
+            .map_err(|e| InitError::new(InitStage::Config, e))?;
+
+        // 首次运行时复制打包的种子数据库
+        copy_seed_database(app_handle, &database_path)
+            .map_err(|e| InitError::new(InitStage::Config, e))?;
+
+        let database_url = format!("sqlite:{}", database_path.display());
         
-        println!("数据库路径: {}", database_path);
+        println!("数据库路径: {}", database_path.display());
         println!("数据库URL: {}", database_url);
         
+        Self::open_with(&database_url, &config).await
+    }
+
+    /// 打开（必要时创建）数据库并执行全部迁移
+    ///
+    /// 空库也能通过迁移建出完整的表结构和种子数据，测试时可传入 `sqlite::memory:`。
+    pub async fn open_with(database_url: &str, config: &DatabaseConfig) -> InitResult<SqlitePool> {
+        let is_memory = database_url.contains(":memory:");
+
+        let mut options = SqliteConnectOptions::from_str(database_url)
+            .map_err(|e| InitError::new(InitStage::Config, anyhow::anyhow!("数据库URL无效: {}", e)))?
+            .create_if_missing(true)
+            .foreign_keys(true)
+            .busy_timeout(Duration::from_millis(config.busy_timeout_ms));
+
+        // 内存数据库不支持 WAL
+        if config.wal && !is_memory {
+            options = options.journal_mode(SqliteJournalMode::Wal);
+        }
+
+        // 内存数据库每个连接都是独立的库，只能使用单连接
+        let max_connections = if is_memory { 1 } else { config.max_connections.max(1) };
+
         // 创建数据库连接池
         let pool = SqlitePoolOptions::new()
-            .max_connections(5)
-            .connect(&database_url)
+            .max_connections(max_connections)
+            .connect_with(options)
             .await
-            .map_err(|e| anyhow::anyhow!("数据库连接失败: {}", e))?;
-            
+            .map_err(|e| InitError::new(InitStage::Connect, anyhow::anyhow!("数据库连接失败: {}", e)))?;
+
+        let repaired = repair_legacy_checksums(&pool)
+            .await
+            .map_err(|e| InitError::new(InitStage::Migrate, anyhow::anyhow!("修正迁移记录失败: {}", e)))?;
+        if repaired > 0 {
+            println!("已修正 {} 条旧迁移记录的校验和", repaired);
+        }
+
         // 运行迁移
         println!("开始数据库迁移...");
-        match sqlx::migrate!("./src/db/migrations")
+        MIGRATOR
             .run(&pool)
-            .await {
-            Ok(_) => println!("数据库迁移成功"),
-            Err(e) => eprintln!("数据库迁移失败: {}", e),
-        }
+            .await
+            .map_err(|e| InitError::new(InitStage::Migrate, anyhow::anyhow!("数据库迁移失败: {}", e)))?;
+        println!("数据库迁移成功");
             
         Ok(pool)
     }
-}
\ No newline at end of file
+}
+
+/// 测试用：在内存中执行全部迁移，得到带种子数据的空白数据库
+#[cfg(test)]
+pub async fn memory_pool() -> SqlitePool {
+    Database::open_with("sqlite::memory:", &DatabaseConfig::default())
+        .await
+        .unwrap_or_else(|e| panic!("创建内存数据库失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::Row;
+
+    async fn count(pool: &SqlitePool, table: &str) -> i64 {
+        sqlx::query(&format!("SELECT COUNT(*) AS count FROM {}", table))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+            .get("count")
+    }
+
+    #[tokio::test]
+    async fn builds_schema_and_seed_data_from_migrations() {
+        let pool = memory_pool().await;
+
+        for table in [
+            "artifacts",
+            "users",
+            "user_favorites",
+            "browsing_history",
+            "roles",
+            "role_permissions",
+            "user_roles",
+            "sessions",
+            "conversations",
+            "conversation_messages",
+            "artifact_media",
+            "artifacts_fts",
+            "artifacts_rtree",
+            "chronology_periods",
+            "vocabulary_terms",
+        ] {
+            let exists = sqlx::query("SELECT 1 FROM sqlite_master WHERE name = ?")
+                .bind(table)
+                .fetch_optional(&pool)
+                .await
+                .unwrap();
+            assert!(exists.is_some(), "缺少表 {}", table);
+        }
+
+        // 种子数据
+        assert!(count(&pool, "artifacts").await > 0);
+        assert_eq!(count(&pool, "artifact_media").await, count(&pool, "artifacts").await);
+        assert!(count(&pool, "chronology_periods").await > 0);
+        assert!(count(&pool, "vocabulary_terms").await > 0);
+        let roles: Vec<String> = sqlx::query("SELECT name FROM roles ORDER BY name")
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get("name"))
+            .collect();
+        assert_eq!(roles, ["admin", "curator", "viewer"]);
+    }
+
+    #[tokio::test]
+    async fn browsing_history_keeps_one_row_per_user_and_artifact() {
+        let pool = memory_pool().await;
+        sqlx::query("INSERT INTO users (username, email, password_hash) VALUES ('tester', 't@example.com', 'x')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let insert = "INSERT INTO browsing_history (user_id, artifact_id) VALUES (1, 1)";
+        sqlx::query(insert).execute(&pool).await.unwrap();
+
+        let duplicate = sqlx::query(insert).execute(&pool).await.unwrap_err();
+        assert!(duplicate.to_string().contains("UNIQUE"), "{}", duplicate);
+        assert_eq!(count(&pool, "browsing_history").await, 1);
+    }
+}
@@ -16,6 +16,11 @@ pub struct NewArtifact {
     pub discovery_location: String,
     pub collection: String,
     pub category: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub location_uncertainty_m: Option<f64>,
+    pub date_from: Option<i64>,
+    pub date_to: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -69,7 +74,75 @@ pub struct ArtifactWithFavorite {
     pub discovery_location: String,
     pub collection: String,
     pub category: String,
+    /// 出土地点坐标（WGS84），未定位时为空
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// 坐标的不确定半径（米）
+    pub location_uncertainty_m: Option<f64>,
+    /// 年份范围（公元前为负数），未能确定时为空
+    pub date_from: Option<i64>,
+    pub date_to: Option<i64>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub is_favorite: bool,
-}
\ No newline at end of file
+    /// 主图文件缺失（取自 artifact_media.file_missing），前端显示占位图
+    pub image_missing: bool,
+}
+/// 文物媒体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaType {
+    Image,
+    #[serde(rename = "model_3d")]
+    Model3d,
+    Audio,
+    Video,
+    Document,
+}
+
+impl MediaType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MediaType::Image => "image",
+            MediaType::Model3d => "model_3d",
+            MediaType::Audio => "audio",
+            MediaType::Video => "video",
+            MediaType::Document => "document",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "image" => Some(MediaType::Image),
+            "model_3d" => Some(MediaType::Model3d),
+            "audio" => Some(MediaType::Audio),
+            "video" => Some(MediaType::Video),
+            "document" => Some(MediaType::Document),
+            _ => None,
+        }
+    }
+}
+
+/// 文物的一张图片或其他媒体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactMedia {
+    pub id: i64,
+    pub artifact_id: i64,
+    pub media_type: MediaType,
+    /// 媒体目录下的文件名
+    pub path: String,
+    pub caption: String,
+    pub sort_order: i64,
+    pub is_primary: bool,
+    /// 上次扫描或写入时文件不存在
+    pub file_missing: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 文物详情，附带全部媒体
+#[derive(Debug, Serialize)]
+pub struct ArtifactDetail {
+    #[serde(flatten)]
+    pub artifact: ArtifactWithFavorite,
+    pub media: Vec<ArtifactMedia>,
+}
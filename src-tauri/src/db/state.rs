@@ -0,0 +1,95 @@
+// src-tauri/src/db/state.rs
+use crate::error::{Error, Result};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::sync::{OnceLock, RwLock};
+
+// 数据库状态变化时发送给前端的事件
+pub const DB_STATUS_EVENT: &str = "database-status";
+
+/// 启动阶段，用于定位初始化失败的位置
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InitStage {
+    Config,
+    Connect,
+    Migrate,
+    Bootstrap,
+}
+
+/// 数据库初始化失败的原因
+#[derive(Debug)]
+pub struct InitError {
+    pub stage: InitStage,
+    pub source: anyhow::Error,
+}
+
+impl InitError {
+    pub fn new(stage: InitStage, source: impl Into<anyhow::Error>) -> Self {
+        InitError {
+            stage,
+            source: source.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for InitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} 阶段失败: {}", self.stage, self.source)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum DbStatus {
+    Initializing,
+    Ready,
+    Failed { stage: InitStage, message: String },
+}
+
+/// 数据库连接池及其就绪状态，应用启动时即注册，初始化完成后才可取到连接池
+#[derive(Debug)]
+pub struct DbState {
+    pool: OnceLock<SqlitePool>,
+    status: RwLock<DbStatus>,
+}
+
+impl Default for DbState {
+    fn default() -> Self {
+        DbState {
+            pool: OnceLock::new(),
+            status: RwLock::new(DbStatus::Initializing),
+        }
+    }
+}
+
+impl DbState {
+    /// 获取连接池，数据库未就绪时返回错误
+    pub fn pool(&self) -> Result<SqlitePool> {
+        if let Some(pool) = self.pool.get() {
+            return Ok(pool.clone());
+        }
+
+        let reason = match self.status() {
+            DbStatus::Failed { message, .. } => format!("数据库初始化失败: {}", message),
+            _ => "数据库正在初始化，请稍后重试".to_string(),
+        };
+        Err(Error::DatabaseNotReady(reason))
+    }
+
+    pub fn status(&self) -> DbStatus {
+        self.status.read().unwrap().clone()
+    }
+
+    pub fn set_ready(&self, pool: SqlitePool) {
+        let _ = self.pool.set(pool);
+        *self.status.write().unwrap() = DbStatus::Ready;
+    }
+
+    pub fn set_failed(&self, error: &InitError) {
+        *self.status.write().unwrap() = DbStatus::Failed {
+            stage: error.stage,
+            message: error.source.to_string(),
+        };
+    }
+}
@@ -1,37 +1,130 @@
 // src-tauri/src/error.rs
+use serde::ser::SerializeStruct;
 use serde::Serialize;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// 一个字段的校验错误
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+fn join_messages(errors: &[FieldError]) -> String {
+    errors.iter().map(|e| e.message.as_str()).collect::<Vec<_>>().join("；")
+}
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Database error: {0}")]
-    Database(#[from] sqlx::Error),
-    
+    Database(sqlx::Error),
+
     #[error("Anyhow error: {0}")]
     Anyhow(#[from] anyhow::Error),
-    
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
-    
-    #[error("Authentication error: {0}")]
-    Auth(String),
-    
-    #[error("Not found: {0}")]
+
+    #[error("{0}")]
+    AuthRequired(String),
+
+    #[error("{0}")]
+    InvalidCredentials(String),
+
+    #[error("{0}")]
+    Forbidden(String),
+
+    #[error("{message}")]
+    Validation {
+        message: String,
+        field: Option<String>,
+    },
+
+    /// 多个字段同时校验失败
+    #[error("{}", join_messages(.0))]
+    InvalidFields(Vec<FieldError>),
+
+    #[error("{0}")]
     NotFound(String),
-    
-    #[error("Network error: {0}")]
-    Network(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    Upstream(String),
+
+    #[error("Database not ready: {0}")]
+    DatabaseNotReady(String),
+}
+
+impl Error {
+    /// 参数校验失败
+    pub fn validation(message: impl Into<String>) -> Self {
+        Error::Validation { message: message.into(), field: None }
+    }
+
+    /// 某个字段校验失败，前端可据此定位到表单项
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Error::Validation { message: message.into(), field: Some(field.into()) }
+    }
+
+    /// 稳定的错误码，前端据此区分错误类型
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Database(_) => "DATABASE",
+            Error::Anyhow(_) => "INTERNAL",
+            Error::Io(_) => "IO",
+            Error::AuthRequired(_) => "AUTH_REQUIRED",
+            Error::InvalidCredentials(_) => "INVALID_CREDENTIALS",
+            Error::Forbidden(_) => "FORBIDDEN",
+            Error::Validation { .. } | Error::InvalidFields(_) => "VALIDATION",
+            Error::NotFound(_) => "NOT_FOUND",
+            Error::Conflict(_) => "CONFLICT",
+            Error::Upstream(_) => "UPSTREAM",
+            Error::DatabaseNotReady(_) => "DATABASE_NOT_READY",
+        }
+    }
+
+    fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            Error::Validation { field: Some(field), .. } => {
+                Some(serde_json::json!({ "field": field }))
+            }
+            // field 为第一个出错的字段，errors 为全部字段的错误
+            Error::InvalidFields(errors) => Some(serde_json::json!({
+                "field": errors.first().map(|e| e.field.as_str()),
+                "errors": errors,
+            })),
+            _ => None,
+        }
+    }
 }
 
-// 实现 Serialize 以便将错误发送到前端
+// 唯一约束冲突单独映射为 CONFLICT，记录不存在映射为 NOT_FOUND
+impl From<sqlx::Error> for Error {
+    fn from(err: sqlx::Error) -> Self {
+        match &err {
+            sqlx::Error::RowNotFound => Error::NotFound("记录不存在".to_string()),
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                Error::Conflict(db_err.message().to_string())
+            }
+            _ => Error::Database(err),
+        }
+    }
+}
+
+// 序列化为 { code, message, details } 发送到前端
 impl Serialize for Error {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(self.to_string().as_ref())
+        let mut state = serializer.serialize_struct("Error", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
     }
 }
-
@@ -0,0 +1,193 @@
+// src-tauri/src/geo/cluster.rs
+//! 地图聚合：按缩放级别在 Web 墨卡托像素网格上聚合文物点，并生成热力图密度网格
+use super::{BoundingBox, GeoPoint};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// 聚合网格的边长（屏幕像素）
+const CLUSTER_CELL_PX: f64 = 64.0;
+const TILE_SIZE_PX: f64 = 256.0;
+/// 墨卡托投影的纬度上限
+const MAX_MERCATOR_LAT: f64 = 85.051_128_78;
+
+/// 参与聚合的文物点
+#[derive(Debug, Clone)]
+pub struct ClusterPoint {
+    pub id: i64,
+    pub title: String,
+    pub point: GeoPoint,
+    pub category: String,
+    pub dynasty: String,
+}
+
+/// 一组相邻的文物
+#[derive(Debug, Serialize)]
+pub struct MapCluster {
+    pub count: usize,
+    /// 组内文物坐标的平均值
+    #[serde(flatten)]
+    pub centroid: GeoPoint,
+    /// 组内文物的范围，点击后可缩放到该范围
+    pub bounds: BoundingBox,
+    /// 数量最多的类别和朝代，数量相同时取名称较小者
+    pub dominant_category: String,
+    pub dominant_dynasty: String,
+    /// 只有一件文物时为其 id 和名称
+    pub artifact_id: Option<i64>,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DensityCell {
+    pub row: u32,
+    pub col: u32,
+    /// 格子中心
+    #[serde(flatten)]
+    pub center: GeoPoint,
+    pub count: u32,
+}
+
+/// 热力图密度网格，只返回有文物的格子
+#[derive(Debug, Serialize)]
+pub struct DensityGrid {
+    pub south: f64,
+    pub west: f64,
+    /// 格子的纬度和经度跨度（度）
+    pub cell_lat: f64,
+    pub cell_lng: f64,
+    pub rows: u32,
+    pub cols: u32,
+    /// 单个格子的最大数量，用于归一化
+    pub max: u32,
+    pub cells: Vec<DensityCell>,
+}
+
+/// 坐标在指定缩放级别下的墨卡托像素位置
+fn project(point: &GeoPoint, zoom: u8) -> (f64, f64) {
+    let scale = TILE_SIZE_PX * 2f64.powi(zoom as i32);
+    let lat = point.latitude.clamp(-MAX_MERCATOR_LAT, MAX_MERCATOR_LAT).to_radians();
+    let x = (point.longitude + 180.0) / 360.0 * scale;
+    let y = (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / PI) / 2.0 * scale;
+    (x, y)
+}
+
+#[derive(Default)]
+struct ClusterAcc<'a> {
+    count: usize,
+    lat_sum: f64,
+    lng_sum: f64,
+    bounds: Option<BoundingBox>,
+    categories: HashMap<&'a str, usize>,
+    dynasties: HashMap<&'a str, usize>,
+    first: Option<&'a ClusterPoint>,
+}
+
+fn dominant(counts: &HashMap<&str, usize>) -> String {
+    counts
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+        .map(|(name, _)| name.to_string())
+        .unwrap_or_default()
+}
+
+/// 按缩放级别聚合，结果按数量从多到少排列
+pub fn cluster_points(points: &[ClusterPoint], zoom: u8) -> Vec<MapCluster> {
+    let mut cells: HashMap<(i64, i64), ClusterAcc> = HashMap::new();
+    for p in points {
+        let (x, y) = project(&p.point, zoom);
+        let key = ((x / CLUSTER_CELL_PX).floor() as i64, (y / CLUSTER_CELL_PX).floor() as i64);
+        let acc = cells.entry(key).or_default();
+        acc.count += 1;
+        acc.lat_sum += p.point.latitude;
+        acc.lng_sum += p.point.longitude;
+        acc.bounds = Some(match acc.bounds {
+            Some(b) => BoundingBox {
+                south: b.south.min(p.point.latitude),
+                west: b.west.min(p.point.longitude),
+                north: b.north.max(p.point.latitude),
+                east: b.east.max(p.point.longitude),
+            },
+            None => BoundingBox {
+                south: p.point.latitude,
+                west: p.point.longitude,
+                north: p.point.latitude,
+                east: p.point.longitude,
+            },
+        });
+        *acc.categories.entry(p.category.as_str()).or_default() += 1;
+        *acc.dynasties.entry(p.dynasty.as_str()).or_default() += 1;
+        acc.first.get_or_insert(p);
+    }
+
+    let mut clusters: Vec<MapCluster> = cells
+        .into_values()
+        .filter_map(|acc| {
+            let single = acc.first.filter(|_| acc.count == 1);
+            Some(MapCluster {
+                count: acc.count,
+                centroid: GeoPoint {
+                    latitude: acc.lat_sum / acc.count as f64,
+                    longitude: acc.lng_sum / acc.count as f64,
+                },
+                bounds: acc.bounds?,
+                dominant_category: dominant(&acc.categories),
+                dominant_dynasty: dominant(&acc.dynasties),
+                artifact_id: single.map(|p| p.id),
+                title: single.map(|p| p.title.clone()),
+            })
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(a.centroid.latitude.total_cmp(&b.centroid.latitude))
+            .then(a.centroid.longitude.total_cmp(&b.centroid.longitude))
+    });
+    clusters
+}
+
+/// 把范围按经度方向 cols 等分，纬度方向按相同的度数划分，统计每格的文物数量
+pub fn density_grid(points: &[ClusterPoint], bbox: &BoundingBox, cols: u32) -> DensityGrid {
+    let cols = cols.max(1);
+    let cell_lng = ((bbox.east - bbox.west) / cols as f64).max(f64::EPSILON);
+    let rows = (((bbox.north - bbox.south) / cell_lng).ceil() as u32).clamp(1, cols * 4);
+    let cell_lat = ((bbox.north - bbox.south) / rows as f64).max(f64::EPSILON);
+
+    let mut counts: HashMap<(u32, u32), u32> = HashMap::new();
+    for p in points {
+        let row = ((p.point.latitude - bbox.south) / cell_lat).floor();
+        let col = ((p.point.longitude - bbox.west) / cell_lng).floor();
+        if row < 0.0 || col < 0.0 {
+            continue;
+        }
+        // 落在北边或东边界上的点归入最后一格
+        let key = ((row as u32).min(rows - 1), (col as u32).min(cols - 1));
+        *counts.entry(key).or_default() += 1;
+    }
+
+    let mut cells: Vec<DensityCell> = counts
+        .into_iter()
+        .map(|((row, col), count)| DensityCell {
+            row,
+            col,
+            center: GeoPoint {
+                latitude: bbox.south + (row as f64 + 0.5) * cell_lat,
+                longitude: bbox.west + (col as f64 + 0.5) * cell_lng,
+            },
+            count,
+        })
+        .collect();
+    cells.sort_by_key(|c| (c.row, c.col));
+
+    DensityGrid {
+        south: bbox.south,
+        west: bbox.west,
+        cell_lat,
+        cell_lng,
+        rows,
+        cols,
+        max: cells.iter().map(|c| c.count).max().unwrap_or(0),
+        cells,
+    }
+}
@@ -0,0 +1,195 @@
+// src-tauri/src/geo/mod.rs
+//! 文物出土地点的坐标及离线地名解析
+//!
+//! 地名表随应用编译（`gazetteer.tsv`），按地点文本中出现的地名匹配，
+//! 优先取范围最小（不确定半径最小）的地名；文本中提到省份时排除其他省份的同名地名。
+pub mod cluster;
+
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+const GAZETTEER_DATA: &str = include_str!("gazetteer.tsv");
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// WGS84 坐标
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GeoPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl GeoPoint {
+    pub fn validate(&self, field: &str) -> Result<()> {
+        if !(-90.0..=90.0).contains(&self.latitude) || !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(Error::invalid_field(field, "经纬度超出范围"));
+        }
+        Ok(())
+    }
+
+    /// 两点间的大圆距离（公里）
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let dlat = lat2 - lat1;
+        let dlng = (other.longitude - self.longitude).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlng / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+/// 经纬度范围
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub south: f64,
+    pub west: f64,
+    pub north: f64,
+    pub east: f64,
+}
+
+impl BoundingBox {
+    pub fn validate(&self) -> Result<()> {
+        let valid = (-90.0..=90.0).contains(&self.south)
+            && (-90.0..=90.0).contains(&self.north)
+            && (-180.0..=180.0).contains(&self.west)
+            && (-180.0..=180.0).contains(&self.east)
+            && self.south <= self.north
+            && self.west <= self.east;
+        if !valid {
+            return Err(Error::invalid_field("bbox", "范围无效，需满足 south ≤ north、west ≤ east"));
+        }
+        Ok(())
+    }
+
+    /// 包含以 center 为圆心、radius_km 为半径的圆的最小范围
+    pub fn around(center: &GeoPoint, radius_km: f64) -> Self {
+        let dlat = (radius_km / EARTH_RADIUS_KM).to_degrees();
+        // 高纬度时经度跨度过大，直接取全部经度
+        let cos_lat = center.latitude.to_radians().cos();
+        let dlng = if cos_lat > 0.01 { dlat / cos_lat } else { 180.0 };
+        BoundingBox {
+            south: (center.latitude - dlat).max(-90.0),
+            west: (center.longitude - dlng).max(-180.0),
+            north: (center.latitude + dlat).min(90.0),
+            east: (center.longitude + dlng).min(180.0),
+        }
+    }
+}
+
+/// 地名表中的一个地点
+#[derive(Debug, Clone, Serialize)]
+pub struct Place {
+    pub name: String,
+    /// 所属省级行政区
+    pub province: String,
+    #[serde(flatten)]
+    pub point: GeoPoint,
+    /// 不确定半径（米）
+    pub uncertainty_m: f64,
+}
+
+impl Place {
+    fn is_province(&self) -> bool {
+        self.name == self.province
+    }
+}
+
+pub struct Gazetteer {
+    places: Vec<Place>,
+}
+
+impl Gazetteer {
+    fn parse(data: &str) -> Self {
+        let places = data
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+                let [name, province, latitude, longitude, uncertainty] = fields[..] else {
+                    return None;
+                };
+                Some(Place {
+                    name: name.to_string(),
+                    province: province.to_string(),
+                    point: GeoPoint {
+                        latitude: latitude.parse().ok()?,
+                        longitude: longitude.parse().ok()?,
+                    },
+                    uncertainty_m: uncertainty.parse().ok()?,
+                })
+            })
+            .collect();
+        Gazetteer { places }
+    }
+
+    /// 解析地点文本，如「河南省安阳市殷墟」
+    pub fn geocode(&self, text: &str) -> Option<&Place> {
+        let text: String = text.split_whitespace().collect();
+        if text.is_empty() {
+            return None;
+        }
+        let matched: Vec<&Place> = self.places.iter().filter(|p| text.contains(&p.name)).collect();
+        let provinces: Vec<&str> = matched
+            .iter()
+            .filter(|p| p.is_province())
+            .map(|p| p.province.as_str())
+            .collect();
+
+        matched
+            .into_iter()
+            .filter(|p| provinces.is_empty() || provinces.contains(&p.province.as_str()))
+            .min_by(|a, b| {
+                a.uncertainty_m
+                    .total_cmp(&b.uncertainty_m)
+                    .then(b.name.chars().count().cmp(&a.name.chars().count()))
+            })
+    }
+}
+
+/// 内置地名表
+pub fn gazetteer() -> &'static Gazetteer {
+    static GAZETTEER: OnceLock<Gazetteer> = OnceLock::new();
+    GAZETTEER.get_or_init(|| Gazetteer::parse(GAZETTEER_DATA))
+}
+
+/// 文物坐标
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coordinates {
+    pub point: GeoPoint,
+    pub uncertainty_m: Option<f64>,
+}
+
+/// 根据出土地点（优先）或所在地推断文物坐标
+pub fn geocode_artifact(discovery_location: &str, location: &str) -> Option<Coordinates> {
+    [discovery_location, location]
+        .into_iter()
+        .find_map(|text| gazetteer().geocode(text))
+        .map(|place| Coordinates {
+            point: place.point,
+            uncertainty_m: Some(place.uncertainty_m),
+        })
+}
+
+/// 整理输入的坐标：经纬度需同时提供，均未提供时按地名推断
+pub fn resolve_coordinates(
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    uncertainty_m: Option<f64>,
+    discovery_location: &str,
+    location: &str,
+) -> Result<Option<Coordinates>> {
+    if let Some(uncertainty) = uncertainty_m {
+        if !uncertainty.is_finite() || uncertainty < 0.0 {
+            return Err(Error::invalid_field("location_uncertainty_m", "不确定半径不能为负数"));
+        }
+    }
+    match (latitude, longitude) {
+        (Some(latitude), Some(longitude)) => {
+            let point = GeoPoint { latitude, longitude };
+            point.validate("latitude")?;
+            Ok(Some(Coordinates { point, uncertainty_m }))
+        }
+        (None, None) => Ok(geocode_artifact(discovery_location, location)),
+        _ => Err(Error::invalid_field("latitude", "经度和纬度需同时填写")),
+    }
+}
@@ -0,0 +1,317 @@
+// src-tauri/src/iiif/image_api.rs
+//! IIIF Image API 3.0 请求解析与图像处理（level2）
+use crate::error::{Error, Result};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use serde_json::json;
+use std::io::Cursor;
+
+/// 切片边长
+pub const TILE_SIZE: u32 = 512;
+
+/// 原图中的矩形区域 `(x, y, w, h)`
+pub type Rect = (u32, u32, u32, u32);
+
+/// 单次输出的最大像素数，`max` 尺寸会缩小到该范围内
+pub const MAX_AREA: u64 = 16_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Region {
+    Full,
+    Square,
+    Pixels { x: u32, y: u32, w: u32, h: u32 },
+    Percent { x: f64, y: f64, w: f64, h: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Size {
+    Max,
+    Width(u32),
+    Height(u32),
+    Percent(f64),
+    Exact(u32, u32),
+    /// `!w,h`：保持比例缩放到不超过 w×h
+    Confined(u32, u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quality {
+    Default,
+    Color,
+    Gray,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Jpg,
+    Png,
+}
+
+impl OutputFormat {
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            OutputFormat::Jpg => "image/jpeg",
+            OutputFormat::Png => "image/png",
+        }
+    }
+}
+
+/// 解析后的图像请求：`{region}/{size}/{rotation}/{quality}.{format}`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageRequest {
+    region: Region,
+    size: Size,
+    /// size 带 `^` 前缀时允许放大
+    upscale: bool,
+    rotation: u32,
+    mirror: bool,
+    quality: Quality,
+    pub format: OutputFormat,
+}
+
+fn bad_request(field: &str, message: impl Into<String>) -> Error {
+    Error::invalid_field(field, message)
+}
+
+fn parse_numbers<T: std::str::FromStr>(value: &str, count: usize, field: &str) -> Result<Vec<T>> {
+    let numbers = value
+        .split(',')
+        .map(|n| n.parse::<T>())
+        .collect::<std::result::Result<Vec<T>, _>>()
+        .map_err(|_| bad_request(field, format!("无效的 {}: {}", field, value)))?;
+    if numbers.len() != count {
+        return Err(bad_request(field, format!("无效的 {}: {}", field, value)));
+    }
+    Ok(numbers)
+}
+
+fn parse_region(value: &str) -> Result<Region> {
+    match value {
+        "full" => Ok(Region::Full),
+        "square" => Ok(Region::Square),
+        _ => {
+            if let Some(pct) = value.strip_prefix("pct:") {
+                let n = parse_numbers::<f64>(pct, 4, "region")?;
+                if n.iter().any(|v| *v < 0.0) || n[2] <= 0.0 || n[3] <= 0.0 {
+                    return Err(bad_request("region", format!("无效的 region: {}", value)));
+                }
+                Ok(Region::Percent { x: n[0], y: n[1], w: n[2], h: n[3] })
+            } else {
+                let n = parse_numbers::<u32>(value, 4, "region")?;
+                if n[2] == 0 || n[3] == 0 {
+                    return Err(bad_request("region", format!("无效的 region: {}", value)));
+                }
+                Ok(Region::Pixels { x: n[0], y: n[1], w: n[2], h: n[3] })
+            }
+        }
+    }
+}
+
+fn parse_size(value: &str) -> Result<(Size, bool)> {
+    let (upscale, value) = match value.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+
+    let size = if value == "max" {
+        Size::Max
+    } else if let Some(pct) = value.strip_prefix("pct:") {
+        let n: f64 = pct
+            .parse()
+            .map_err(|_| bad_request("size", format!("无效的 size: {}", value)))?;
+        if n <= 0.0 {
+            return Err(bad_request("size", format!("无效的 size: {}", value)));
+        }
+        Size::Percent(n)
+    } else if let Some(confined) = value.strip_prefix('!') {
+        let n = parse_numbers::<u32>(confined, 2, "size")?;
+        Size::Confined(n[0], n[1])
+    } else if let Some(w) = value.strip_suffix(',') {
+        Size::Width(w.parse().map_err(|_| bad_request("size", format!("无效的 size: {}", value)))?)
+    } else if let Some(h) = value.strip_prefix(',') {
+        Size::Height(h.parse().map_err(|_| bad_request("size", format!("无效的 size: {}", value)))?)
+    } else {
+        let n = parse_numbers::<u32>(value, 2, "size")?;
+        Size::Exact(n[0], n[1])
+    };
+
+    Ok((size, upscale))
+}
+
+impl ImageRequest {
+    /// 解析 URL 中的各段参数
+    pub fn parse(region: &str, size: &str, rotation: &str, quality_format: &str) -> Result<Self> {
+        let region = parse_region(region)?;
+        let (size, upscale) = parse_size(size)?;
+
+        let (mirror, degrees) = match rotation.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, rotation),
+        };
+        let rotation = match degrees {
+            "0" => 0,
+            "90" => 90,
+            "180" => 180,
+            "270" => 270,
+            _ => return Err(bad_request("rotation", "仅支持 0、90、180、270 度旋转")),
+        };
+
+        let (quality, format) = quality_format
+            .rsplit_once('.')
+            .ok_or_else(|| bad_request("format", "缺少图像格式"))?;
+        let quality = match quality {
+            "default" => Quality::Default,
+            "color" => Quality::Color,
+            "gray" => Quality::Gray,
+            _ => return Err(bad_request("quality", format!("不支持的 quality: {}", quality))),
+        };
+        let format = match format {
+            "jpg" => OutputFormat::Jpg,
+            "png" => OutputFormat::Png,
+            _ => return Err(bad_request("format", format!("不支持的格式: {}", format))),
+        };
+
+        Ok(ImageRequest { region, size, upscale, rotation, mirror, quality, format })
+    }
+
+    /// 不旋转、不镜像、不转灰度，可以直接返回现成的切片
+    pub fn is_unmodified(&self) -> bool {
+        self.rotation == 0 && !self.mirror && self.quality != Quality::Gray
+    }
+
+    /// 计算原图中的裁剪区域 `(x, y, w, h)` 及输出尺寸
+    pub fn resolve(&self, width: u32, height: u32) -> Result<(Rect, (u32, u32))> {
+        let region = self.resolve_region(width, height)?;
+        let size = self.resolve_size(region.2, region.3)?;
+        Ok((region, size))
+    }
+
+    // 计算裁剪区域，超出图像的部分被截掉
+    fn resolve_region(&self, width: u32, height: u32) -> Result<Rect> {
+        let (x, y, w, h) = match self.region {
+            Region::Full => (0, 0, width, height),
+            Region::Square => {
+                let side = width.min(height);
+                ((width - side) / 2, (height - side) / 2, side, side)
+            }
+            Region::Pixels { x, y, w, h } => (x, y, w, h),
+            Region::Percent { x, y, w, h } => (
+                (x / 100.0 * width as f64).round() as u32,
+                (y / 100.0 * height as f64).round() as u32,
+                (w / 100.0 * width as f64).round() as u32,
+                (h / 100.0 * height as f64).round() as u32,
+            ),
+        };
+
+        if x >= width || y >= height {
+            return Err(bad_request("region", "区域超出图像范围"));
+        }
+        let w = w.min(width - x);
+        let h = h.min(height - y);
+        if w == 0 || h == 0 {
+            return Err(bad_request("region", "区域超出图像范围"));
+        }
+        Ok((x, y, w, h))
+    }
+
+    // 根据裁剪区域计算输出尺寸
+    fn resolve_size(&self, rw: u32, rh: u32) -> Result<(u32, u32)> {
+        let scale = |s: f64| ((rw as f64 * s).round() as u32, (rh as f64 * s).round() as u32);
+
+        let (w, h) = match self.size {
+            Size::Max => {
+                let area = rw as u64 * rh as u64;
+                if area > MAX_AREA {
+                    // 向下取整，保证不超过 MAX_AREA
+                    let s = (MAX_AREA as f64 / area as f64).sqrt();
+                    ((rw as f64 * s).floor() as u32, (rh as f64 * s).floor() as u32)
+                } else {
+                    (rw, rh)
+                }
+            }
+            Size::Width(w) => (w, (rh as f64 * w as f64 / rw as f64).round() as u32),
+            Size::Height(h) => ((rw as f64 * h as f64 / rh as f64).round() as u32, h),
+            Size::Percent(n) => scale(n / 100.0),
+            Size::Exact(w, h) => (w, h),
+            Size::Confined(w, h) => scale((w as f64 / rw as f64).min(h as f64 / rh as f64)),
+        };
+
+        if w == 0 || h == 0 {
+            return Err(bad_request("size", "输出尺寸不能为 0"));
+        }
+        if !self.upscale && (w > rw || h > rh) {
+            return Err(bad_request("size", "放大输出需要使用 ^ 前缀"));
+        }
+        if w as u64 * h as u64 > MAX_AREA {
+            return Err(bad_request("size", format!("输出像素数不能超过 {}", MAX_AREA)));
+        }
+        Ok((w, h))
+    }
+
+    /// 把裁剪好的区域缩放到输出尺寸，再执行镜像、旋转和色彩转换，返回编码后的图像
+    pub fn render(&self, region: DynamicImage, (out_w, out_h): (u32, u32)) -> Result<Vec<u8>> {
+        let mut output = region;
+        if (output.width(), output.height()) != (out_w, out_h) {
+            output = output.resize_exact(out_w, out_h, FilterType::Triangle);
+        }
+        // 规范要求先镜像再旋转
+        if self.mirror {
+            output = output.fliph();
+        }
+        output = match self.rotation {
+            90 => output.rotate90(),
+            180 => output.rotate180(),
+            270 => output.rotate270(),
+            _ => output,
+        };
+
+        let output = match (self.quality, self.format) {
+            (Quality::Gray, _) => DynamicImage::ImageLuma8(output.to_luma8()),
+            // JPEG 不支持透明通道
+            (_, OutputFormat::Jpg) => DynamicImage::ImageRgb8(output.to_rgb8()),
+            _ => output,
+        };
+
+        let format = match self.format {
+            OutputFormat::Jpg => ImageFormat::Jpeg,
+            OutputFormat::Png => ImageFormat::Png,
+        };
+        let mut bytes = Cursor::new(Vec::new());
+        output
+            .write_to(&mut bytes, format)
+            .map_err(|e| anyhow::anyhow!("图像编码失败: {}", e))?;
+        Ok(bytes.into_inner())
+    }
+}
+
+/// 切片的缩放倍数，逐级翻倍直到整张图可放入一个切片
+pub fn scale_factors(width: u32, height: u32) -> Vec<u32> {
+    let mut factors = vec![1];
+    while width.max(height) / factors.last().unwrap() > TILE_SIZE {
+        factors.push(factors.last().unwrap() * 2);
+    }
+    factors
+}
+
+/// 生成 info.json；`id` 为图像服务的根地址
+pub fn info_json(id: &str, width: u32, height: u32) -> serde_json::Value {
+    let scale_factors = scale_factors(width, height);
+
+    json!({
+        "@context": "http://iiif.io/api/image/3/context.json",
+        "id": id,
+        "type": "ImageService3",
+        "protocol": "http://iiif.io/api/image",
+        "profile": "level2",
+        "width": width,
+        "height": height,
+        "maxArea": MAX_AREA,
+        "tiles": [{
+            "width": TILE_SIZE,
+            "height": TILE_SIZE,
+            "scaleFactors": scale_factors,
+        }],
+        "extraQualities": ["color", "gray"],
+        "extraFeatures": ["mirroring", "sizeUpscaling"],
+    })
+}
@@ -0,0 +1,121 @@
+// src-tauri/src/iiif/manifest.rs
+//! IIIF Presentation API 3.0 清单
+use crate::db::models::Artifact;
+use serde_json::{json, Value};
+
+// 清单中文本的语言标记
+const LANGUAGE: &str = "zh";
+
+// 缩略图尺寸
+const THUMBNAIL_SIZE: u32 = 400;
+
+/// 画布上的一张图片
+#[derive(Debug, Clone)]
+pub struct CanvasImage {
+    /// 图像服务根地址（IIIF Image API 的 id）
+    pub service_id: String,
+    pub width: u32,
+    pub height: u32,
+    pub label: Option<String>,
+}
+
+fn text(value: &str) -> Value {
+    json!({ LANGUAGE: [value] })
+}
+
+fn metadata_pairs(artifact: &Artifact, category_label: &str) -> Vec<Value> {
+    let period = if artifact.period.is_empty() {
+        artifact.dynasty.clone()
+    } else {
+        format!("{}（{}）", artifact.dynasty, artifact.period)
+    };
+
+    [
+        ("年代", period.as_str()),
+        ("类别", category_label),
+        ("材质", artifact.material.as_str()),
+        ("尺寸", artifact.dimensions.as_str()),
+        ("出土地点", artifact.discovery_location.as_str()),
+        ("收藏", artifact.collection.as_str()),
+        ("所在地", artifact.location.as_str()),
+    ]
+    .into_iter()
+    .filter(|(_, value)| !value.trim().is_empty())
+    .map(|(label, value)| json!({ "label": text(label), "value": text(value) }))
+    .collect()
+}
+
+fn image_url(service_id: &str, size: &str) -> String {
+    format!("{}/full/{}/0/default.jpg", service_id, size)
+}
+
+fn canvas(manifest_id: &str, index: usize, image: &CanvasImage) -> Value {
+    let canvas_id = format!("{}/canvas/{}", manifest_id, index);
+    let mut canvas = json!({
+        "id": canvas_id,
+        "type": "Canvas",
+        "width": image.width,
+        "height": image.height,
+        "items": [{
+            "id": format!("{}/page/1", canvas_id),
+            "type": "AnnotationPage",
+            "items": [{
+                "id": format!("{}/annotation/1", canvas_id),
+                "type": "Annotation",
+                "motivation": "painting",
+                "target": canvas_id,
+                "body": {
+                    "id": image_url(&image.service_id, "max"),
+                    "type": "Image",
+                    "format": "image/jpeg",
+                    "width": image.width,
+                    "height": image.height,
+                    "service": [{
+                        "id": image.service_id,
+                        "type": "ImageService3",
+                        "profile": "level2",
+                    }],
+                },
+            }],
+        }],
+    });
+    if let Some(label) = &image.label {
+        canvas["label"] = text(label);
+    }
+    canvas
+}
+
+/// 生成文物的清单，每张图片一个画布；类别显示为词表中的中文名称
+pub fn build_manifest(manifest_id: &str, artifact: &Artifact, category_label: &str, images: &[CanvasImage]) -> Value {
+    let mut manifest = json!({
+        "@context": "http://iiif.io/api/presentation/3/context.json",
+        "id": manifest_id,
+        "type": "Manifest",
+        "label": text(&artifact.title),
+        "metadata": metadata_pairs(artifact, category_label),
+        "items": images
+            .iter()
+            .enumerate()
+            .map(|(index, image)| canvas(manifest_id, index + 1, image))
+            .collect::<Vec<_>>(),
+    });
+
+    if !artifact.description.is_empty() {
+        manifest["summary"] = text(&artifact.description);
+    }
+    if !artifact.collection.is_empty() {
+        manifest["requiredStatement"] = json!({
+            "label": text("收藏机构"),
+            "value": text(&artifact.collection),
+        });
+    }
+    if let Some(first) = images.first() {
+        manifest["thumbnail"] = json!([{
+            "id": image_url(&first.service_id, &format!("!{},{}", THUMBNAIL_SIZE, THUMBNAIL_SIZE)),
+            "type": "Image",
+            "format": "image/jpeg",
+        }]);
+    }
+
+    manifest
+}
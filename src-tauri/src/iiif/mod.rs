@@ -0,0 +1,240 @@
+// src-tauri/src/iiif/mod.rs
+//! 本地 IIIF 服务：通过自定义协议提供 Image API 切片和 Presentation 清单
+//!
+//! - `{base}/manifest/{artifact_id}`：文物清单
+//! - `{base}/image/{identifier}/info.json`：图像信息，identifier 为 artifact_media 的 id
+//! - `{base}/image/{identifier}/{region}/{size}/{rotation}/{quality}.{format}`：图像
+//!
+//! 图像请求从切片金字塔（见 `pyramid`）中读取，不解码整幅原图。
+pub mod image_api;
+pub mod manifest;
+pub mod pyramid;
+
+use crate::api::media::{list_media, media_file_path};
+use crate::api::rag::fetch_artifact;
+use crate::db::models::MediaType;
+use crate::db::DbState;
+use crate::error::{Error, Result};
+use crate::storage::MediaStore;
+use crate::vocabulary::{Vocabularies, Vocabulary};
+use image_api::{info_json, ImageRequest};
+use manifest::{build_manifest, CanvasImage};
+use pyramid::{oriented_dimensions, Pyramid};
+use sqlx::{Row, SqlitePool};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Manager};
+
+pub const IIIF_SCHEME: &str = "iiif";
+
+/// 自定义协议的根地址；Windows 和 Android 的 WebView 使用 http://<scheme>.localhost
+pub fn base_url() -> &'static str {
+    if cfg!(any(windows, target_os = "android")) {
+        "http://iiif.localhost"
+    } else {
+        "iiif://localhost"
+    }
+}
+
+/// 图像服务根地址，identifier 为媒体 id
+pub fn image_service_id(identifier: i64) -> String {
+    format!("{}/image/{}", base_url(), identifier)
+}
+
+pub fn manifest_id(artifact_id: i64) -> String {
+    format!("{}/manifest/{}", base_url(), artifact_id)
+}
+
+/// 切片生成锁：同时只生成一张图片的切片，避免并发解码多幅长卷
+#[derive(Debug, Default)]
+pub struct IiifState {
+    generating: Arc<Mutex<()>>,
+}
+
+impl IiifState {
+    /// 读取图片的切片，尚未生成时先生成
+    async fn pyramid(&self, dir: PathBuf, source: PathBuf) -> Result<Pyramid> {
+        if let Some(pyramid) = Pyramid::open(&dir, &source) {
+            return Ok(pyramid);
+        }
+        let generating = self.generating.clone();
+        tokio::task::spawn_blocking(move || {
+            let _guard = generating.lock().unwrap();
+            // 等待期间可能已由其他请求生成
+            Pyramid::ensure(&dir, &source)
+        })
+        .await
+        .map_err(anyhow::Error::from)?
+    }
+}
+
+// 图像标识对应的图片名称及文件
+async fn resolve_image(pool: &SqlitePool, store: &MediaStore, identifier: i64) -> Result<(String, PathBuf)> {
+    let row = sqlx::query("SELECT path FROM artifact_media WHERE id = ? AND media_type = 'image'")
+        .bind(identifier)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("图像不存在: {}", identifier)))?;
+    let name: String = row.get("path");
+    let path = media_file_path(store, MediaType::Image, &name);
+    if !path.exists() {
+        return Err(Error::NotFound(format!("图片文件不存在: {}", path.display())));
+    }
+    Ok((name, path))
+}
+
+
+/// 生成文物的 IIIF 清单，每张图片一个画布，缺失的图片文件会被跳过
+pub async fn artifact_manifest(
+    pool: &SqlitePool,
+    store: &MediaStore,
+    artifact_id: i64,
+) -> Result<serde_json::Value> {
+    let artifact = fetch_artifact(pool, artifact_id).await?;
+
+    let mut images = Vec::new();
+    for media in list_media(pool, artifact.id).await? {
+        if media.media_type != MediaType::Image {
+            continue;
+        }
+        let path = media_file_path(store, MediaType::Image, &media.path);
+        let Ok((width, height)) = oriented_dimensions(&path) else {
+            println!("警告: IIIF 清单跳过无法读取的图片: {}", path.display());
+            continue;
+        };
+        images.push(CanvasImage {
+            service_id: image_service_id(media.id),
+            width,
+            height,
+            label: (!media.caption.is_empty()).then_some(media.caption),
+        });
+    }
+    if images.is_empty() {
+        return Err(Error::NotFound(format!("文物 {} 没有可用的图片", artifact.id)));
+    }
+    let vocabularies = Vocabularies::load(pool).await?;
+    let category_label = vocabularies.label(Vocabulary::Category, &artifact.category);
+    Ok(build_manifest(&manifest_id(artifact.id), &artifact, category_label, &images))
+}
+
+enum Reply {
+    Json(serde_json::Value),
+    Image(Vec<u8>, &'static str),
+    Redirect(String),
+}
+
+// 解码路径中的 %XX，viewer 可能会转义 ^ 和 !
+pub(crate) fn percent_decode(segment: &str) -> String {
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let bytes = segment.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let (Some(high), Some(low)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                output.push(high * 16 + low);
+                i += 3;
+                continue;
+            }
+        }
+        output.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+fn parse_identifier(value: &str) -> Result<i64> {
+    value
+        .parse()
+        .map_err(|_| Error::NotFound(format!("图像不存在: {}", value)))
+}
+
+async fn route(app: &AppHandle, path: &str) -> Result<Reply> {
+    let segments: Vec<String> = path
+        .trim_matches('/')
+        .split('/')
+        .map(percent_decode)
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    let pool = app.state::<DbState>().pool()?;
+    let state = app.state::<IiifState>();
+    let store = app.state::<MediaStore>();
+
+    match segments.as_slice() {
+        ["manifest", artifact_id] => {
+            let artifact_id = parse_identifier(artifact_id)?;
+            Ok(Reply::Json(artifact_manifest(&pool, &store, artifact_id).await?))
+        }
+        ["image", identifier] => Ok(Reply::Redirect(format!(
+            "{}/info.json",
+            image_service_id(parse_identifier(identifier)?)
+        ))),
+        ["image", identifier, "info.json"] => {
+            let identifier = parse_identifier(identifier)?;
+            let (_, path) = resolve_image(&pool, &store, identifier).await?;
+            let (width, height) = oriented_dimensions(&path)?;
+            Ok(Reply::Json(info_json(&image_service_id(identifier), width, height)))
+        }
+        ["image", identifier, region, size, rotation, quality_format] => {
+            let request = ImageRequest::parse(region, size, rotation, quality_format)?;
+            let (name, path) = resolve_image(&pool, &store, parse_identifier(identifier)?).await?;
+            let pyramid = state.pyramid(store.tiles_dir(&name), path).await?;
+            let (region, size) = request.resolve(pyramid.width, pyramid.height)?;
+            let bytes = tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+                // viewer 请求的标准切片直接返回文件
+                if request.is_unmodified() && request.format == pyramid.tile_format() {
+                    if let Some(tile) = pyramid.tile_file(region, size) {
+                        return Ok(std::fs::read(tile)?);
+                    }
+                }
+                request.render(pyramid.read_region(region, size)?, size)
+            })
+            .await
+            .map_err(anyhow::Error::from)??;
+            Ok(Reply::Image(bytes, request.format.mime_type()))
+        }
+        _ => Err(Error::NotFound(format!("未知的 IIIF 路径: {}", path))),
+    }
+}
+
+fn error_status(error: &Error) -> StatusCode {
+    match error.code() {
+        "VALIDATION" => StatusCode::BAD_REQUEST,
+        "NOT_FOUND" => StatusCode::NOT_FOUND,
+        "DATABASE_NOT_READY" => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// 处理自定义协议请求
+pub async fn handle_request(app: &AppHandle, request: Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let builder = Response::builder().header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+
+    let response = match route(app, request.uri().path()).await {
+        Ok(Reply::Json(value)) => builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/ld+json")
+            .body(serde_json::to_vec(&value).unwrap_or_default()),
+        Ok(Reply::Image(bytes, mime_type)) => builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, mime_type)
+            .header(header::CACHE_CONTROL, "max-age=3600")
+            .body(bytes),
+        Ok(Reply::Redirect(location)) => builder
+            .status(StatusCode::SEE_OTHER)
+            .header(header::LOCATION, location)
+            .body(Vec::new()),
+        Err(e) => builder
+            .status(error_status(&e))
+            .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+            .body(e.to_string().into_bytes()),
+    };
+
+    response.unwrap_or_else(|e| {
+        let mut fallback = Response::new(e.to_string().into_bytes());
+        *fallback.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        fallback
+    })
+}
@@ -0,0 +1,291 @@
+// src-tauri/src/iiif/pyramid.rs
+//! 切片金字塔：原图逐级缩小一半，每级按 `TILE_SIZE` 切成小图保存在存储目录下
+//!
+//! 只在上传、扫描或首次访问时解码一次原图；之后的 IIIF 请求只读取覆盖所需区域的切片，
+//! 内存占用与输出尺寸相当，与原图大小无关。
+use super::image_api::{scale_factors, OutputFormat, Rect, TILE_SIZE};
+use crate::error::Result;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{ColorType, DynamicImage, GenericImage, ImageDecoder, ImageFormat, ImageReader};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+// 记录原图尺寸及来源的文件，最后写入，存在即表示切片已完整生成
+const INFO_FILE: &str = "pyramid.json";
+
+// 切片格式的版本，旧版本生成的切片会重新生成（版本 1 未按 Exif 方向旋转）
+const PYRAMID_VERSION: u32 = 2;
+
+const TILE_QUALITY: u8 = 90;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PyramidInfo {
+    #[serde(default)]
+    version: u32,
+    width: u32,
+    height: u32,
+    /// 有透明通道的图片用 PNG 切片，其余用 JPEG
+    alpha: bool,
+    source_size: u64,
+    source_modified: u64,
+}
+
+/// 一张图片的切片金字塔
+#[derive(Debug, Clone)]
+pub struct Pyramid {
+    dir: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    alpha: bool,
+    scales: Vec<u32>,
+}
+
+fn open_decoder(source: &Path) -> Result<impl ImageDecoder> {
+    ImageReader::open(source)?
+        .with_guessed_format()?
+        .into_decoder()
+        .map_err(|e| anyhow::anyhow!("读取图片 {} 失败: {}", source.display(), e).into())
+}
+
+/// 按 Exif 方向旋转后的图片尺寸，只读取文件头
+pub fn oriented_dimensions(source: &Path) -> Result<(u32, u32)> {
+    let mut decoder = open_decoder(source)?;
+    let (width, height) = decoder.dimensions();
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    Ok(match orientation {
+        Orientation::Rotate90
+        | Orientation::Rotate270
+        | Orientation::Rotate90FlipH
+        | Orientation::Rotate270FlipH => (height, width),
+        _ => (width, height),
+    })
+}
+
+// 解码整幅原图并按 Exif 方向旋转，与上传时生成的缩略图方向一致
+fn open_oriented(source: &Path) -> Result<DynamicImage> {
+    let mut decoder = open_decoder(source)?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut image = DynamicImage::from_decoder(decoder)
+        .map_err(|e| anyhow::anyhow!("读取图片 {} 失败: {}", source.display(), e))?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+// 原图的大小和修改时间，用于判断切片是否过期
+fn source_stamp(source: &Path) -> Result<(u64, u64)> {
+    let metadata = std::fs::metadata(source)?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    Ok((metadata.len(), modified))
+}
+
+fn write_tile(image: &DynamicImage, alpha: bool, path: &Path) -> Result<()> {
+    let mut bytes = Vec::new();
+    if alpha {
+        image
+            .write_to(&mut std::io::Cursor::new(&mut bytes), ImageFormat::Png)
+            .map_err(|e| anyhow::anyhow!("生成切片失败: {}", e))?;
+    } else {
+        JpegEncoder::new_with_quality(&mut bytes, TILE_QUALITY)
+            .encode_image(&DynamicImage::ImageRgb8(image.to_rgb8()))
+            .map_err(|e| anyhow::anyhow!("生成切片失败: {}", e))?;
+    }
+    std::fs::write(path, bytes)?;
+    Ok(())
+}
+
+impl Pyramid {
+    fn from_info(dir: &Path, info: PyramidInfo) -> Self {
+        Pyramid {
+            dir: dir.to_path_buf(),
+            width: info.width,
+            height: info.height,
+            alpha: info.alpha,
+            scales: scale_factors(info.width, info.height),
+        }
+    }
+
+    /// 读取已生成的切片；尚未生成或原图已变化时返回 None
+    pub fn open(dir: &Path, source: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(dir.join(INFO_FILE)).ok()?;
+        let info: PyramidInfo = serde_json::from_str(&content).ok()?;
+        let (size, modified) = source_stamp(source).ok()?;
+        let current = info.version == PYRAMID_VERSION && info.source_size == size && info.source_modified == modified;
+        current.then(|| Self::from_info(dir, info))
+    }
+
+    /// 读取切片，必要时先生成（阻塞操作，生成时会解码整幅原图）
+    pub fn ensure(dir: &Path, source: &Path) -> Result<Self> {
+        match Self::open(dir, source) {
+            Some(pyramid) => Ok(pyramid),
+            None => Self::generate(dir, source),
+        }
+    }
+
+    /// 解码原图并重新生成全部切片
+    pub fn generate(dir: &Path, source: &Path) -> Result<Self> {
+        let (source_size, source_modified) = source_stamp(source)?;
+        let mut level = open_oriented(source)?;
+        let info = PyramidInfo {
+            version: PYRAMID_VERSION,
+            width: level.width(),
+            height: level.height(),
+            alpha: level.color().has_alpha(),
+            source_size,
+            source_modified,
+        };
+
+        if dir.exists() {
+            std::fs::remove_dir_all(dir)?;
+        }
+        let pyramid = Self::from_info(dir, info.clone());
+        for (index, &scale) in pyramid.scales.iter().enumerate() {
+            // 每级是上一级的一半
+            if index > 0 {
+                level = level.resize_exact(
+                    level.width().div_ceil(2),
+                    level.height().div_ceil(2),
+                    FilterType::Triangle,
+                );
+            }
+            std::fs::create_dir_all(dir.join(scale.to_string()))?;
+            for row in 0..level.height().div_ceil(TILE_SIZE) {
+                for col in 0..level.width().div_ceil(TILE_SIZE) {
+                    let (x, y) = (col * TILE_SIZE, row * TILE_SIZE);
+                    let tile = level.crop_imm(
+                        x,
+                        y,
+                        TILE_SIZE.min(level.width() - x),
+                        TILE_SIZE.min(level.height() - y),
+                    );
+                    write_tile(&tile, info.alpha, &pyramid.tile_path(scale, col, row))?;
+                }
+            }
+        }
+
+        let content = serde_json::to_vec(&info).map_err(anyhow::Error::from)?;
+        std::fs::write(dir.join(INFO_FILE), content)?;
+        Ok(pyramid)
+    }
+
+    fn tile_path(&self, scale: u32, col: u32, row: u32) -> PathBuf {
+        let ext = if self.alpha { "png" } else { "jpg" };
+        self.dir.join(scale.to_string()).join(format!("{}_{}.{}", col, row, ext))
+    }
+
+    pub fn tile_format(&self) -> OutputFormat {
+        if self.alpha {
+            OutputFormat::Png
+        } else {
+            OutputFormat::Jpg
+        }
+    }
+
+    // 不超过缩小倍数的最大一级，保证读取的切片分辨率不低于输出
+    fn level_for(&self, w: u32, h: u32, out_w: u32, out_h: u32) -> u32 {
+        let downscale = (w as f64 / out_w as f64).min(h as f64 / out_h as f64);
+        self.scales
+            .iter()
+            .rev()
+            .copied()
+            .find(|&scale| scale as f64 <= downscale)
+            .unwrap_or(1)
+    }
+
+    /// 请求的区域和尺寸恰好是一个切片时，返回该切片文件
+    pub fn tile_file(&self, (x, y, w, h): Rect, (out_w, out_h): (u32, u32)) -> Option<PathBuf> {
+        let scale = self.level_for(w, h, out_w, out_h);
+        let span = TILE_SIZE * scale;
+        let aligned = x % span == 0
+            && y % span == 0
+            && w == span.min(self.width - x)
+            && h == span.min(self.height - y)
+            && (out_w, out_h) == (w.div_ceil(scale), h.div_ceil(scale));
+        aligned.then(|| self.tile_path(scale, x / span, y / span))
+    }
+
+    /// 从合适的一级拼接出覆盖区域的图像，尺寸不小于输出尺寸（阻塞操作）
+    pub fn read_region(&self, (x, y, w, h): Rect, (out_w, out_h): (u32, u32)) -> Result<DynamicImage> {
+        let scale = self.level_for(w, h, out_w, out_h);
+        let (level_w, level_h) = (self.width.div_ceil(scale), self.height.div_ceil(scale));
+        let (left, top) = (x / scale, y / scale);
+        let right = (x + w).div_ceil(scale).min(level_w);
+        let bottom = (y + h).div_ceil(scale).min(level_h);
+
+        let color = if self.alpha { ColorType::Rgba8 } else { ColorType::Rgb8 };
+        let mut canvas = DynamicImage::new(right - left, bottom - top, color);
+        for row in top / TILE_SIZE..bottom.div_ceil(TILE_SIZE) {
+            for col in left / TILE_SIZE..right.div_ceil(TILE_SIZE) {
+                let path = self.tile_path(scale, col, row);
+                let tile = image::open(&path)
+                    .map_err(|e| anyhow::anyhow!("读取切片 {} 失败: {}", path.display(), e))?;
+                // 切片与区域的交集
+                let (tile_x, tile_y) = (col * TILE_SIZE, row * TILE_SIZE);
+                let (from_x, from_y) = (left.max(tile_x), top.max(tile_y));
+                let to_x = right.min(tile_x + tile.width());
+                let to_y = bottom.min(tile_y + tile.height());
+                if to_x <= from_x || to_y <= from_y {
+                    continue;
+                }
+                let part = tile.crop_imm(from_x - tile_x, from_y - tile_y, to_x - from_x, to_y - from_y);
+                canvas
+                    .copy_from(&part, from_x - left, from_y - top)
+                    .map_err(|e| anyhow::anyhow!("拼接切片失败: {}", e))?;
+            }
+        }
+        Ok(canvas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    // 40×20 的 JPEG，Exif 方向为 6（需顺时针旋转 90°）
+    fn rotated_jpeg() -> Vec<u8> {
+        let image = RgbImage::from_fn(40, 20, |x, _| if x < 20 { Rgb([255, 0, 0]) } else { Rgb([0, 0, 255]) });
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, 95)
+            .encode_image(&DynamicImage::ImageRgb8(image))
+            .unwrap();
+
+        // TIFF：IFD0 只有 Orientation 一个条目
+        let mut tiff = b"II\x2a\0\x08\0\0\0\x01\0".to_vec();
+        tiff.extend([0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00]);
+        tiff.extend([0; 4]);
+        let mut segment = vec![0xFF, 0xE1];
+        segment.extend(((2 + 6 + tiff.len()) as u16).to_be_bytes());
+        segment.extend(b"Exif\0\0");
+        segment.extend(tiff);
+        [&jpeg[..2], &segment, &jpeg[2..]].concat()
+    }
+
+    #[test]
+    fn applies_exif_orientation() {
+        let dir = std::env::temp_dir().join(format!("pyramid-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("rotated.jpg");
+        std::fs::write(&source, rotated_jpeg()).unwrap();
+
+        assert_eq!(oriented_dimensions(&source).unwrap(), (20, 40));
+
+        let tiles = dir.join("tiles");
+        let pyramid = Pyramid::ensure(&tiles, &source).unwrap();
+        assert_eq!((pyramid.width, pyramid.height), (20, 40));
+        // 旋转后左半部分（红色）在上方
+        let region = pyramid.read_region((0, 0, 20, 40), (20, 40)).unwrap().to_rgb8();
+        assert!(region.get_pixel(10, 5)[0] > 200);
+        assert!(region.get_pixel(10, 35)[2] > 200);
+        assert!(Pyramid::open(&tiles, &source).is_some());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
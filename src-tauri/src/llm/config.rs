@@ -0,0 +1,253 @@
+// src-tauri/src/llm/config.rs
+use super::openai::OpenAiCompatibleProvider;
+use super::simulator::SimulatorProvider;
+use super::LlmProvider;
+use crate::error::Error;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+// 应用配置目录下的模型配置文件
+pub const CONFIG_FILE_NAME: &str = "llm.json";
+
+// 未在配置文件中填写密钥时，DeepSeek 从该环境变量读取
+pub const DEEPSEEK_API_KEY_ENV: &str = "DEEPSEEK_API_KEY";
+
+// 示例占位密钥，视为未配置
+const PLACEHOLDER_API_KEY: &str = "sk-your-deepseek-api-key";
+
+const DEEPSEEK_BASE_URL: &str = "https://api.deepseek.com";
+const DEEPSEEK_MODEL: &str = "deepseek-chat";
+
+// Ollama 默认地址；llama.cpp server 一般为 http://localhost:8080/v1
+const OPENAI_COMPATIBLE_BASE_URL: &str = "http://localhost:11434/v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProviderKind {
+    #[serde(rename = "simulator")]
+    Simulator,
+    #[serde(rename = "deepseek")]
+    DeepSeek,
+    /// 任意兼容 OpenAI 接口的服务
+    #[serde(rename = "openai_compatible")]
+    OpenAiCompatible,
+}
+
+/// 大模型配置，保存在应用配置目录下的 llm.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LlmConfig {
+    pub provider: ProviderKind,
+    /// 未设置时使用各服务的默认地址
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    /// 未设置时 DeepSeek 使用 deepseek-chat，其他兼容服务必须填写
+    pub model: Option<String>,
+    pub temperature: f32,
+    pub max_tokens: i32,
+    /// 模型上下文窗口（token），用于裁剪对话历史
+    pub context_window: i32,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        LlmConfig {
+            provider: ProviderKind::Simulator,
+            base_url: None,
+            api_key: None,
+            model: None,
+            temperature: 0.7,
+            max_tokens: 2000,
+            context_window: 8192,
+        }
+    }
+}
+
+/// 返回给前端的配置，不包含密钥本身
+#[derive(Debug, Clone, Serialize)]
+pub struct LlmConfigView {
+    pub provider: ProviderKind,
+    pub base_url: Option<String>,
+    pub model: Option<String>,
+    pub temperature: f32,
+    pub max_tokens: i32,
+    pub context_window: i32,
+    pub has_api_key: bool,
+}
+
+/// 前端提交的配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct LlmConfigUpdate {
+    pub provider: ProviderKind,
+    pub base_url: Option<String>,
+    /// None 表示保留原有密钥，空字符串表示清除
+    pub api_key: Option<String>,
+    pub model: Option<String>,
+    pub temperature: f32,
+    pub max_tokens: i32,
+    pub context_window: i32,
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+impl LlmConfig {
+    /// 读取配置文件；没有配置文件但设置了 DeepSeek 密钥时使用 DeepSeek，否则使用模拟模式
+    pub fn load(app_handle: &AppHandle) -> Result<Self> {
+        let file = match app_handle.path().app_config_dir() {
+            Ok(dir) => dir.join(CONFIG_FILE_NAME),
+            Err(_) => return Ok(Self::from_env()),
+        };
+
+        Self::from_file(&file)
+    }
+
+    fn from_env() -> Self {
+        let mut config = LlmConfig::default();
+        if env_api_key().is_some() {
+            config.provider = ProviderKind::DeepSeek;
+        }
+        config
+    }
+
+    fn from_file(file: &Path) -> Result<Self> {
+        if !file.exists() {
+            return Ok(Self::from_env());
+        }
+
+        let content = std::fs::read_to_string(file)?;
+        let config = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("模型配置文件 {} 格式错误: {}", file.display(), e))?;
+        println!("已加载模型配置: {}", file.display());
+        Ok(config)
+    }
+
+    /// 写入应用配置目录
+    pub fn save(&self, app_handle: &AppHandle) -> Result<()> {
+        let dir = app_handle
+            .path()
+            .app_config_dir()
+            .map_err(|e| anyhow::anyhow!("无法获取应用配置目录: {}", e))?;
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join(CONFIG_FILE_NAME), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 合并前端提交的配置；服务地址变化且未同时提交新密钥时清除原有密钥，
+    /// 避免把密钥发送到新的地址
+    pub fn apply(&mut self, update: LlmConfigUpdate) {
+        let previous_base_url = self.resolve_base_url();
+        self.provider = update.provider;
+        self.base_url = non_empty(&update.base_url);
+        self.model = non_empty(&update.model);
+        self.temperature = update.temperature;
+        self.max_tokens = update.max_tokens;
+        self.context_window = update.context_window;
+        if let Some(api_key) = update.api_key {
+            self.api_key = non_empty(&Some(api_key));
+        } else if self.resolve_base_url() != previous_base_url {
+            self.api_key = None;
+        }
+    }
+
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if !(0.0..=2.0).contains(&self.temperature) {
+            return Err(Error::invalid_field("temperature", "temperature 应在 0 到 2 之间"));
+        }
+        if self.max_tokens <= 0 {
+            return Err(Error::invalid_field("max_tokens", "max_tokens 必须大于 0"));
+        }
+        if self.context_window <= self.max_tokens {
+            return Err(Error::invalid_field("context_window", "上下文窗口必须大于 max_tokens"));
+        }
+        if let Some(base_url) = &self.base_url {
+            if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+                return Err(Error::invalid_field("base_url", "服务地址必须以 http:// 或 https:// 开头"));
+            }
+        }
+        Ok(())
+    }
+
+    // 配置文件中的密钥优先；DeepSeek 使用默认地址时可退回环境变量，
+    // 自定义地址不会带上环境变量中的密钥
+    fn resolve_api_key(&self) -> Option<String> {
+        non_empty(&self.api_key).or_else(|| match self.provider {
+            ProviderKind::DeepSeek if self.resolve_base_url().as_deref() == Some(DEEPSEEK_BASE_URL) => {
+                env_api_key()
+            }
+            _ => None,
+        })
+    }
+
+    fn resolve_base_url(&self) -> Option<String> {
+        non_empty(&self.base_url).or_else(|| match self.provider {
+            ProviderKind::Simulator => None,
+            ProviderKind::DeepSeek => Some(DEEPSEEK_BASE_URL.to_string()),
+            ProviderKind::OpenAiCompatible => Some(OPENAI_COMPATIBLE_BASE_URL.to_string()),
+        })
+    }
+
+    fn resolve_model(&self) -> Option<String> {
+        non_empty(&self.model).or_else(|| match self.provider {
+            ProviderKind::DeepSeek => Some(DEEPSEEK_MODEL.to_string()),
+            _ => None,
+        })
+    }
+
+    pub fn view(&self) -> LlmConfigView {
+        LlmConfigView {
+            provider: self.provider,
+            base_url: self.resolve_base_url(),
+            model: self.resolve_model(),
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            context_window: self.context_window,
+            has_api_key: self.resolve_api_key().is_some(),
+        }
+    }
+
+    /// 扣除回复预留和固定提示后，可用于对话历史的 token 数
+    pub fn history_budget(&self, fixed_tokens: usize) -> usize {
+        let available = (self.context_window - self.max_tokens).max(0) as usize;
+        available.saturating_sub(fixed_tokens)
+    }
+
+    /// 按配置创建模型后端
+    pub fn build_provider(&self) -> crate::error::Result<Box<dyn LlmProvider>> {
+        let (name, api_key) = match self.provider {
+            ProviderKind::Simulator => return Ok(Box::new(SimulatorProvider)),
+            ProviderKind::DeepSeek => {
+                let api_key = self.resolve_api_key().ok_or_else(|| {
+                    Error::invalid_field("api_key", "未配置 DeepSeek API 密钥")
+                })?;
+                ("DeepSeek", Some(api_key))
+            }
+            ProviderKind::OpenAiCompatible => ("OpenAI-compatible server", self.resolve_api_key()),
+        };
+
+        let model = self
+            .resolve_model()
+            .ok_or_else(|| Error::invalid_field("model", "未配置模型名称"))?;
+
+        Ok(Box::new(OpenAiCompatibleProvider {
+            name: name.to_string(),
+            base_url: self.resolve_base_url().unwrap_or_default(),
+            api_key,
+            model,
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+        }))
+    }
+}
+
+fn env_api_key() -> Option<String> {
+    std::env::var(DEEPSEEK_API_KEY_ENV)
+        .ok()
+        .filter(|key| !key.is_empty() && key != PLACEHOLDER_API_KEY)
+}
@@ -0,0 +1,34 @@
+// src-tauri/src/llm/context.rs
+use super::ChatMessage;
+
+// 每条消息的角色、分隔符等固定开销
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// 粗略估算文本的 token 数：中文等非 ASCII 字符约 1 个 token，ASCII 约 4 个字符 1 个 token
+pub fn estimate_tokens(text: &str) -> usize {
+    let ascii = text.chars().filter(char::is_ascii).count();
+    let other = text.chars().count() - ascii;
+    other + ascii.div_ceil(4)
+}
+
+pub fn estimate_message_tokens(message: &ChatMessage) -> usize {
+    estimate_tokens(&message.content) + MESSAGE_OVERHEAD_TOKENS
+}
+
+/// 从最新的消息往前保留，直到超出预算；返回的消息仍按时间顺序排列
+pub fn fit_history(history: Vec<ChatMessage>, budget: usize) -> Vec<ChatMessage> {
+    let mut used = 0;
+    let mut keep = 0;
+
+    for message in history.iter().rev() {
+        let tokens = estimate_message_tokens(message);
+        if used + tokens > budget {
+            break;
+        }
+        used += tokens;
+        keep += 1;
+    }
+
+    let skip = history.len() - keep;
+    history.into_iter().skip(skip).collect()
+}
@@ -0,0 +1,47 @@
+// src-tauri/src/llm/mod.rs
+use crate::api::rag::RetrievedArtifact;
+use crate::error::Result;
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+pub mod config;
+pub mod context;
+pub mod openai;
+pub mod simulator;
+
+pub use config::{LlmConfig, LlmConfigUpdate, LlmConfigView};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+/// 一次补全请求
+#[derive(Debug)]
+pub struct CompletionRequest {
+    /// 完整消息列表（系统提示 + 对话历史 + 当前问题）
+    pub messages: Vec<ChatMessage>,
+    /// 当前用户问题，模拟模式按关键词回答
+    pub question: String,
+    /// 检索到的馆藏资料，模拟模式据此组织回答
+    pub artifacts: Vec<RetrievedArtifact>,
+}
+
+/// 增量文本回调
+pub type DeltaSink<'a> = &'a mut (dyn FnMut(String) + Send);
+
+/// 大模型后端
+///
+/// 取消由调用方负责：丢弃返回的 future 即停止生成。
+pub trait LlmProvider: Send + Sync {
+    /// 一次性返回完整回复
+    fn complete<'a>(&'a self, request: &'a CompletionRequest) -> BoxFuture<'a, Result<String>>;
+
+    /// 流式生成，每收到一段文本调用一次 on_delta
+    fn stream<'a>(
+        &'a self,
+        request: &'a CompletionRequest,
+        on_delta: DeltaSink<'a>,
+    ) -> BoxFuture<'a, Result<()>>;
+}
@@ -0,0 +1,226 @@
+// src-tauri/src/llm/openai.rs
+use super::{ChatMessage, CompletionRequest, DeltaSink, LlmProvider};
+use crate::error::{Error, Result};
+use futures_util::future::BoxFuture;
+use futures_util::{FutureExt, StreamExt};
+use serde::Serialize;
+
+/// OpenAI `/chat/completions` 接口的请求体
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: &'a [ChatMessage],
+    temperature: f32,
+    max_tokens: i32,
+    stream: bool,
+}
+
+/// 兼容 OpenAI 接口的服务：DeepSeek、本地 llama.cpp / Ollama 等
+#[derive(Debug, Clone)]
+pub struct OpenAiCompatibleProvider {
+    /// 服务名称，用于错误信息
+    pub name: String,
+    /// 形如 https://api.deepseek.com 或 http://localhost:11434/v1
+    pub base_url: String,
+    pub api_key: Option<String>,
+    pub model: String,
+    pub temperature: f32,
+    pub max_tokens: i32,
+}
+
+impl OpenAiCompatibleProvider {
+    fn endpoint(&self) -> String {
+        format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
+    }
+
+    // 发送请求，非 2xx 状态码视为上游错误
+    async fn send(&self, messages: &[ChatMessage], stream: bool) -> Result<reqwest::Response> {
+        let body = ChatCompletionRequest {
+            model: &self.model,
+            messages,
+            temperature: self.temperature,
+            max_tokens: self.max_tokens,
+            stream,
+        };
+
+        let client = reqwest::Client::new();
+        let mut request = client
+            .post(self.endpoint())
+            .header("Content-Type", "application/json")
+            .json(&body);
+        // 本地服务通常不需要密钥
+        if let Some(api_key) = &self.api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Upstream(format!("Failed to send request to {}: {}", self.name, e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(Error::Upstream(format!(
+                "{} API error: {} - {}",
+                self.name,
+                status,
+                error_text
+            )));
+        }
+
+        Ok(response)
+    }
+
+    async fn complete_inner(&self, request: &CompletionRequest) -> Result<String> {
+        let response = self.send(&request.messages, false).await?;
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| Error::Upstream(format!("Failed to parse {} response: {}", self.name, e)))?;
+
+        // 提取回复内容
+        let content = body
+            .get("choices")
+            .and_then(|choices| choices.get(0))
+            .and_then(|choice| choice.get("message"))
+            .and_then(|message| message.get("content"))
+            .and_then(|content| content.as_str())
+            .unwrap_or("抱歉，我现在无法回复您的消息。");
+
+        Ok(content.to_string())
+    }
+
+    async fn stream_inner(&self, request: &CompletionRequest, on_delta: DeltaSink<'_>) -> Result<()> {
+        let response = self.send(&request.messages, true).await?;
+
+        let mut stream = response.bytes_stream();
+        let mut parser = SseParser::default();
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk
+                .map_err(|e| Error::Upstream(format!("Failed to read {} stream: {}", self.name, e)))?;
+
+            for data in parser.push(&chunk) {
+                if data == "[DONE]" {
+                    return Ok(());
+                }
+                if let Some(delta) = self.extract_delta(&data)? {
+                    on_delta(delta);
+                }
+            }
+        }
+
+        // 流结束时最后一行可能没有换行符
+        if let Some(data) = parser.finish() {
+            if data != "[DONE]" {
+                if let Some(delta) = self.extract_delta(&data)? {
+                    on_delta(delta);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // 从流式数据块中提取增量文本
+    fn extract_delta(&self, data: &str) -> Result<Option<String>> {
+        let chunk: serde_json::Value = serde_json::from_str(data).map_err(|e| {
+            Error::Upstream(format!("Failed to parse {} stream chunk: {}", self.name, e))
+        })?;
+
+        Ok(chunk
+            .get("choices")
+            .and_then(|choices| choices.get(0))
+            .and_then(|choice| choice.get("delta"))
+            .and_then(|delta| delta.get("content"))
+            .and_then(|content| content.as_str())
+            .filter(|content| !content.is_empty())
+            .map(|content| content.to_string()))
+    }
+}
+
+impl LlmProvider for OpenAiCompatibleProvider {
+    fn complete<'a>(&'a self, request: &'a CompletionRequest) -> BoxFuture<'a, Result<String>> {
+        self.complete_inner(request).boxed()
+    }
+
+    fn stream<'a>(
+        &'a self,
+        request: &'a CompletionRequest,
+        on_delta: DeltaSink<'a>,
+    ) -> BoxFuture<'a, Result<()>> {
+        self.stream_inner(request, on_delta).boxed()
+    }
+}
+
+/// 按行解析 SSE 数据流，处理跨数据块的半行和被截断的多字节字符
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    /// 追加一个数据块，返回其中完整的 `data:` 字段内容
+    fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            events.extend(Self::parse_line(&line));
+        }
+        events
+    }
+
+    /// 数据流结束，处理缓冲区中剩余的不带换行符的最后一行
+    fn finish(&mut self) -> Option<String> {
+        let line = std::mem::take(&mut self.buffer);
+        Self::parse_line(&line)
+    }
+
+    fn parse_line(line: &[u8]) -> Option<String> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim_end_matches(['\r', '\n']);
+        line.strip_prefix("data:").map(|data| data.trim_start().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SseParser;
+
+    #[test]
+    fn joins_multibyte_characters_split_across_chunks() {
+        let payload = "data: 青铜鼎\n".as_bytes();
+        // 在“青”字的 UTF-8 编码中间切开
+        let (first, second) = payload.split_at(7);
+        let mut parser = SseParser::default();
+        assert!(parser.push(first).is_empty());
+        assert_eq!(parser.push(second), vec!["青铜鼎"]);
+    }
+
+    #[test]
+    fn handles_crlf_line_endings() {
+        let mut parser = SseParser::default();
+        let events = parser.push(b"data: {\"a\":1}\r\n\r\ndata: [DONE]\r\n");
+        assert_eq!(events, vec!["{\"a\":1}", "[DONE]"]);
+        assert_eq!(parser.finish(), None);
+    }
+
+    #[test]
+    fn yields_done_marker_and_skips_other_fields() {
+        let mut parser = SseParser::default();
+        let events = parser.push(b": keep-alive\nevent: message\ndata: [DONE]\n");
+        assert_eq!(events, vec!["[DONE]"]);
+    }
+
+    #[test]
+    fn flushes_trailing_line_without_newline() {
+        let mut parser = SseParser::default();
+        assert_eq!(parser.push(b"data: first\ndata: last"), vec!["first"]);
+        assert_eq!(parser.finish().as_deref(), Some("last"));
+        assert_eq!(parser.finish(), None);
+    }
+}
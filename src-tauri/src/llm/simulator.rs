@@ -0,0 +1,70 @@
+// src-tauri/src/llm/simulator.rs
+use super::{CompletionRequest, DeltaSink, LlmProvider};
+use crate::api::rag::simulate_grounded_answer;
+use crate::error::Result;
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use std::time::Duration;
+
+// 模拟模式下每次推送的字符数和间隔，让前端体验与真实流式一致
+const SIMULATED_CHUNK_CHARS: usize = 4;
+const SIMULATED_CHUNK_DELAY_MS: u64 = 30;
+
+/// 离线模拟回复，无需任何模型服务即可体验完整功能
+#[derive(Debug, Clone, Default)]
+pub struct SimulatorProvider;
+
+impl SimulatorProvider {
+    // 检索到馆藏资料时据此回答，否则使用固定回复
+    fn respond(&self, request: &CompletionRequest) -> String {
+        simulate_grounded_answer(&request.artifacts)
+            .unwrap_or_else(|| keyword_response(&request.question).to_string())
+    }
+
+    async fn stream_inner(&self, request: &CompletionRequest, on_delta: DeltaSink<'_>) -> Result<()> {
+        let chars: Vec<char> = self.respond(request).chars().collect();
+
+        for piece in chars.chunks(SIMULATED_CHUNK_CHARS) {
+            tokio::time::sleep(Duration::from_millis(SIMULATED_CHUNK_DELAY_MS)).await;
+            on_delta(piece.iter().collect());
+        }
+
+        Ok(())
+    }
+}
+
+impl LlmProvider for SimulatorProvider {
+    fn complete<'a>(&'a self, request: &'a CompletionRequest) -> BoxFuture<'a, Result<String>> {
+        let response = self.respond(request);
+        async move { Ok(response) }.boxed()
+    }
+
+    fn stream<'a>(
+        &'a self,
+        request: &'a CompletionRequest,
+        on_delta: DeltaSink<'a>,
+    ) -> BoxFuture<'a, Result<()>> {
+        self.stream_inner(request, on_delta).boxed()
+    }
+}
+
+// 按关键词匹配的固定回复
+fn keyword_response(message: &str) -> &'static str {
+    if message.contains("你好") || message.contains("hi") || message.contains("Hello") {
+        "您好！我是文博助手，很高兴为您服务！我可以帮您了解各种文物知识，比如青铜器、陶瓷、玉器、书画等。请问有什么可以帮助您的吗？"
+    } else if message.contains("青铜") {
+        "青铜器是中国古代文明的重要标志，始于夏商时期。著名的青铜器包括司母戊鼎、四羊方尊等。青铜器主要用于礼器、乐器和兵器，体现了古代中国高超的冶金技术和艺术水平。"
+    } else if message.contains("陶瓷") {
+        "中国陶瓷有着悠久的历史，从新石器时代的陶器到宋元明清的精美瓷器。著名的景德镇瓷器、唐三彩、龙泉青瓷等都是中国陶瓷艺术的瑰宝。每种陶瓷都有其独特的制作工艺和文化内涵。"
+    } else if message.contains("玉器") {
+        "玉在中国文化中象征着纯洁和美德。从新石器时代的红山文化、良渚文化玉器，到明清时期的精美玉雕，玉器一直是中华文化的重要组成部分。古人说'君子比德于玉'，体现了玉的文化意义。"
+    } else if message.contains("书画") {
+        "中国书画是独特的艺术形式，书法包括篆、隶、楷、行、草五种字体，绘画则有人物、山水、花鸟等题材。著名的书画家有王羲之、顾恺之、吴道子、张大千等，他们的作品代表了中国艺术的最高成就。"
+    } else if message.contains("博物馆") || message.contains("参观") {
+        "参观博物馆时，建议您：1)提前了解展览内容；2)安排充足时间；3)注意观察文物的细节；4)可以拍照但要遵守规定；5)多听讲解了解背后的故事。如果您想了解特定博物馆的信息，我可以为您详细介绍。"
+    } else if message.contains("帮助") || message.contains("help") {
+        "我可以帮助您：\n📚 介绍各类文物知识（青铜器、陶瓷、玉器、书画等）\n🏛️ 提供博物馆参观建议\n📖 讲解文物历史背景\n🎨 介绍艺术流派和代表作品\n🔍 回答文物相关问题\n\n请问您想了解哪个方面呢？"
+    } else {
+        "感谢您的提问！作为文博助手，我主要专注于文物知识、历史文化和博物馆相关内容。如果您有关于青铜器、陶瓷、玉器、书画等方面的问题，我很乐意为您详细解答。您可以试试问我'介绍一下青铜器'或'博物馆参观注意事项'等问题。"
+    }
+}
@@ -1,13 +1,24 @@
 // src-tauri/src/main.rs
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 
 mod db;
 mod api;
 mod error;
+mod llm;
+mod iiif;
+mod storage;
+mod geo;
+mod chronology;
+mod vocabulary;
+mod validation;
 
-use db::Database;
+use db::{Database, DbState, InitError, InitStage, DB_STATUS_EVENT};
 use error::Result;
+use api::ai::ChatState;
+use llm::LlmConfig;
 use api::auth::init_auth_state;
+use iiif::{IiifState, IIIF_SCHEME};
+use storage::{MediaStore, MEDIA_SCHEME};
 
 
 
@@ -15,42 +26,149 @@ use api::auth::init_auth_state;
 async fn main() -> Result<()> {
     
     tauri::Builder::default()
-        
+        // 本地 IIIF 图像服务，供深度缩放浏览长卷等大图
+        .register_asynchronous_uri_scheme_protocol(IIIF_SCHEME, |ctx, request, responder| {
+            let app_handle = ctx.app_handle().clone();
+            tauri::async_runtime::spawn(async move {
+                responder.respond(iiif::handle_request(&app_handle, request).await);
+            });
+        })
+        // 文物图片（含缩略图），上传的图片保存在应用数据目录
+        .register_asynchronous_uri_scheme_protocol(MEDIA_SCHEME, |ctx, request, responder| {
+            let app_handle = ctx.app_handle().clone();
+            tauri::async_runtime::spawn(async move {
+                responder.respond(storage::handle_request(&app_handle, request).await);
+            });
+        })
         .setup(|app| {
             // 初始化认证状态
             app.manage(init_auth_state());
             
+            // AI 助手：读取模型配置，配置文件有误时退回模拟模式
+            let llm_config = LlmConfig::load(app.handle()).unwrap_or_else(|e| {
+                eprintln!("读取模型配置失败，使用模拟模式: {}", e);
+                LlmConfig::default()
+            });
+            app.manage(ChatState::new(llm_config));
+            app.manage(IiifState::default());
+            app.manage(MediaStore::init(app.handle())?);
+            
+            // 数据库状态先注册为“初始化中”，命令在就绪前会返回明确的错误
+            app.manage(DbState::default());
+            
             // 获取应用句柄，它可以在整个应用生命周期内使用
             let app_handle = app.handle().clone();
             
             tauri::async_runtime::spawn(async move {
-                match Database::init(&app_handle).await {
+                let result = match Database::init(&app_handle).await {
+                    // 首次运行时确保存在管理员
+                    Ok(pool) => api::roles::bootstrap_admin(&pool)
+                        .await
+                        .map(|_| pool)
+                        .map_err(|e| InitError::new(InitStage::Bootstrap, e)),
+                    Err(e) => Err(e),
+                };
+
+                let db_state = app_handle.state::<DbState>();
+                let ready_pool = match result {
                     Ok(pool) => {
-                        // 使用 app_handle 来管理状态
-                        app_handle.manage(pool);
+                        db_state.set_ready(pool.clone());
                         println!("数据库初始化成功");
+                        Some(pool)
                     }
                     Err(e) => {
+                        db_state.set_failed(&e);
                         eprintln!("数据库初始化失败: {}", e);
+                        None
+                    }
+                };
+
+                // 通知前端数据库状态
+                if let Err(e) = app_handle.emit(DB_STATUS_EVENT, db_state.status()) {
+                    eprintln!("发送数据库状态事件失败: {}", e);
+                }
+
+                if let Some(pool) = ready_pool {
+                    // 为尚无坐标的文物按地名补全坐标
+                    match api::geo::backfill_coordinates(&pool).await {
+                        Ok(0) => {}
+                        Ok(count) => println!("已为 {} 件文物补全坐标", count),
+                        Err(e) => eprintln!("补全文物坐标失败: {}", e),
+                    }
+
+                    // 为尚无年份的文物按朝代和年代推断年份
+                    match api::chronology::backfill_dates(&pool).await {
+                        Ok(0) => {}
+                        Ok(count) => println!("已为 {} 件文物推断年份", count),
+                        Err(e) => eprintln!("推断文物年份失败: {}", e),
+                    }
+
+                    // 检查媒体文件是否存在，刷新缺失标记；完整扫描由 scan_media 命令执行
+                    let store = app_handle.state::<MediaStore>();
+                    match api::maintenance::refresh_missing_flags(&pool, &store).await {
+                        Ok(0) => {}
+                        Ok(count) => println!("已更新 {} 条媒体的文件缺失标记", count),
+                        Err(e) => eprintln!("刷新媒体文件缺失标记失败: {}", e),
                     }
                 }
             });
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
+            api::system::get_database_status,
             api::artifacts::get_artifacts,
             api::artifacts::get_artifact_by_id,
+            api::search::search_artifacts,
+            api::search::get_artifact_facets,
+            api::chronology::get_chronology,
+            api::chronology::get_timeline,
+            api::vocabulary::get_vocabulary,
+            api::vocabulary::create_vocabulary_term,
+            api::vocabulary::update_vocabulary_term,
+            api::vocabulary::delete_vocabulary_term,
+            api::vocabulary::merge_vocabulary_terms,
+            api::geo::get_map_artifacts,
+            api::geo::get_map_clusters,
+            api::geo::geocode_location,
             api::artifacts::toggle_favorite,
             api::artifacts::create_artifact,
             api::artifacts::update_artifact,
             api::artifacts::delete_artifact,
+            api::import::import_artifacts,
+            api::export::export_artifacts,
+            api::iiif::get_iiif_manifest,
+            api::media::upload_artifact_image,
+            api::media::add_artifact_media,
+            api::media::update_artifact_media,
+            api::media::reorder_artifact_media,
+            api::media::delete_artifact_media,
+            api::maintenance::scan_media,
             api::auth::login,
             api::auth::register,
             api::auth::get_current_user,
             api::auth::logout,
             api::auth::update_profile,
+            api::auth::list_sessions,
+            api::auth::revoke_session,
+            api::auth::revoke_other_sessions,
             api::auth::validate_password_strength,
+            api::roles::get_my_permissions,
+            api::roles::list_roles,
+            api::roles::list_users_with_roles,
+            api::roles::grant_role,
+            api::roles::revoke_role,
             api::ai::chat_with_ai,
+            api::ai::chat_with_ai_stream,
+            api::ai::cancel_chat,
+            api::ai::ask_about_artifact,
+            api::ai::get_llm_config,
+            api::ai::set_llm_config,
+            api::conversations::create_conversation,
+            api::conversations::list_conversations,
+            api::conversations::get_conversation,
+            api::conversations::rename_conversation,
+            api::conversations::delete_conversation,
+            api::conversations::continue_conversation,
             api::history::add_to_history,
             api::history::get_browsing_history,
             api::history::clear_browsing_history,
@@ -0,0 +1,371 @@
+// src-tauri/src/storage/metadata.rs
+//! 去除图片元数据中的 GPS 定位信息，其余字节保持不变
+//!
+//! - JPEG：从 Exif 中删除 GPS IFD，含 GPS 的 XMP 段整体删除
+//! - PNG：含 GPS 的 eXIf 和 XMP 块整体删除
+use image::ImageFormat;
+
+const GPS_IFD_TAG: u16 = 0x8825;
+
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const PNG_XMP_KEYWORD: &[u8] = b"XML:com.adobe.xmp\0";
+
+#[derive(Clone, Copy)]
+enum ByteOrder {
+    Little,
+    Big,
+}
+
+struct Tiff<'a> {
+    data: &'a mut [u8],
+    order: ByteOrder,
+}
+
+impl<'a> Tiff<'a> {
+    fn new(data: &'a mut [u8]) -> Option<Self> {
+        let order = match data.get(..4)? {
+            [b'I', b'I', 42, 0] => ByteOrder::Little,
+            [b'M', b'M', 0, 42] => ByteOrder::Big,
+            _ => return None,
+        };
+        Some(Tiff { data, order })
+    }
+
+    fn u16_at(&self, offset: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(offset..offset + 2)?.try_into().ok()?;
+        Some(match self.order {
+            ByteOrder::Little => u16::from_le_bytes(bytes),
+            ByteOrder::Big => u16::from_be_bytes(bytes),
+        })
+    }
+
+    fn u32_at(&self, offset: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(offset..offset + 4)?.try_into().ok()?;
+        Some(match self.order {
+            ByteOrder::Little => u32::from_le_bytes(bytes),
+            ByteOrder::Big => u32::from_be_bytes(bytes),
+        })
+    }
+
+    fn set_u16(&mut self, offset: usize, value: u16) {
+        let bytes = match self.order {
+            ByteOrder::Little => value.to_le_bytes(),
+            ByteOrder::Big => value.to_be_bytes(),
+        };
+        self.data[offset..offset + 2].copy_from_slice(&bytes);
+    }
+
+    fn zero(&mut self, start: usize, len: usize) -> Option<()> {
+        self.data.get_mut(start..start.checked_add(len)?)?.fill(0);
+        Some(())
+    }
+
+    // 删除 IFD0 中的 GPS 指针并清空 GPS IFD；返回是否存在 GPS 信息
+    fn remove_gps(&mut self) -> Option<bool> {
+        let ifd0 = self.u32_at(4)? as usize;
+        let count = self.u16_at(ifd0)? as usize;
+        // 条目之后还有 4 字节的下一个 IFD 偏移
+        let end = ifd0 + 2 + count * 12 + 4;
+        if end > self.data.len() {
+            return None;
+        }
+
+        let Some(index) = (0..count).find(|i| self.u16_at(ifd0 + 2 + i * 12) == Some(GPS_IFD_TAG))
+        else {
+            return Some(false);
+        };
+        let entry = ifd0 + 2 + index * 12;
+        let gps_ifd = self.u32_at(entry + 8)? as usize;
+
+        // 清空 GPS IFD 中各条目引用的数据及 IFD 本身
+        let gps_count = self.u16_at(gps_ifd)? as usize;
+        for i in 0..gps_count {
+            let gps_entry = gps_ifd + 2 + i * 12;
+            let size = type_size(self.u16_at(gps_entry + 2)?) * self.u32_at(gps_entry + 4)? as usize;
+            if size > 4 {
+                let value = self.u32_at(gps_entry + 8)? as usize;
+                self.zero(value, size)?;
+            }
+        }
+        self.zero(gps_ifd, 2 + gps_count * 12 + 4)?;
+
+        // 后续条目前移，IFD 位置不变，其他偏移无需修改
+        self.data.copy_within(entry + 12..end, entry);
+        self.zero(end - 12, 12)?;
+        self.set_u16(ifd0, (count - 1) as u16);
+        Some(true)
+    }
+}
+
+fn type_size(field_type: u16) -> usize {
+    match field_type {
+        3 | 8 => 2,
+        4 | 9 | 11 => 4,
+        5 | 10 | 12 => 8,
+        _ => 1,
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+fn strip_jpeg(bytes: &[u8]) -> Option<(Vec<u8>, bool)> {
+    if bytes.get(..2)? != [0xFF, 0xD8] {
+        return None;
+    }
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(&bytes[..2]);
+    let mut removed = false;
+    let mut pos = 2;
+
+    loop {
+        if *bytes.get(pos)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(pos + 1)?;
+        // 从 SOS 开始是图像数据，原样保留
+        if marker == 0xDA || marker == 0xD9 {
+            output.extend_from_slice(&bytes[pos..]);
+            return Some((output, removed));
+        }
+        let len = u16::from_be_bytes([*bytes.get(pos + 2)?, *bytes.get(pos + 3)?]) as usize;
+        if len < 2 {
+            return None;
+        }
+        let segment = bytes.get(pos..pos + 2 + len)?;
+        let payload = &segment[4..];
+
+        if marker == 0xE1 && payload.starts_with(EXIF_HEADER) {
+            let mut segment = segment.to_vec();
+            match Tiff::new(&mut segment[4 + EXIF_HEADER.len()..]).and_then(|mut t| t.remove_gps()) {
+                Some(found) => {
+                    removed |= found;
+                    output.extend_from_slice(&segment);
+                }
+                // 无法解析的 Exif 无法确认不含定位，整段删除
+                None => removed = true,
+            }
+        } else if marker == 0xE1 && payload.starts_with(XMP_HEADER) && contains(payload, b"GPS") {
+            removed = true;
+        } else {
+            output.extend_from_slice(segment);
+        }
+        pos += 2 + len;
+    }
+}
+
+fn strip_png(bytes: &[u8]) -> Option<(Vec<u8>, bool)> {
+    if !bytes.starts_with(PNG_SIGNATURE) {
+        return None;
+    }
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(PNG_SIGNATURE);
+    let mut removed = false;
+    let mut pos = PNG_SIGNATURE.len();
+
+    while pos < bytes.len() {
+        let len = u32::from_be_bytes(bytes.get(pos..pos + 4)?.try_into().ok()?) as usize;
+        let chunk_type = bytes.get(pos + 4..pos + 8)?;
+        // 长度、类型、数据、CRC
+        let chunk = bytes.get(pos..pos + 12 + len)?;
+        let data = &chunk[8..8 + len];
+
+        let has_gps = match chunk_type {
+            b"eXIf" => {
+                let mut data = data.to_vec();
+                Tiff::new(&mut data).and_then(|mut t| t.remove_gps()).unwrap_or(true)
+            }
+            b"iTXt" => data.starts_with(PNG_XMP_KEYWORD) && contains(data, b"GPS"),
+            _ => false,
+        };
+        if has_gps {
+            removed = true;
+        } else {
+            output.extend_from_slice(chunk);
+        }
+        pos += 12 + len;
+    }
+    Some((output, removed))
+}
+
+/// 去除 GPS 信息，返回处理后的字节及是否删除了定位信息；文件结构无法解析时返回 None
+pub fn strip_gps(format: ImageFormat, bytes: &[u8]) -> Option<(Vec<u8>, bool)> {
+    match format {
+        ImageFormat::Jpeg => strip_jpeg(bytes),
+        ImageFormat::Png => strip_png(bytes),
+        _ => Some((bytes.to_vec(), false)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // GPS 纬度的分子，用于检查数据是否已清空
+    const LATITUDE: u32 = 0x0A8C_0A8C;
+
+    /// 构造 TIFF：IFD0 含 Make 条目，`gps` 为 Some 时再加一个指向该偏移的 GPS 指针，
+    /// 指针有效时 GPS IFD 紧跟在 IFD0 之后，含一个纬度条目
+    fn tiff(big_endian: bool, gps: Option<Option<u32>>) -> Vec<u8> {
+        let u16b = |v: u16| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+        let u32b = |v: u32| if big_endian { v.to_be_bytes() } else { v.to_le_bytes() };
+        let mut t = if big_endian { b"MM\0\x2a".to_vec() } else { b"II\x2a\0".to_vec() };
+        t.extend(u32b(8));
+
+        let count = if gps.is_some() { 2 } else { 1 };
+        let gps_ifd = 8 + 2 + count * 12 + 4;
+        t.extend(u16b(count as u16));
+        t.extend(u16b(0x010F));
+        t.extend(u16b(2));
+        t.extend(u32b(4));
+        t.extend(b"abc\0");
+        if let Some(offset) = gps {
+            t.extend(u16b(GPS_IFD_TAG));
+            t.extend(u16b(4));
+            t.extend(u32b(1));
+            t.extend(u32b(offset.unwrap_or(gps_ifd as u32)));
+        }
+        t.extend(u32b(0));
+
+        if gps == Some(None) {
+            let value = gps_ifd + 2 + 12 + 4;
+            t.extend(u16b(1));
+            t.extend(u16b(2));
+            t.extend(u16b(5));
+            t.extend(u32b(3));
+            t.extend(u32b(value as u32));
+            t.extend(u32b(0));
+            for v in [LATITUDE, 1, 54, 1, 2700, 100] {
+                t.extend(u32b(v));
+            }
+        }
+        t
+    }
+
+    fn jpeg(segments: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xD8];
+        for (marker, payload) in segments {
+            bytes.extend([0xFF, *marker]);
+            bytes.extend(((payload.len() + 2) as u16).to_be_bytes());
+            bytes.extend(payload);
+        }
+        bytes.extend([0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]);
+        bytes
+    }
+
+    fn exif(tiff: Vec<u8>) -> (u8, Vec<u8>) {
+        (0xE1, [EXIF_HEADER.to_vec(), tiff].concat())
+    }
+
+    fn png(chunks: &[(&[u8], Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = PNG_SIGNATURE.to_vec();
+        for (chunk_type, data) in chunks {
+            bytes.extend((data.len() as u32).to_be_bytes());
+            bytes.extend(*chunk_type);
+            bytes.extend(data);
+            bytes.extend([0; 4]);
+        }
+        bytes
+    }
+
+    fn contains_u32(bytes: &[u8], value: u32) -> bool {
+        contains(bytes, &value.to_le_bytes()) || contains(bytes, &value.to_be_bytes())
+    }
+
+    fn assert_gps_removed(big_endian: bool) {
+        let input = jpeg(&[exif(tiff(big_endian, Some(None)))]);
+        assert!(contains_u32(&input, LATITUDE));
+
+        let (output, removed) = strip_gps(ImageFormat::Jpeg, &input).unwrap();
+        assert!(removed);
+        // 段长度不变，仅 GPS 数据被清空
+        assert_eq!(output.len(), input.len());
+        assert!(!contains_u32(&output, LATITUDE));
+        assert!(contains(&output, b"abc\0"));
+
+        // IFD0 只剩 Make 一个条目
+        let mut segment = output[4..].to_vec();
+        let tiff = Tiff::new(&mut segment[2 + EXIF_HEADER.len()..]).unwrap();
+        assert_eq!(tiff.u16_at(8), Some(1));
+        assert_eq!(tiff.u16_at(10), Some(0x010F));
+    }
+
+    #[test]
+    fn removes_gps_from_little_endian_exif() {
+        assert_gps_removed(false);
+    }
+
+    #[test]
+    fn removes_gps_from_big_endian_exif() {
+        assert_gps_removed(true);
+    }
+
+    #[test]
+    fn keeps_exif_without_gps_unchanged() {
+        for big_endian in [false, true] {
+            let input = jpeg(&[exif(tiff(big_endian, None))]);
+            assert_eq!(strip_gps(ImageFormat::Jpeg, &input), Some((input, false)));
+        }
+    }
+
+    #[test]
+    fn drops_exif_with_invalid_gps_offset() {
+        let expected = jpeg(&[]);
+        for offset in [u32::MAX, 0x7FFF_FFFF, 200] {
+            let input = jpeg(&[exif(tiff(false, Some(Some(offset))))]);
+            assert_eq!(strip_gps(ImageFormat::Jpeg, &input), Some((expected.clone(), true)));
+        }
+
+        // GPS IFD 被截断
+        let mut truncated = tiff(true, Some(None));
+        truncated.truncate(40);
+        let input = jpeg(&[exif(truncated)]);
+        assert_eq!(strip_gps(ImageFormat::Jpeg, &input), Some((expected, true)));
+    }
+
+    #[test]
+    fn drops_jpeg_xmp_with_gps() {
+        let xmp = |body: &[u8]| (0xE1, [XMP_HEADER, body].concat());
+        let input = jpeg(&[xmp(b"<exif:GPSLatitude>39,54.27N</exif:GPSLatitude>")]);
+        assert_eq!(strip_gps(ImageFormat::Jpeg, &input), Some((jpeg(&[]), true)));
+
+        let input = jpeg(&[xmp(b"<dc:title>bronze</dc:title>")]);
+        assert_eq!(strip_gps(ImageFormat::Jpeg, &input), Some((input, false)));
+    }
+
+    #[test]
+    fn drops_png_chunks_with_gps() {
+        let ihdr: (&[u8], Vec<u8>) = (b"IHDR", vec![0; 13]);
+        let iend: (&[u8], Vec<u8>) = (b"IEND", Vec::new());
+        let xmp = |body: &[u8]| [PNG_XMP_KEYWORD, b"\0\0\0\0", body].concat();
+
+        let input = png(&[
+            ihdr.clone(),
+            (b"eXIf", tiff(false, Some(None))),
+            (b"iTXt", xmp(b"<exif:GPSLongitude>116,23.1E</exif:GPSLongitude>")),
+            iend.clone(),
+        ]);
+        let expected = png(&[ihdr.clone(), iend.clone()]);
+        assert_eq!(strip_gps(ImageFormat::Png, &input), Some((expected, true)));
+
+        let input = png(&[
+            ihdr,
+            (b"eXIf", tiff(true, None)),
+            (b"iTXt", xmp(b"<dc:title>bronze</dc:title>")),
+            iend,
+        ]);
+        assert_eq!(strip_gps(ImageFormat::Png, &input), Some((input, false)));
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let input = jpeg(&[exif(tiff(false, Some(None)))]);
+        assert_eq!(strip_gps(ImageFormat::Jpeg, &input[..20]), None);
+
+        let input = png(&[(b"IHDR", vec![0; 13])]);
+        assert_eq!(strip_gps(ImageFormat::Png, &input[..input.len() - 3]), None);
+    }
+}
@@ -0,0 +1,188 @@
+// src-tauri/src/storage/mod.rs
+//! 文物图片存储
+//!
+//! 上传的图片按内容的 SHA-256 命名（`{hash}.{ext}`），保存在应用数据目录下，
+//! 同时生成缩略图和中等尺寸两个版本；早期随应用分发的图片仍在 `public/images` 中，
+//! 打包后位于资源目录的 `public/images`。
+//! 两者都通过 `media` 协议访问：`{base}/{rendition}/{image_path}`。
+pub mod metadata;
+pub mod scan;
+pub mod upload;
+
+use crate::error::{Error, Result};
+use std::path::{Path, PathBuf};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Manager};
+
+pub use upload::{store_upload, StoredImage};
+
+pub const MEDIA_SCHEME: &str = "media";
+
+// 随应用分发的文件在资源目录中的位置，对应 tauri.conf.json 中的 bundle.resources
+const BUNDLED_DIR: &str = "public";
+
+// 开发时资源尚未复制到资源目录（如直接 cargo run），退回源码中的 public 目录
+const SOURCE_BUNDLED_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../public");
+
+/// 图片版本
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rendition {
+    Original,
+    /// 长边不超过 1280 像素
+    Medium,
+    /// 长边不超过 320 像素
+    Thumb,
+}
+
+impl Rendition {
+    pub fn dir(self) -> &'static str {
+        match self {
+            Rendition::Original => "original",
+            Rendition::Medium => "medium",
+            Rendition::Thumb => "thumb",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "original" => Some(Rendition::Original),
+            "medium" => Some(Rendition::Medium),
+            "thumb" => Some(Rendition::Thumb),
+            _ => None,
+        }
+    }
+}
+
+/// 上传图片的存储目录及随应用分发的文件目录
+#[derive(Debug, Clone)]
+pub struct MediaStore {
+    root: PathBuf,
+    bundled: PathBuf,
+}
+
+impl MediaStore {
+    pub fn new(root: PathBuf, bundled: PathBuf) -> Self {
+        MediaStore { root, bundled }
+    }
+
+    /// 在应用数据目录下创建存储目录，并从资源目录定位随应用分发的文件
+    pub fn init(app: &AppHandle) -> Result<Self> {
+        let root = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| anyhow::anyhow!("获取应用数据目录失败: {}", e))?
+            .join("images");
+        for rendition in [Rendition::Original, Rendition::Medium, Rendition::Thumb] {
+            std::fs::create_dir_all(root.join(rendition.dir()))?;
+        }
+
+        let resource = app
+            .path()
+            .resolve(BUNDLED_DIR, BaseDirectory::Resource)
+            .map_err(|e| anyhow::anyhow!("获取资源目录失败: {}", e))?;
+        let bundled = if !resource.is_dir() && cfg!(debug_assertions) {
+            PathBuf::from(SOURCE_BUNDLED_DIR)
+        } else {
+            resource
+        };
+        println!("随应用分发的文件目录: {}", bundled.display());
+        Ok(MediaStore::new(root, bundled))
+    }
+
+    /// 随应用分发的文件所在目录，`dir` 为相对于 public 的子目录
+    pub fn bundled_dir(&self, dir: &str) -> PathBuf {
+        self.bundled.join(dir)
+    }
+
+    pub(crate) fn stored_path(&self, rendition: Rendition, name: &str) -> PathBuf {
+        self.root.join(rendition.dir()).join(name)
+    }
+
+    /// 图片的 IIIF 切片目录
+    pub fn tiles_dir(&self, image_path: &str) -> PathBuf {
+        self.root.join("tiles").join(image_path)
+    }
+
+    /// 原图的完整路径：上传的图片在存储目录中，其余在随应用分发的图片目录中
+    pub fn image_file(&self, image_path: &str) -> PathBuf {
+        if is_stored_name(image_path) {
+            self.stored_path(Rendition::Original, image_path)
+        } else {
+            self.bundled_dir("images").join(image_path)
+        }
+    }
+
+    /// 指定版本的图片路径；早期图片没有缩略图，直接使用原图
+    pub fn rendition_file(&self, image_path: &str, rendition: Rendition) -> PathBuf {
+        match (rendition, stored_hash(image_path)) {
+            (Rendition::Original, _) | (_, None) => self.image_file(image_path),
+            (_, Some(hash)) => self.stored_path(rendition, &format!("{}.jpg", hash)),
+        }
+    }
+}
+
+// 上传图片的文件名：64 位十六进制哈希加扩展名
+fn stored_hash(image_path: &str) -> Option<&str> {
+    let (hash, ext) = image_path.split_once('.')?;
+    let valid = hash.len() == 64
+        && hash.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+        && matches!(ext, "jpg" | "png");
+    valid.then_some(hash)
+}
+
+pub fn is_stored_name(image_path: &str) -> bool {
+    stored_hash(image_path).is_some()
+}
+
+fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase()).as_deref() {
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+async fn route(store: &MediaStore, path: &str) -> Result<(Vec<u8>, &'static str)> {
+    let path = crate::iiif::percent_decode(path.trim_start_matches('/'));
+    let (rendition, image_path) = path
+        .split_once('/')
+        .and_then(|(r, p)| Some((Rendition::parse(r)?, p)))
+        .ok_or_else(|| Error::NotFound(format!("未知的图片路径: {}", path)))?;
+    if image_path.is_empty() || image_path.contains(['/', '\\']) || image_path.contains("..") {
+        return Err(Error::NotFound(format!("未知的图片路径: {}", path)));
+    }
+
+    let file = store.rendition_file(image_path, rendition);
+    let bytes = tokio::fs::read(&file)
+        .await
+        .map_err(|_| Error::NotFound(format!("图片文件不存在: {}", image_path)))?;
+    Ok((bytes, content_type(&file)))
+}
+
+/// 处理自定义协议请求
+pub async fn handle_request(app: &AppHandle, request: Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let store = app.state::<MediaStore>();
+    let builder = Response::builder().header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+
+    let response = match route(&store, request.uri().path()).await {
+        Ok((bytes, mime_type)) => builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, mime_type)
+            // 上传的图片按内容命名，不会变化
+            .header(header::CACHE_CONTROL, "max-age=86400")
+            .body(bytes),
+        Err(e) => builder
+            .status(StatusCode::NOT_FOUND)
+            .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+            .body(e.to_string().into_bytes()),
+    };
+
+    response.unwrap_or_else(|e| {
+        let mut fallback = Response::new(e.to_string().into_bytes());
+        *fallback.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        fallback
+    })
+}
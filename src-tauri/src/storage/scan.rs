@@ -0,0 +1,166 @@
+// src-tauri/src/storage/scan.rs
+//! 媒体完整性扫描中的文件操作（均为阻塞操作）
+use super::{is_stored_name, stored_hash, MediaStore, Rendition};
+use crate::error::Result;
+use crate::iiif::pyramid::Pyramid;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// 超过该大小的图片视为过大
+pub const OVERSIZED_BYTES: u64 = 5 * 1024 * 1024;
+
+/// 长边超过该像素数的图片视为过大
+pub const OVERSIZED_DIMENSION: u32 = 6000;
+
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp"];
+
+/// 图片文件所在位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageLocation {
+    /// 随应用分发的图片目录
+    Bundled,
+    /// 上传图片的存储目录
+    Stored,
+}
+
+/// 磁盘上的一个图片文件
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageFile {
+    pub name: String,
+    pub location: ImageLocation,
+    pub size: u64,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// 文件内容的 SHA-256
+    pub hash: String,
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl ImageFile {
+    pub fn is_oversized(&self) -> bool {
+        self.size > OVERSIZED_BYTES
+            || self.width.max(self.height).is_some_and(|side| side > OVERSIZED_DIMENSION)
+    }
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn is_image_name(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+fn scan_dir(dir: &Path, location: ImageLocation, files: &mut Vec<ImageFile>) -> Result<()> {
+    // 目录不存在时视为没有文件
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let entry = entry?;
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if !entry.file_type()?.is_file() || !is_image_name(&name) {
+            continue;
+        }
+        // 存储目录中只认按内容命名的文件，其余视为残留的临时文件
+        if location == ImageLocation::Stored && !is_stored_name(&name) {
+            continue;
+        }
+        let path = entry.path();
+        let (width, height) = match image::image_dimensions(&path) {
+            Ok((w, h)) => (Some(w), Some(h)),
+            Err(_) => (None, None),
+        };
+        files.push(ImageFile {
+            size: entry.metadata()?.len(),
+            hash: hash_file(&path)?,
+            name,
+            location,
+            width,
+            height,
+            path,
+        });
+    }
+    Ok(())
+}
+
+/// 列出两个图片目录中的全部原图，按位置和文件名排序
+pub fn list_image_files(store: &MediaStore) -> Result<Vec<ImageFile>> {
+    let mut files = Vec::new();
+    scan_dir(&store.bundled_dir("images"), ImageLocation::Bundled, &mut files)?;
+    scan_dir(&store.root.join(Rendition::Original.dir()), ImageLocation::Stored, &mut files)?;
+    files.sort_by_key(|f| (f.location == ImageLocation::Stored, f.name.clone()));
+    Ok(files)
+}
+
+// 跨文件系统时 rename 会失败，改为复制后删除
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+/// 为尚无切片或原图已变化的图片生成 IIIF 切片，返回生成的数量；无法解码的图片跳过
+pub fn generate_tiles(store: &MediaStore, files: &[ImageFile]) -> usize {
+    let mut generated = 0;
+    for file in files {
+        let dir = store.tiles_dir(&file.name);
+        if Pyramid::open(&dir, &file.path).is_some() {
+            continue;
+        }
+        match Pyramid::generate(&dir, &file.path) {
+            Ok(_) => generated += 1,
+            Err(e) => eprintln!("生成 {} 的切片失败: {}", file.name, e),
+        }
+    }
+    generated
+}
+
+/// 将图片文件（及其缩略图）移入隔离目录，不删除；返回隔离目录
+pub fn quarantine(store: &MediaStore, files: &[ImageFile]) -> Result<PathBuf> {
+    let dir = store
+        .root
+        .join("quarantine")
+        .join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+
+    for file in files {
+        let target = match file.location {
+            ImageLocation::Bundled => dir.join("bundled"),
+            ImageLocation::Stored => dir.join(Rendition::Original.dir()),
+        };
+        std::fs::create_dir_all(&target)?;
+        move_file(&file.path, &target.join(&file.name))?;
+        // 切片可随时重新生成，直接删除
+        let tiles = store.tiles_dir(&file.name);
+        if tiles.exists() {
+            std::fs::remove_dir_all(&tiles)?;
+        }
+
+        let hash = stored_hash(&file.name).filter(|_| file.location == ImageLocation::Stored);
+        if let Some(hash) = hash {
+            for rendition in [Rendition::Medium, Rendition::Thumb] {
+                let name = format!("{}.jpg", hash);
+                let path = store.stored_path(rendition, &name);
+                if path.exists() {
+                    let target = dir.join(rendition.dir());
+                    std::fs::create_dir_all(&target)?;
+                    move_file(&path, &target.join(&name))?;
+                }
+            }
+        }
+    }
+    Ok(dir)
+}
@@ -0,0 +1,136 @@
+// src-tauri/src/storage/upload.rs
+//! 上传图片的校验、去除定位信息、按内容存储及生成缩略图
+use super::metadata::strip_gps;
+use super::{MediaStore, Rendition};
+use crate::error::{Error, Result};
+use crate::iiif::pyramid::Pyramid;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::io::Cursor;
+use std::path::Path;
+
+/// 单个文件的最大字节数
+pub const MAX_UPLOAD_BYTES: usize = 20 * 1024 * 1024;
+
+// 单边最大像素
+const MAX_DIMENSION: u32 = 20_000;
+
+const MEDIUM_SIZE: u32 = 1280;
+const THUMB_SIZE: u32 = 320;
+const RENDITION_QUALITY: u8 = 85;
+
+/// 保存后的图片信息
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredImage {
+    /// 写入 image_path / artifact_media.path 的文件名
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+    pub size: usize,
+    /// 原文件包含 GPS 定位信息并已删除
+    pub gps_removed: bool,
+    /// 相同内容的图片已存在
+    pub duplicate: bool,
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::invalid_field("content", message)
+}
+
+// 解码并按 Exif 方向旋转，用于校验和生成缩略图
+fn decode(bytes: &[u8]) -> Result<DynamicImage> {
+    let mut reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(anyhow::Error::from)?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    reader.limits(limits);
+
+    let mut decoder = reader
+        .into_decoder()
+        .map_err(|e| invalid(format!("无法解析图片: {}", e)))?;
+    let orientation = decoder
+        .orientation()
+        .map_err(|e| invalid(format!("无法解析图片: {}", e)))?;
+    let mut image =
+        DynamicImage::from_decoder(decoder).map_err(|e| invalid(format!("无法解析图片: {}", e)))?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+// 先写临时文件再改名，避免中断时留下不完整的文件
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn write_rendition(image: &DynamicImage, max_size: u32, path: &Path) -> Result<()> {
+    let resized = if image.width().max(image.height()) > max_size {
+        image.resize(max_size, max_size, FilterType::Triangle)
+    } else {
+        image.clone()
+    };
+    let mut bytes = Vec::new();
+    JpegEncoder::new_with_quality(&mut bytes, RENDITION_QUALITY)
+        .encode_image(&DynamicImage::ImageRgb8(resized.to_rgb8()))
+        .map_err(|e| anyhow::anyhow!("生成缩略图失败: {}", e))?;
+    write_atomic(path, &bytes)
+}
+
+/// 校验并保存上传的图片（阻塞操作）
+///
+/// 仅接受 JPEG 和 PNG；GPS 信息删除后按内容哈希命名，相同图片只保存一份。
+/// 同时生成缩略图、中等尺寸图片和 IIIF 切片。
+pub fn store_upload(store: &MediaStore, bytes: &[u8]) -> Result<StoredImage> {
+    if bytes.is_empty() {
+        return Err(invalid("文件为空"));
+    }
+    if bytes.len() > MAX_UPLOAD_BYTES {
+        return Err(invalid(format!(
+            "图片不能超过 {} MB",
+            MAX_UPLOAD_BYTES / 1024 / 1024
+        )));
+    }
+
+    let (format, ext) = match image::guess_format(bytes) {
+        Ok(ImageFormat::Jpeg) => (ImageFormat::Jpeg, "jpg"),
+        Ok(ImageFormat::Png) => (ImageFormat::Png, "png"),
+        _ => return Err(invalid("仅支持 JPEG 和 PNG 图片")),
+    };
+
+    let (bytes, gps_removed) =
+        strip_gps(format, bytes).ok_or_else(|| invalid("图片文件结构损坏"))?;
+    let image = decode(&bytes)?;
+
+    let name = format!("{:x}.{}", Sha256::digest(&bytes), ext);
+    let hash = &name[..64];
+    let original = store.stored_path(Rendition::Original, &name);
+    let duplicate = original.exists();
+    if !duplicate {
+        write_atomic(&original, &bytes)?;
+    }
+
+    for (rendition, size) in [(Rendition::Medium, MEDIUM_SIZE), (Rendition::Thumb, THUMB_SIZE)] {
+        let path = store.stored_path(rendition, &format!("{}.jpg", hash));
+        if !path.exists() {
+            write_rendition(&image, size, &path)?;
+        }
+    }
+    // 预先生成 IIIF 切片，查看大图时不必解码整幅原图
+    Pyramid::ensure(&store.tiles_dir(&name), &original)?;
+
+    Ok(StoredImage {
+        path: name,
+        width: image.width(),
+        height: image.height(),
+        size: bytes.len(),
+        gps_removed,
+        duplicate,
+    })
+}
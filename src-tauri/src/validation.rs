@@ -0,0 +1,243 @@
+// src-tauri/src/validation.rs
+//! 文物字段校验：必填项、长度上限、图片文件名和尺寸格式
+//!
+//! 逐项检查并收集全部字段的错误，最后一次性返回 `Error::InvalidFields`，
+//! 前端据此在表单中同时标出所有出错的字段。
+use crate::error::{Error, FieldError, Result};
+
+/// 文本字段的规则：(字段, 名称, 是否必填, 最大字符数)
+const TEXT_RULES: &[(&str, &str, bool, usize)] = &[
+    ("title", "名称", true, 200),
+    ("image_path", "图片", true, 255),
+    ("period", "年代", false, 100),
+    ("dynasty", "朝代", true, 50),
+    ("location", "所在地", false, 200),
+    ("description", "简介", false, 2_000),
+    ("detailed_description", "详细介绍", false, 50_000),
+    ("dimensions", "尺寸", false, 200),
+    ("discovery_location", "出土地点", false, 200),
+];
+
+// 图片文件允许的扩展名
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp"];
+
+// 尺寸允许的单位
+const DIMENSION_UNITS: &[&str] = &["mm", "cm", "m", "毫米", "厘米", "米", "g", "kg", "克", "千克", "公斤"];
+
+// 尺寸中多项之间的分隔符
+const DIMENSION_SEPARATORS: &[char] = &['，', ',', '；', ';', '、'];
+
+/// 收集字段校验错误
+#[derive(Debug, Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Validator::default()
+    }
+
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// 记录字段校验错误并返回 None；其他错误直接返回
+    pub fn check<T>(&mut self, result: Result<T>) -> Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(Error::Validation { message, field }) => {
+                self.add(field.as_deref().unwrap_or_default(), message);
+                Ok(None)
+            }
+            Err(Error::InvalidFields(errors)) => {
+                self.errors.extend(errors);
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 按 `TEXT_RULES` 检查必填和长度，没有规则的字段不检查
+    pub fn text(&mut self, field: &str, value: &str) {
+        let Some(&(_, label, required, max_chars)) = TEXT_RULES.iter().find(|(name, ..)| *name == field) else {
+            return;
+        };
+        if value.trim().is_empty() {
+            if required {
+                self.add(field, format!("{}不能为空", label));
+            }
+        } else if value.chars().count() > max_chars {
+            self.add(field, format!("{}不能超过 {} 个字符", label, max_chars));
+        }
+    }
+
+    /// 图片只能是图片目录下的文件名，返回去掉首尾空白后的文件名
+    pub fn image_path(&mut self, value: &str) -> String {
+        let value = value.trim();
+        self.text("image_path", value);
+        if value.is_empty() {
+            return String::new();
+        }
+        if value.contains(['/', '\\', ':']) || value.contains("..") || value.chars().any(char::is_control) {
+            self.add("image_path", "图片只能填写文件名");
+        } else if !value
+            .rsplit_once('.')
+            .is_some_and(|(_, ext)| IMAGE_EXTENSIONS.iter().any(|e| ext.eq_ignore_ascii_case(e)))
+        {
+            self.add("image_path", format!("图片须为 {} 格式", IMAGE_EXTENSIONS.join("、")));
+        }
+        value.to_string()
+    }
+
+    /// 尺寸如「高35.6cm，口径28.5cm」「30×20×10cm」「重 832.84kg」，可为空
+    pub fn dimensions(&mut self, value: &str) {
+        self.text("dimensions", value);
+        let invalid = value
+            .split(DIMENSION_SEPARATORS)
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+            .find(|segment| !is_valid_dimension(segment));
+        if let Some(segment) = invalid {
+            self.add(
+                "dimensions",
+                format!("「{}」不是有效的尺寸，应为数值加单位，如「高35.6cm，口径28.5cm」", segment),
+            );
+        }
+    }
+
+    pub fn into_errors(self) -> Vec<FieldError> {
+        self.errors
+    }
+
+    /// 有错误时返回 `Error::InvalidFields`
+    pub fn finish(self) -> Result<()> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidFields(self.errors))
+        }
+    }
+}
+
+// 一项尺寸：可选的名称（如「高」「口径」），一个或以 × 相连的多个正数，以及单位
+fn is_valid_dimension(segment: &str) -> bool {
+    let Some(start) = segment.find(|c: char| c.is_ascii_digit()) else {
+        return false;
+    };
+    // 负数
+    if segment[..start].trim_end().ends_with(['-', '－']) {
+        return false;
+    }
+    let rest = &segment[start..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | ' ' | '×' | 'x' | 'X' | '*')))
+        .unwrap_or(rest.len());
+    let (numbers, unit) = rest.split_at(end);
+    let numbers_valid = numbers
+        .split(['×', 'x', 'X', '*'])
+        .all(|n| n.trim().parse::<f64>().is_ok_and(|v| v.is_finite() && v > 0.0));
+    numbers_valid && DIMENSION_UNITS.iter().any(|u| unit.trim().eq_ignore_ascii_case(u))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(check: impl FnOnce(&mut Validator)) -> Vec<(String, String)> {
+        let mut validator = Validator::new();
+        check(&mut validator);
+        validator.into_errors().into_iter().map(|e| (e.field, e.message)).collect()
+    }
+
+    #[test]
+    fn required_fields_must_not_be_blank() {
+        assert_eq!(
+            errors(|v| v.text("title", "  ")),
+            [("title".to_string(), "名称不能为空".to_string())]
+        );
+        assert_eq!(errors(|v| v.text("dynasty", ""))[0].1, "朝代不能为空");
+        assert!(errors(|v| v.text("period", "")).is_empty());
+        // 没有规则的字段不检查
+        assert!(errors(|v| v.text("unknown", "")).is_empty());
+    }
+
+    #[test]
+    fn text_limits_count_characters_not_bytes() {
+        assert!(errors(|v| v.text("dynasty", &"清".repeat(50))).is_empty());
+        assert_eq!(
+            errors(|v| v.text("dynasty", &"清".repeat(51))),
+            [("dynasty".to_string(), "朝代不能超过 50 个字符".to_string())]
+        );
+        assert_eq!(errors(|v| v.text("title", &"a".repeat(201))).len(), 1);
+    }
+
+    #[test]
+    fn image_path_must_be_a_plain_file_name() {
+        let mut validator = Validator::new();
+        assert_eq!(validator.image_path(" bronze_ding.JPG "), "bronze_ding.JPG");
+        assert!(validator.into_errors().is_empty());
+
+        for path in [
+            "../secret.jpg",
+            "..\\secret.jpg",
+            "/etc/passwd.png",
+            "C:\\images\\ding.jpg",
+            "images/ding.jpg",
+            "ding..jpg",
+            "ding\n.jpg",
+        ] {
+            let errors = errors(|v| {
+                v.image_path(path);
+            });
+            assert_eq!(errors.len(), 1, "{:?}", path);
+            assert_eq!(errors[0].1, "图片只能填写文件名", "{:?}", path);
+        }
+
+        assert_eq!(errors(|v| { v.image_path("ding.bmp"); })[0].0, "image_path");
+        assert_eq!(errors(|v| { v.image_path("ding"); }).len(), 1);
+        assert_eq!(errors(|v| { v.image_path(""); })[0].1, "图片不能为空");
+    }
+
+    #[test]
+    fn accepts_common_dimension_formats() {
+        for value in [
+            "",
+            "高35.6cm，口径28.5cm",
+            "高133厘米，口长110厘米，口宽79厘米",
+            "30×20×10cm",
+            "30 x 20 cm",
+            "重 832.84kg",
+            "长 5.28 米；宽 24.8 厘米",
+        ] {
+            assert!(errors(|v| v.dimensions(value)).is_empty(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_dimensions() {
+        for value in ["很大", "高35.6", "高-5cm", "高0cm", "30×cm", "高35.6英寸", "高1.2.3cm"] {
+            let errors = errors(|v| v.dimensions(value));
+            assert_eq!(errors.len(), 1, "{:?}", value);
+            assert_eq!(errors[0].0, "dimensions");
+        }
+        // 多项中只报告第一项错误
+        let errors = errors(|v| v.dimensions("高35cm，宽很宽，深也很深"));
+        assert!(errors[0].1.contains("「宽很宽」"), "{}", errors[0].1);
+    }
+
+    #[test]
+    fn finish_collects_all_errors() {
+        let mut validator = Validator::new();
+        validator.text("title", "");
+        validator.dimensions("很大");
+        match validator.finish() {
+            Err(Error::InvalidFields(errors)) => assert_eq!(errors.len(), 2),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(Validator::new().finish().is_ok());
+    }
+}
@@ -0,0 +1,170 @@
+// src-tauri/src/vocabulary.rs
+//! 类别、材质、收藏机构的受控词表
+//!
+//! 词条按 code 写入 artifacts 的文本列（category / material / collection），
+//! 同时以 *_id 外键指向 `vocabulary_terms`。录入时可填 code 或中英文名称。
+use crate::error::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Vocabulary {
+    Category,
+    Material,
+    Collection,
+}
+
+impl Vocabulary {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Vocabulary::Category => "category",
+            Vocabulary::Material => "material",
+            Vocabulary::Collection => "collection",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Vocabulary::Category => "类别",
+            Vocabulary::Material => "材质",
+            Vocabulary::Collection => "收藏机构",
+        }
+    }
+
+    /// artifacts 表中对应的文本列和外键列
+    pub fn columns(&self) -> (&'static str, &'static str) {
+        match self {
+            Vocabulary::Category => ("category", "category_id"),
+            Vocabulary::Material => ("material", "material_id"),
+            Vocabulary::Collection => ("collection", "collection_id"),
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "material" => Vocabulary::Material,
+            "collection" => Vocabulary::Collection,
+            _ => Vocabulary::Category,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VocabularyTerm {
+    pub id: i64,
+    pub vocabulary: Vocabulary,
+    pub code: String,
+    pub label_zh: String,
+    pub label_en: String,
+    pub parent_id: Option<i64>,
+    pub sort_order: i64,
+}
+
+pub(crate) const TERM_COLUMNS: &str = "id, vocabulary, code, label_zh, label_en, parent_id, sort_order";
+
+pub(crate) fn row_to_term(row: &SqliteRow) -> VocabularyTerm {
+    VocabularyTerm {
+        id: row.get("id"),
+        vocabulary: Vocabulary::parse(row.get::<String, _>("vocabulary").as_str()),
+        code: row.get("code"),
+        label_zh: row.get("label_zh"),
+        label_en: row.get("label_en"),
+        parent_id: row.get("parent_id"),
+        sort_order: row.get("sort_order"),
+    }
+}
+
+/// 筛选条件：文物的词条为 code 对应的词条或其下级词条（查询需包含 artifacts a 别名，绑定一个 code）
+pub(crate) fn subtree_condition(vocabulary: Vocabulary) -> String {
+    let (_, id_column) = vocabulary.columns();
+    format!(
+        "a.{} IN (WITH RECURSIVE subtree(id) AS (\
+         SELECT id FROM vocabulary_terms WHERE vocabulary = '{}' AND code = ? \
+         UNION ALL SELECT t.id FROM vocabulary_terms t INNER JOIN subtree s ON t.parent_id = s.id\
+         ) SELECT id FROM subtree)",
+        id_column,
+        vocabulary.as_str()
+    )
+}
+
+/// 经词表校验后的取值：词条的 code 及 id，为空时两者都为空
+#[derive(Debug, Clone, Default)]
+pub struct TermValue {
+    pub code: String,
+    pub id: Option<i64>,
+}
+
+/// 内存中的词表，用于批量校验和显示名称
+pub struct Vocabularies {
+    terms: Vec<VocabularyTerm>,
+}
+
+impl Vocabularies {
+    pub async fn load(pool: &SqlitePool) -> Result<Self> {
+        let terms = sqlx::query(&format!(
+            "SELECT {} FROM vocabulary_terms ORDER BY vocabulary, sort_order, id",
+            TERM_COLUMNS
+        ))
+        .fetch_all(pool)
+        .await?
+        .iter()
+        .map(row_to_term)
+        .collect();
+        Ok(Vocabularies { terms })
+    }
+
+    pub fn terms(&self, vocabulary: Vocabulary) -> impl Iterator<Item = &VocabularyTerm> {
+        self.terms.iter().filter(move |t| t.vocabulary == vocabulary)
+    }
+
+    /// 按 code、中文或英文名称（不区分大小写）查找词条
+    pub fn find(&self, vocabulary: Vocabulary, value: &str) -> Option<&VocabularyTerm> {
+        let value = value.trim();
+        let terms = || self.terms(vocabulary);
+        terms()
+            .find(|t| t.code == value)
+            .or_else(|| terms().find(|t| t.label_zh == value))
+            .or_else(|| terms().find(|t| !t.label_en.is_empty() && t.label_en.eq_ignore_ascii_case(value)))
+            .or_else(|| terms().find(|t| t.code.eq_ignore_ascii_case(value)))
+    }
+
+    /// code 对应的中文名称，不在词表中时原样返回
+    pub fn label<'a>(&'a self, vocabulary: Vocabulary, code: &'a str) -> &'a str {
+        self.terms(vocabulary)
+            .find(|t| t.code == code)
+            .map(|t| t.label_zh.as_str())
+            .unwrap_or(code)
+    }
+
+    /// 校验输入的词条：类别必填，材质和收藏机构可为空
+    pub fn resolve(&self, vocabulary: Vocabulary, value: &str) -> Result<TermValue> {
+        let value = value.trim();
+        if value.is_empty() {
+            if vocabulary == Vocabulary::Category {
+                return Err(Error::invalid_field(vocabulary.as_str(), "类别不能为空"));
+            }
+            return Ok(TermValue::default());
+        }
+        match self.find(vocabulary, value) {
+            Some(term) => Ok(TermValue {
+                code: term.code.clone(),
+                id: Some(term.id),
+            }),
+            None => {
+                let mut message = format!("未知的{}「{}」", vocabulary.label(), value);
+                // 类别较少，直接列出可选项
+                if vocabulary == Vocabulary::Category {
+                    let allowed: Vec<String> = self
+                        .terms(vocabulary)
+                        .map(|t| format!("{}（{}）", t.code, t.label_zh))
+                        .collect();
+                    message.push_str(&format!("，可选: {}", allowed.join("、")));
+                } else {
+                    message.push_str("，请先在词表中添加");
+                }
+                Err(Error::invalid_field(vocabulary.as_str(), message))
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 // src/App.tsx
 import { useEffect, useState } from 'react';
-import { authApi } from './lib/api';
+import { Loader2 } from 'lucide-react';
+import { authApi, getErrorMessage, systemApi } from './lib/api';
 import { User } from './types';
 import { AppRouter } from './router';
 import { LoginDialog } from './components/auth/LoginDialog';
@@ -9,9 +10,20 @@ function App() {
   const [currentUser, setCurrentUser] = useState<User | null>(null);
   const [isLoginDialogOpen, setIsLoginDialogOpen] = useState(false);
   const [successMessage, setSuccessMessage] = useState<string | null>(null);
+  // 数据库初始化完成前各页面的命令都会返回 DATABASE_NOT_READY，先等待再渲染
+  const [dbReady, setDbReady] = useState(false);
+  const [dbError, setDbError] = useState<string | null>(null);
+
+  useEffect(() => {
+    systemApi
+      .waitForDatabase()
+      .then(() => setDbReady(true))
+      .catch((err) => setDbError(getErrorMessage(err, '数据库初始化失败')));
+  }, []);
 
   // 检查用户登录状态
   useEffect(() => {
+    if (!dbReady) return;
     const checkUser = async () => {
       try {
         const user = await authApi.getCurrentUser();
@@ -21,7 +33,7 @@ function App() {
       }
     };
     checkUser();
-  }, []);
+  }, [dbReady]);
 
   const handleLogout = async () => {
     try {
@@ -48,6 +60,28 @@ function App() {
     }
   };
 
+  if (dbError) {
+    return (
+      <div className="flex h-screen items-center justify-center p-6">
+        <div className="max-w-md text-center">
+          <p className="font-semibold text-red-500">无法打开数据库</p>
+          <p className="mt-2 text-sm text-muted-foreground break-all">{dbError}</p>
+        </div>
+      </div>
+    );
+  }
+
+  if (!dbReady) {
+    return (
+      <div className="flex h-screen items-center justify-center">
+        <div className="text-center">
+          <Loader2 className="h-8 w-8 animate-spin mx-auto text-muted-foreground" />
+          <p className="mt-2 text-muted-foreground">正在初始化数据库...</p>
+        </div>
+      </div>
+    );
+  }
+
   return (
     <>
       <AppRouter currentUser={currentUser} onLoginClick={() => setIsLoginDialogOpen(true)} onLogoutClick={handleLogout} />
@@ -1,18 +1,39 @@
-import { useState } from 'react';
+import { useCallback, useEffect, useRef, useState } from 'react';
 import { Button } from './ui/button';
 import { Input } from './ui/input';
 import { Card, CardContent, CardHeader, CardTitle } from './ui/card';
 import { ScrollArea } from './ui/scroll-area';
-import { Send, Bot, User, Lightbulb, HelpCircle, BookOpen, MapPin } from 'lucide-react';
-import { aiApi } from '../lib/api';
+import { Send, Square, Bot, User, Lightbulb, HelpCircle, BookOpen, MapPin, Plus, Pencil, Trash2, MessageSquare } from 'lucide-react';
+import { aiApi, artifactApi, conversationApi, getAuthToken } from '../lib/api';
+import { ArtifactWithFavorite, Citation, Conversation, ConversationMessage } from '../types';
+import { ArtifactDialog } from './artifacts/ArtifactDialog';
 
 interface Message {
   id: string;
   content: string;
   role: 'user' | 'assistant';
   timestamp: Date;
+  citations?: Citation[];
 }
 
+// 去掉回答中的 [#id] 引用标注，引用以文物链接的形式单独展示
+const stripCitationMarkers = (content: string) => content.replace(/\s?\[#\d+\]/g, '');
+
+const welcomeMessage: Message = {
+  id: 'welcome',
+  content: '您好！我是数字文物博物馆的AI助手"文博助手"。我可以帮您了解文物知识、历史背景、文化内涵，或者为您提供参观建议。\n\n💡 **您可以问我：**\n• 青铜器、陶瓷、玉器、书画等文物知识\n• 博物馆参观建议和注意事项\n• 文物的历史背景和文化意义',
+  role: 'assistant',
+  timestamp: new Date()
+};
+
+const toMessage = (message: ConversationMessage): Message => ({
+  id: `db-${message.id}`,
+  content: message.cancelled ? `${message.content}（已停止生成）` : message.content,
+  role: message.role,
+  timestamp: new Date(message.created_at),
+  citations: message.citations,
+});
+
 const presetQuestions = [
   { icon: BookOpen, text: "介绍一下青铜器", category: "文物知识" },
   { icon: Lightbulb, text: "博物馆参观注意事项", category: "参观指南" },
@@ -21,16 +42,84 @@ const presetQuestions = [
 ];
 
 export default function AIAssistant() {
-  const [messages, setMessages] = useState<Message[]>([
-    {
-      id: '1',
-      content: '您好！我是数字文物博物馆的AI助手"文博助手"。我可以帮您了解文物知识、历史背景、文化内涵，或者为您提供参观建议。\n\n💡 **您可以问我：**\n• 青铜器、陶瓷、玉器、书画等文物知识\n• 博物馆参观建议和注意事项\n• 文物的历史背景和文化意义',
-      role: 'assistant',
-      timestamp: new Date()
-    }
-  ]);
+  const [messages, setMessages] = useState<Message[]>([welcomeMessage]);
   const [input, setInput] = useState('');
   const [isLoading, setIsLoading] = useState(false);
+  // 正在流式生成的请求ID，用于取消
+  const activeRequestRef = useRef<string | null>(null);
+  const [selectedArtifact, setSelectedArtifact] = useState<ArtifactWithFavorite | null>(null);
+  // 登录后对话保存在后端，未登录时仅保留在当前页面
+  const isLoggedIn = !!getAuthToken();
+  const [conversations, setConversations] = useState<Conversation[]>([]);
+  const [activeConversationId, setActiveConversationId] = useState<number | null>(null);
+
+  const loadConversations = useCallback(async () => {
+    if (!getAuthToken()) return;
+    try {
+      setConversations(await conversationApi.list());
+    } catch (error) {
+      console.error('Failed to load conversations:', error);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadConversations();
+  }, [loadConversations]);
+
+  const handleSelectConversation = async (conversationId: number) => {
+    if (isLoading) return;
+    try {
+      const detail = await conversationApi.get(conversationId);
+      setActiveConversationId(detail.id);
+      setMessages([welcomeMessage, ...detail.messages.map(toMessage)]);
+    } catch (error) {
+      console.error('Failed to load conversation:', error);
+    }
+  };
+
+  const handleNewConversation = () => {
+    if (isLoading) return;
+    setActiveConversationId(null);
+    setMessages([welcomeMessage]);
+  };
+
+  const handleRenameConversation = async (conversation: Conversation) => {
+    const title = window.prompt('重命名对话', conversation.title);
+    if (!title || !title.trim() || title === conversation.title) return;
+    try {
+      await conversationApi.rename(conversation.id, title);
+      await loadConversations();
+    } catch (error) {
+      console.error('Failed to rename conversation:', error);
+    }
+  };
+
+  const handleDeleteConversation = async (conversation: Conversation) => {
+    if (!window.confirm(`确定删除对话「${conversation.title}」吗？`)) return;
+    try {
+      await conversationApi.delete(conversation.id);
+      if (conversation.id === activeConversationId) {
+        handleNewConversation();
+      }
+      await loadConversations();
+    } catch (error) {
+      console.error('Failed to delete conversation:', error);
+    }
+  };
+
+  const handleOpenCitation = async (artifactId: number) => {
+    try {
+      const artifact = await artifactApi.getArtifactById(artifactId);
+      if (artifact) setSelectedArtifact(artifact);
+    } catch (error) {
+      console.error('Failed to load cited artifact:', error);
+    }
+  };
+
+  const handleToggleFavorite = async (artifactId: number) => {
+    const isFavorite = await artifactApi.toggleFavorite(artifactId);
+    setSelectedArtifact(prev => (prev && prev.id === artifactId ? { ...prev, is_favorite: isFavorite } : prev));
+  };
 
   const handleSend = async () => {
     if (!input.trim()) return;
@@ -41,35 +130,74 @@ export default function AIAssistant() {
       role: 'user',
       timestamp: new Date()
     };
+    const assistantId = (Date.now() + 1).toString();
 
     setMessages(prev => [...prev, userMessage]);
     const currentInput = input;
     setInput('');
     setIsLoading(true);
 
+    const requestId = crypto.randomUUID();
+    activeRequestRef.current = requestId;
+
+    // 收到第一段文本时再插入助手消息，之后逐段追加
+    const appendDelta = (delta: string) => {
+      setMessages(prev => {
+        if (!prev.some(msg => msg.id === assistantId)) {
+          return [...prev, { id: assistantId, content: delta, role: 'assistant', timestamp: new Date() }];
+        }
+        return prev.map(msg =>
+          msg.id === assistantId ? { ...msg, content: msg.content + delta } : msg
+        );
+      });
+    };
+
     try {
-      // 调用DeepSeek API（或模拟响应）
-      const response = await aiApi.chatWithAI(
+      if (isLoggedIn) {
+        // 没有打开的对话时先新建，历史由后端读取
+        let conversationId = activeConversationId;
+        if (conversationId === null) {
+          conversationId = (await conversationApi.create()).id;
+          setActiveConversationId(conversationId);
+        }
+        const turn = await conversationApi.continue(conversationId, requestId, currentInput, appendDelta);
+        if (turn.cancelled && !turn.assistant_message) {
+          appendDelta('（已停止生成）');
+        }
+        const citations = turn.assistant_message?.citations ?? [];
+        if (citations.length > 0) {
+          setMessages(prev => prev.map(msg =>
+            msg.id === assistantId ? { ...msg, citations } : msg
+          ));
+        }
+        await loadConversations();
+        return;
+      }
+
+      // 调用DeepSeek API（或模拟响应），增量显示回复
+      const { response, citations, cancelled } = await aiApi.chatWithAIStream(
+        requestId,
         currentInput, 
         messages.slice(-5).map(msg => ({
           role: msg.role,
           content: msg.content
-        }))
+        })),
+        appendDelta
       );
 
-      const assistantMessage: Message = {
-        id: (Date.now() + 1).toString(),
-        content: response,
-        role: 'assistant',
-        timestamp: new Date()
-      };
-
-      setMessages(prev => [...prev, assistantMessage]);
+      if (cancelled && !response) {
+        appendDelta('（已停止生成）');
+      }
+      if (citations.length > 0) {
+        setMessages(prev => prev.map(msg =>
+          msg.id === assistantId ? { ...msg, citations } : msg
+        ));
+      }
     } catch (error) {
       console.error('Error sending message:', error);
       
       const errorMessage: Message = {
-        id: (Date.now() + 1).toString(),
+        id: (Date.now() + 2).toString(),
         content: '抱歉，我现在无法回复您的消息。请检查网络连接或稍后再试。',
         role: 'assistant',
         timestamp: new Date()
@@ -77,10 +205,17 @@ export default function AIAssistant() {
 
       setMessages(prev => [...prev, errorMessage]);
     } finally {
+      activeRequestRef.current = null;
       setIsLoading(false);
     }
   };
 
+  const handleStop = () => {
+    if (activeRequestRef.current) {
+      aiApi.cancelChat(activeRequestRef.current).catch(console.error);
+    }
+  };
+
   const handleKeyPress = (e: React.KeyboardEvent) => {
     if (e.key === 'Enter' && !e.shiftKey) {
       e.preventDefault();
@@ -93,7 +228,53 @@ export default function AIAssistant() {
   };
 
   return (
-    <div className="flex flex-col h-full p-4">
+    <div className="flex h-full gap-4 p-4">
+      {isLoggedIn && (
+        <Card className="w-60 flex-shrink-0 flex flex-col">
+          <CardHeader className="pb-3">
+            <Button variant="outline" size="sm" onClick={handleNewConversation} disabled={isLoading}>
+              <Plus className="h-4 w-4 mr-1" />
+              新对话
+            </Button>
+          </CardHeader>
+          <CardContent className="flex-1 p-0">
+            <ScrollArea className="h-full px-2 pb-2">
+              {conversations.length === 0 ? (
+                <p className="text-xs text-muted-foreground text-center py-4">暂无历史对话</p>
+              ) : (
+                <div className="space-y-1">
+                  {conversations.map(conversation => (
+                    <div
+                      key={conversation.id}
+                      className={`group flex items-center gap-2 rounded-md px-2 py-1.5 text-sm cursor-pointer hover:bg-muted ${
+                        conversation.id === activeConversationId ? 'bg-muted font-medium' : ''
+                      }`}
+                      onClick={() => handleSelectConversation(conversation.id)}
+                    >
+                      <MessageSquare className="h-4 w-4 flex-shrink-0" />
+                      <span className="flex-1 truncate" title={conversation.title}>{conversation.title}</span>
+                      <button
+                        className="opacity-0 group-hover:opacity-100"
+                        title="重命名"
+                        onClick={(e) => { e.stopPropagation(); handleRenameConversation(conversation); }}
+                      >
+                        <Pencil className="h-3 w-3" />
+                      </button>
+                      <button
+                        className="opacity-0 group-hover:opacity-100 text-destructive"
+                        title="删除"
+                        onClick={(e) => { e.stopPropagation(); handleDeleteConversation(conversation); }}
+                      >
+                        <Trash2 className="h-3 w-3" />
+                      </button>
+                    </div>
+                  ))}
+                </div>
+              )}
+            </ScrollArea>
+          </CardContent>
+        </Card>
+      )}
       <Card className="flex-1 flex flex-col">
         <CardHeader className="pb-3">
           <CardTitle className="flex items-center gap-2">
@@ -155,7 +336,25 @@ export default function AIAssistant() {
                         : 'bg-muted'
                     }`}
                   >
-                    <p className="text-sm whitespace-pre-wrap">{message.content}</p>
+                    <p className="text-sm whitespace-pre-wrap">
+                      {message.role === 'assistant' ? stripCitationMarkers(message.content) : message.content}
+                    </p>
+                    {message.citations && message.citations.length > 0 && (
+                      <div className="flex flex-wrap gap-1 mt-2">
+                        {message.citations.map(citation => (
+                          <Button
+                            key={citation.artifact_id}
+                            variant="outline"
+                            size="sm"
+                            className="h-6 px-2 text-xs"
+                            onClick={() => handleOpenCitation(citation.artifact_id)}
+                          >
+                            <BookOpen className="h-3 w-3 mr-1" />
+                            {citation.title}
+                          </Button>
+                        ))}
+                      </div>
+                    )}
                     <p className="text-xs opacity-70 mt-1">
                       {message.timestamp.toLocaleTimeString()}
                     </p>
@@ -168,7 +367,7 @@ export default function AIAssistant() {
                 </div>
               ))}
               
-              {isLoading && (
+              {isLoading && messages[messages.length - 1]?.role === 'user' && (
                 <div className="flex gap-3 justify-start">
                   <div className="w-8 h-8 rounded-full bg-primary/10 flex items-center justify-center flex-shrink-0">
                     <Bot className="h-4 w-4" />
@@ -195,13 +394,19 @@ export default function AIAssistant() {
                 disabled={isLoading}
                 className="flex-1"
               />
-              <Button 
-                onClick={handleSend} 
-                disabled={isLoading || !input.trim()}
-                size="icon"
-              >
-                <Send className="h-4 w-4" />
-              </Button>
+              {isLoading ? (
+                <Button onClick={handleStop} variant="outline" size="icon" title="停止生成">
+                  <Square className="h-4 w-4" />
+                </Button>
+              ) : (
+                <Button 
+                  onClick={handleSend} 
+                  disabled={!input.trim()}
+                  size="icon"
+                >
+                  <Send className="h-4 w-4" />
+                </Button>
+              )}
             </div>
             {/* <div className="text-xs text-muted-foreground text-center">
               💡 现在在模拟模式下运行，无需API密钥即可体验完整功能
@@ -209,6 +414,16 @@ export default function AIAssistant() {
           </div>
         </CardContent>
       </Card>
+
+      {selectedArtifact && (
+        <ArtifactDialog
+          open={!!selectedArtifact}
+          onOpenChange={(open) => !open && setSelectedArtifact(null)}
+          artifact={selectedArtifact}
+          onToggleFavorite={handleToggleFavorite}
+          onOpenArtifact={handleOpenCitation}
+        />
+      )}
     </div>
   );
 }
@@ -2,9 +2,14 @@ import { useState, useEffect, useRef } from 'react';
 import { Card, CardContent, CardHeader, CardTitle } from './ui/card';
 import { Button } from './ui/button';
 import { Badge } from './ui/badge';
-import { Search, MapPin, Navigation, Layers, Info } from 'lucide-react';
+import { Switch } from './ui/switch';
+import { Search, MapPin, Navigation, Layers, Info, Locate } from 'lucide-react';
 import L from 'leaflet';
 import 'leaflet/dist/leaflet.css';
+import { artifactApi, geoApi, getErrorMessage } from '@/lib/api';
+import { Category, DensityGrid, MapArtifact, MapCluster, MapQuery } from '@/types';
+import { ArtifactDialog } from './artifacts/ArtifactDialog';
+import { useVocabulary } from '@/hooks/useVocabulary';
 
 // 修复 Leaflet 图标在 React 中的问题
 import markerIcon from 'leaflet/dist/images/marker-icon.png';
@@ -24,231 +29,323 @@ const DefaultIcon = L.icon({
 
 L.Marker.prototype.options.icon = DefaultIcon;
 
-interface ArtifactLocation {
-  id: number;
-  title: string;
-  category: string;
-  dynasty: string;
-  location: string;
-  description: string;
-  coordinates: {
-    lng: number;
-    lat: number;
-  };
-}
-
-// 模拟文物位置数据
-const mockArtifactLocations: ArtifactLocation[] = [
-  {
-    id: 1,
-    title: "司母戊鼎",
-    category: "青铜器",
-    dynasty: "商代",
-    location: "河南安阳殷墟",
-    description: "中国现存最大的青铜器",
-    coordinates: { lng: 114.3524, lat: 36.0671 }
-  },
-  {
-    id: 2,
-    title: "四羊方尊",
-    category: "青铜器",
-    dynasty: "商代",
-    location: "湖南宁乡",
-    description: "中国商代晚期青铜礼器",
-    coordinates: { lng: 112.5459, lat: 28.2534 }
-  },
-  {
-    id: 3,
-    title: "马王堆汉墓帛画",
-    category: "书画",
-    dynasty: "汉代",
-    location: "湖南长沙马王堆",
-    description: "汉代绘画艺术珍品",
-    coordinates: { lng: 112.9388, lat: 28.2282 }
-  },
-  {
-    id: 4,
-    title: "唐三彩骆驼载乐俑",
-    category: "陶瓷",
-    dynasty: "唐代",
-    location: "陕西西安",
-    description: "唐代陶瓷艺术代表作",
-    coordinates: { lng: 108.9402, lat: 34.3416 }
-  },
-  {
-    id: 5,
-    title: "清明上河图",
-    category: "书画",
-    dynasty: "北宋",
-    location: "河南开封",
-    description: "中国十大传世名画之一",
-    coordinates: { lng: 114.3479, lat: 34.7971 }
-  },
-  {
-    id: 6,
-    title: "金缕玉衣",
-    category: "玉器",
-    dynasty: "汉代",
-    location: "河北满城汉墓",
-    description: "汉代玉衣精品",
-    coordinates: { lng: 115.4712, lat: 38.9537 }
-  }
-];
+const DEFAULT_CENTER: L.LatLngTuple = [34.3416, 108.9402];
+const DEFAULT_ZOOM = 5;
+
+// 「附近文物」的查询半径（公里）
+const NEARBY_RADIUS_KM = 100;
+
+// 弹窗内容来自数据库，需转义
+const escapeHtml = (text: string) =>
+  text.replace(/[&<>"']/g, ch => ({ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;', "'": '&#39;' })[ch]!);
+
+const popupContent = (artifact: MapArtifact, categoryLabel: (code: string) => string) => `
+  <div style="padding: 10px; max-width: 200px;">
+    <h4 style="margin: 0 0 8px 0; font-weight: bold;">${escapeHtml(artifact.title)}</h4>
+    <p style="margin: 4px 0; font-size: 12px;"><strong>类别:</strong> ${escapeHtml(categoryLabel(artifact.category))}</p>
+    <p style="margin: 4px 0; font-size: 12px;"><strong>朝代:</strong> ${escapeHtml(artifact.dynasty)}</p>
+    <p style="margin: 4px 0; font-size: 12px;"><strong>出土地点:</strong> ${escapeHtml(artifact.discovery_location || artifact.location)}</p>
+    <p style="margin: 4px 0; font-size: 12px;">${escapeHtml(artifact.description)}</p>
+  </div>
+`;
+
+// 聚合点的图标，大小随数量增长
+const clusterIcon = (cluster: MapCluster) => {
+  const size = Math.min(28 + Math.log10(cluster.count) * 12, 60);
+  return L.divIcon({
+    html: `<div style="width:${size}px;height:${size}px;line-height:${size}px;border-radius:50%;background:rgba(37,99,235,0.85);color:#fff;text-align:center;font-size:12px;font-weight:600;border:2px solid #fff;">${cluster.count}</div>`,
+    className: '',
+    iconSize: [size, size],
+    iconAnchor: [size / 2, size / 2],
+  });
+};
+
+const formatUncertainty = (meters: number | null) => {
+  if (meters === null) return '';
+  return meters >= 1000 ? `±${(meters / 1000).toFixed(0)} 公里` : `±${meters.toFixed(0)} 米`;
+};
 
 export default function MapExploration() {
   const mapContainer = useRef<HTMLDivElement>(null);
   const mapRef = useRef<L.Map | null>(null);
-  const markersRef = useRef<L.Marker[]>([]);
-  const [selectedArtifact, setSelectedArtifact] = useState<ArtifactLocation | null>(null);
+  const markersRef = useRef<L.LayerGroup | null>(null);
+  const heatmapRef = useRef<L.LayerGroup | null>(null);
+  const uncertaintyRef = useRef<L.Circle | null>(null);
+  const [artifacts, setArtifacts] = useState<MapArtifact[]>([]);
+  const [unlocated, setUnlocated] = useState(0);
+  const [truncated, setTruncated] = useState(0);
+  const [clusters, setClusters] = useState<MapCluster[]>([]);
+  const [heatmap, setHeatmap] = useState<DensityGrid | null>(null);
+  const [showHeatmap, setShowHeatmap] = useState(false);
+  const [selectedArtifact, setSelectedArtifact] = useState<MapArtifact | null>(null);
+  const [detailArtifact, setDetailArtifact] = useState<MapArtifact | null>(null);
   const [searchQuery, setSearchQuery] = useState('');
-  const [filterCategory, setFilterCategory] = useState<string>('all');
+  const [debouncedQuery, setDebouncedQuery] = useState('');
+  const [filterCategory, setFilterCategory] = useState<Category>('all');
+  const { termsOf, label } = useVocabulary();
+  const categoryLabel = (category: string) => label('category', category);
+  // 筛选按钮只列出顶级类别，筛选结果包含下级类别
+  const categories: Array<{ value: Category; label: string }> = [
+    { value: 'all', label: '全部' },
+    ...termsOf('category')
+      .filter(term => term.parent_id === null)
+      .map(term => ({ value: term.code, label: term.label_zh })),
+  ];
+  // 只显示当前视野内的文物，移动地图时重新查询
+  const [inViewOnly, setInViewOnly] = useState(false);
+  // 以某件文物为中心查询附近的文物
+  const [nearby, setNearby] = useState<MapArtifact | null>(null);
   const [mapLoaded, setMapLoaded] = useState(false);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
 
   // 初始化地图
   useEffect(() => {
     if (!mapContainer.current || mapRef.current) return;
 
-    const initializeMap = () => {
-      try {
-        // 创建地图实例
-        const mapInstance = L.map(mapContainer.current!).setView([34.3416, 108.9402], 5);
-
-        // 添加 OpenStreetMap 图层
-        L.tileLayer('https://{s}.tile.openstreetmap.org/{z}/{x}/{y}.png', {
-          attribution: '© <a href="https://www.openstreetmap.org/copyright">OpenStreetMap</a> contributors',
-          maxZoom: 19,
-        }).addTo(mapInstance);
-
-        // 添加缩放控件
-        L.control.zoom({
-          position: 'topright'
-        }).addTo(mapInstance);
-
-        // 添加比例尺
-        L.control.scale({
-          imperial: false,
-          metric: true
-        }).addTo(mapInstance);
-
-        mapRef.current = mapInstance;
-        setMapLoaded(true);
-        console.log('Leaflet 地图初始化成功');
-
-        // 添加默认标记
-        addArtifactMarkers();
-
-        // 处理窗口大小变化
-        const handleResize = () => {
-          mapInstance.invalidateSize();
-        };
-        
-        window.addEventListener('resize', handleResize);
-
-        return () => {
-          window.removeEventListener('resize', handleResize);
-          if (mapInstance) {
-            mapInstance.remove();
-          }
+    try {
+      // 创建地图实例
+      const mapInstance = L.map(mapContainer.current).setView(DEFAULT_CENTER, DEFAULT_ZOOM);
+
+      // 添加 OpenStreetMap 图层
+      L.tileLayer('https://{s}.tile.openstreetmap.org/{z}/{x}/{y}.png', {
+        attribution: '© <a href="https://www.openstreetmap.org/copyright">OpenStreetMap</a> contributors',
+        maxZoom: 19,
+      }).addTo(mapInstance);
+
+      // 添加缩放控件
+      L.control.zoom({
+        position: 'topright'
+      }).addTo(mapInstance);
+
+      // 添加比例尺
+      L.control.scale({
+        imperial: false,
+        metric: true
+      }).addTo(mapInstance);
+
+      markersRef.current = L.layerGroup().addTo(mapInstance);
+      heatmapRef.current = L.layerGroup().addTo(mapInstance);
+      mapRef.current = mapInstance;
+      setMapLoaded(true);
+
+      // 处理窗口大小变化
+      const handleResize = () => {
+        mapInstance.invalidateSize();
+      };
+
+      window.addEventListener('resize', handleResize);
+
+      return () => {
+        window.removeEventListener('resize', handleResize);
+        mapInstance.remove();
+        mapRef.current = null;
+      };
+    } catch (error) {
+      console.error('Leaflet 地图初始化失败:', error);
+    }
+  }, []);
+
+  // 搜索词防抖
+  useEffect(() => {
+    const timer = setTimeout(() => setDebouncedQuery(searchQuery.trim()), 300);
+    return () => clearTimeout(timer);
+  }, [searchQuery]);
+
+  // 按筛选条件、视野或附近范围查询文物
+  useEffect(() => {
+    const map = mapRef.current;
+    if (!map) return;
+    let cancelled = false;
+
+    const load = async (fit: boolean) => {
+      const query: MapQuery = {
+        params: {
+          query: debouncedQuery || undefined,
+          category: filterCategory,
+        },
+      };
+      if (nearby && nearby.latitude !== null && nearby.longitude !== null) {
+        query.center = { latitude: nearby.latitude, longitude: nearby.longitude };
+        query.radius_km = NEARBY_RADIUS_KM;
+      } else if (inViewOnly) {
+        const bounds = map.getBounds();
+        query.bbox = {
+          south: Math.max(bounds.getSouth(), -90),
+          west: Math.max(bounds.getWest(), -180),
+          north: Math.min(bounds.getNorth(), 90),
+          east: Math.min(bounds.getEast(), 180),
         };
-      } catch (error) {
-        console.error('Leaflet 地图初始化失败:', error);
+      }
+
+      try {
+        setLoading(true);
+        setError(null);
+        const result = await geoApi.getMapArtifacts(query);
+        if (cancelled) return;
+        setArtifacts(result.items);
+        setUnlocated(result.unlocated);
+        setTruncated(result.truncated);
+
+        // 调整地图视图以显示所有标记
+        if (fit && result.items.length > 0) {
+          const bounds = L.latLngBounds(result.items.map(a => [a.latitude!, a.longitude!] as L.LatLngTuple));
+          map.fitBounds(bounds.pad(0.1), { maxZoom: 10 });
+        }
+      } catch (err) {
+        if (!cancelled) setError(getErrorMessage(err, '加载地图数据失败'));
+      } finally {
+        if (!cancelled) setLoading(false);
       }
     };
 
-    initializeMap();
-  }, []);
+    load(!inViewOnly);
 
-  // 添加文物标记
-  const addArtifactMarkers = () => {
-    if (!mapRef.current) return;
+    if (!inViewOnly || nearby) {
+      return () => {
+        cancelled = true;
+      };
+    }
+    const handleMoveEnd = () => load(false);
+    map.on('moveend', handleMoveEnd);
+    return () => {
+      cancelled = true;
+      map.off('moveend', handleMoveEnd);
+    };
+  }, [mapLoaded, debouncedQuery, filterCategory, inViewOnly, nearby]);
 
-    // 清除现有标记
-    markersRef.current.forEach(marker => {
-      marker.remove();
-    });
-    markersRef.current = [];
-
-    // 过滤文物
-    const filteredArtifacts = mockArtifactLocations.filter(artifact => {
-      const matchesSearch = artifact.title.toLowerCase().includes(searchQuery.toLowerCase()) ||
-                           artifact.location.toLowerCase().includes(searchQuery.toLowerCase());
-      const matchesCategory = filterCategory === 'all' || artifact.category === filterCategory;
-      return matchesSearch && matchesCategory;
-    });
+  // 地图上的标记按当前视野和缩放级别由后端聚合
+  useEffect(() => {
+    const map = mapRef.current;
+    if (!map) return;
+    let cancelled = false;
 
-    // 添加新标记
-    filteredArtifacts.forEach(artifact => {
-      const marker = L.marker([artifact.coordinates.lat, artifact.coordinates.lng])
-        .addTo(mapRef.current!)
-        .bindPopup(`
-          <div style="padding: 10px; max-width: 200px;">
-            <h4 style="margin: 0 0 8px 0; font-weight: bold;">${artifact.title}</h4>
-            <p style="margin: 4px 0; font-size: 12px;"><strong>类别:</strong> ${artifact.category}</p>
-            <p style="margin: 4px 0; font-size: 12px;"><strong>朝代:</strong> ${artifact.dynasty}</p>
-            <p style="margin: 4px 0; font-size: 12px;"><strong>地点:</strong> ${artifact.location}</p>
-            <p style="margin: 4px 0; font-size: 12px;">${artifact.description}</p>
-          </div>
-        `);
+    const loadClusters = async () => {
+      const bounds = map.getBounds();
+      try {
+        const result = await geoApi.getMapClusters({
+          bbox: {
+            south: Math.max(bounds.getSouth(), -90),
+            west: Math.max(bounds.getWest(), -180),
+            north: Math.min(bounds.getNorth(), 90),
+            east: Math.min(bounds.getEast(), 180),
+          },
+          zoom: Math.round(map.getZoom()),
+          params: {
+            query: debouncedQuery || undefined,
+            category: filterCategory,
+          },
+        });
+        if (cancelled) return;
+        setClusters(result.clusters);
+        setHeatmap(result.heatmap);
+      } catch (err) {
+        if (!cancelled) setError(getErrorMessage(err, '加载地图数据失败'));
+      }
+    };
 
-      marker.on('click', () => {
-        setSelectedArtifact(artifact);
-      });
+    loadClusters();
+    map.on('moveend', loadClusters);
+    return () => {
+      cancelled = true;
+      map.off('moveend', loadClusters);
+    };
+  }, [mapLoaded, debouncedQuery, filterCategory]);
 
-      marker.on('popupopen', () => {
-        setSelectedArtifact(artifact);
-      });
+  // 同步地图标记
+  useEffect(() => {
+    const map = mapRef.current;
+    const layer = markersRef.current;
+    if (!map || !layer) return;
 
-      markersRef.current.push(marker);
-    });
+    // 清除现有标记
+    layer.clearLayers();
+
+    clusters.forEach(cluster => {
+      const position: L.LatLngTuple = [cluster.latitude, cluster.longitude];
+      if (cluster.artifact_id === null) {
+        const marker = L.marker(position, { icon: clusterIcon(cluster) })
+          .bindTooltip(`${cluster.count} 件 · 多为${categoryLabel(cluster.dominant_category)} · ${cluster.dominant_dynasty}`)
+          .addTo(layer);
+        // 点击聚合点时缩放到其范围
+        marker.on('click', () => {
+          const { south, west, north, east } = cluster.bounds;
+          map.fitBounds([[south, west], [north, east]], { padding: [40, 40], maxZoom: 12 });
+        });
+        return;
+      }
 
-    // 调整地图视图以显示所有标记
-    if (filteredArtifacts.length > 0) {
-      const group = L.featureGroup(markersRef.current);
-      mapRef.current.fitBounds(group.getBounds().pad(0.1));
-    }
-  };
+      const artifact = artifacts.find(a => a.id === cluster.artifact_id);
+      const marker = L.marker(position)
+        .addTo(layer)
+        .bindPopup(artifact ? popupContent(artifact, categoryLabel) : `<strong>${escapeHtml(cluster.title ?? '')}</strong>`);
+      if (artifact) {
+        marker.on('click', () => setSelectedArtifact(artifact));
+      }
+    });
+  }, [clusters, artifacts, label]);
 
-  // 当搜索或过滤条件变化时更新标记
+  // 热力图：按格子内的数量设置透明度
   useEffect(() => {
-    if (mapRef.current) {
-      addArtifactMarkers();
-    }
-  }, [searchQuery, filterCategory]);
-
-  const categories = ['all', '青铜器', '陶瓷', '玉器', '书画'];
-
-  const handleLocateArtifact = (artifact: ArtifactLocation) => {
-    if (!mapRef.current) return;
-    
+    const layer = heatmapRef.current;
+    if (!layer) return;
+    layer.clearLayers();
+    if (!showHeatmap || !heatmap || heatmap.max === 0) return;
+
+    heatmap.cells.forEach(cell => {
+      const south = heatmap.south + cell.row * heatmap.cell_lat;
+      const west = heatmap.west + cell.col * heatmap.cell_lng;
+      L.rectangle([[south, west], [south + heatmap.cell_lat, west + heatmap.cell_lng]], {
+        stroke: false,
+        fillColor: '#dc2626',
+        fillOpacity: 0.15 + 0.6 * (cell.count / heatmap.max),
+        interactive: false,
+      }).addTo(layer);
+    });
+  }, [heatmap, showHeatmap]);
 
-    const zoomLevel = 7;
-    mapRef.current.setView([artifact.coordinates.lat, artifact.coordinates.lng], zoomLevel);
+  // 选中文物时显示坐标的不确定范围
+  useEffect(() => {
+    const map = mapRef.current;
+    uncertaintyRef.current?.remove();
+    uncertaintyRef.current = null;
+    if (!map || !selectedArtifact?.location_uncertainty_m) return;
+    if (selectedArtifact.latitude === null || selectedArtifact.longitude === null) return;
+    uncertaintyRef.current = L.circle([selectedArtifact.latitude, selectedArtifact.longitude], {
+      radius: selectedArtifact.location_uncertainty_m,
+      color: '#3b82f6',
+      weight: 1,
+      fillOpacity: 0.08,
+    }).addTo(map);
+  }, [selectedArtifact]);
+
+  const handleLocateArtifact = (artifact: MapArtifact) => {
+    if (!mapRef.current || artifact.latitude === null || artifact.longitude === null) return;
+
+    const zoomLevel = 9;
+    mapRef.current.setView([artifact.latitude, artifact.longitude], zoomLevel);
     setSelectedArtifact(artifact);
-    
-    // 打开对应的标记弹窗
-    const targetMarker = markersRef.current.find(marker => {
-      const latlng = marker.getLatLng();
-      return latlng.lat === artifact.coordinates.lat && 
-             latlng.lng === artifact.coordinates.lng;
-    });
-    
-    if (targetMarker) {
-      // 使用setTimeout确保在地图移动完成后再打开弹窗
-      setTimeout(() => {
-        targetMarker.openPopup();
-      }, 300);
-    }
+
+    // 标记会随视野重新聚合，弹窗直接挂在地图上
+    L.popup({ offset: [0, -34] })
+      .setLatLng([artifact.latitude, artifact.longitude])
+      .setContent(popupContent(artifact, categoryLabel))
+      .openOn(mapRef.current);
   };
 
   const handleResetView = () => {
     if (!mapRef.current) return;
-    mapRef.current.setView([34.3416, 108.9402], 5);
+    mapRef.current.setView(DEFAULT_CENTER, DEFAULT_ZOOM);
     setSelectedArtifact(null);
-    
+    setNearby(null);
+
     // 关闭所有弹窗
-    markersRef.current.forEach(marker => {
-      marker.closePopup();
-    });
+    mapRef.current.closePopup();
+  };
+
+  const handleToggleFavorite = async (artifactId: number) => {
+    const isFavorite = await artifactApi.toggleFavorite(artifactId);
+    setDetailArtifact(prev => (prev && prev.id === artifactId ? { ...prev, is_favorite: isFavorite } : prev));
   };
 
   return (
@@ -261,13 +358,13 @@ export default function MapExploration() {
               <MapPin className="h-5 w-5" />
               文物地图探索
               <Badge variant="secondary" className="ml-auto">
-                {mockArtifactLocations.length} 个文物地点
+                {loading ? '加载中...' : `${artifacts.length} 个文物地点`}
               </Badge>
             </CardTitle>
           </CardHeader>
           <CardContent className="p-0 h-full">
-            <div 
-              ref={mapContainer} 
+            <div
+              ref={mapContainer}
               className="w-full h-full min-h-[500px] bg-gray-100 relative z-0"
             >
               {!mapLoaded && (
@@ -300,22 +397,41 @@ export default function MapExploration() {
                   className="w-full pl-10 pr-4 py-2 border rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500"
                 />
               </div>
-              
+
               <div>
                 <label className="text-sm font-medium mb-2 block">文物类别</label>
                 <div className="flex flex-wrap gap-2">
                   {categories.map(category => (
                     <Button
-                      key={category}
-                      variant={filterCategory === category ? "default" : "outline"}
+                      key={category.value}
+                      variant={filterCategory === category.value ? "default" : "outline"}
                       size="sm"
-                      onClick={() => setFilterCategory(category)}
+                      onClick={() => setFilterCategory(category.value)}
                     >
-                      {category === 'all' ? '全部' : category}
+                      {category.label}
                     </Button>
                   ))}
                 </div>
               </div>
+
+              <div className="flex items-center justify-between">
+                <label htmlFor="map-in-view" className="text-sm font-medium">只显示当前视野</label>
+                <Switch id="map-in-view" checked={inViewOnly} onCheckedChange={setInViewOnly} />
+              </div>
+
+              <div className="flex items-center justify-between">
+                <label htmlFor="map-heatmap" className="text-sm font-medium">显示热力图</label>
+                <Switch id="map-heatmap" checked={showHeatmap} onCheckedChange={setShowHeatmap} />
+              </div>
+
+              {nearby && (
+                <div className="flex items-center justify-between text-sm">
+                  <span className="truncate">「{nearby.title}」附近 {NEARBY_RADIUS_KM} 公里</span>
+                  <Button variant="link" size="sm" onClick={() => setNearby(null)}>
+                    清除
+                  </Button>
+                </div>
+              )}
             </CardContent>
           </Card>
 
@@ -328,40 +444,69 @@ export default function MapExploration() {
               </CardTitle>
             </CardHeader>
             <CardContent>
+              {error && <div className="text-sm text-destructive mb-2">{error}</div>}
               <div className="space-y-2 max-h-80 overflow-y-auto">
-                {mockArtifactLocations
-                  .filter(artifact => {
-                    const matchesSearch = artifact.title.toLowerCase().includes(searchQuery.toLowerCase()) ||
-                                     artifact.location.toLowerCase().includes(searchQuery.toLowerCase());
-                    const matchesCategory = filterCategory === 'all' || artifact.category === filterCategory;
-                    return matchesSearch && matchesCategory;
-                  })
-                  .map(artifact => (
-                    <div
-                      key={artifact.id}
-                      className={`p-3 rounded-lg border cursor-pointer transition-colors hover:bg-gray-50 ${
-                        selectedArtifact?.id === artifact.id ? 'bg-blue-50 border-blue-200' : ''
-                      }`}
-                      onClick={() => handleLocateArtifact(artifact)}
-                    >
-                      <div className="flex items-start justify-between">
-                        <div className="flex-1">
-                          <h4 className="font-medium text-sm">{artifact.title}</h4>
-                          <p className="text-xs text-gray-500 mt-1">{artifact.location}</p>
-                          <div className="flex gap-1 mt-2">
-                            <Badge variant="secondary" className="text-xs">
-                              {artifact.category}
-                            </Badge>
-                            <Badge variant="outline" className="text-xs">
-                              {artifact.dynasty}
-                            </Badge>
-                          </div>
+                {artifacts.map(artifact => (
+                  <div
+                    key={artifact.id}
+                    className={`p-3 rounded-lg border cursor-pointer transition-colors hover:bg-gray-50 ${
+                      selectedArtifact?.id === artifact.id ? 'bg-blue-50 border-blue-200' : ''
+                    }`}
+                    onClick={() => handleLocateArtifact(artifact)}
+                  >
+                    <div className="flex items-start justify-between">
+                      <div className="flex-1">
+                        <h4 className="font-medium text-sm">{artifact.title}</h4>
+                        <p className="text-xs text-gray-500 mt-1">
+                          {artifact.discovery_location || artifact.location}
+                          {artifact.location_uncertainty_m !== null && ` · ${formatUncertainty(artifact.location_uncertainty_m)}`}
+                          {artifact.distance_km !== null && ` · ${artifact.distance_km.toFixed(1)} 公里`}
+                        </p>
+                        <div className="flex gap-1 mt-2">
+                          <Badge variant="secondary" className="text-xs">
+                            {categoryLabel(artifact.category)}
+                          </Badge>
+                          <Badge variant="outline" className="text-xs">
+                            {artifact.dynasty}
+                          </Badge>
                         </div>
-                        <Navigation className="h-4 w-4 text-gray-400 flex-shrink-0 mt-1" />
                       </div>
+                      <Navigation className="h-4 w-4 text-gray-400 flex-shrink-0 mt-1" />
                     </div>
-                  ))}
+                    {selectedArtifact?.id === artifact.id && (
+                      <div className="flex gap-2 mt-2">
+                        <Button
+                          size="sm"
+                          variant="outline"
+                          onClick={(e) => {
+                            e.stopPropagation();
+                            setDetailArtifact(artifact);
+                          }}
+                        >
+                          查看详情
+                        </Button>
+                        <Button
+                          size="sm"
+                          variant="outline"
+                          onClick={(e) => {
+                            e.stopPropagation();
+                            setNearby(artifact);
+                          }}
+                        >
+                          <Locate className="h-3 w-3 mr-1" />
+                          附近文物
+                        </Button>
+                      </div>
+                    )}
+                  </div>
+                ))}
               </div>
+              {(truncated > 0 || unlocated > 0) && (
+                <div className="text-xs text-muted-foreground mt-2 space-y-1">
+                  {truncated > 0 && <div>另有 {truncated} 件未显示，请缩小范围</div>}
+                  {unlocated > 0 && <div>{unlocated} 件符合条件的文物尚无坐标</div>}
+                </div>
+              )}
             </CardContent>
           </Card>
 
@@ -383,11 +528,20 @@ export default function MapExploration() {
           <div className="flex items-center gap-2 text-sm text-gray-600">
             <Info className="h-4 w-4" />
             <span>
-               点击地图标记或文物列表查看详细信息。使用 OpenStreetMap 数据。
+               点击地图标记或文物列表查看详细信息。坐标按出土地点推断，圆圈表示误差范围。使用 OpenStreetMap 数据。
             </span>
           </div>
         </CardContent>
       </Card>
+
+      {detailArtifact && (
+        <ArtifactDialog
+          open={!!detailArtifact}
+          onOpenChange={(open) => !open && setDetailArtifact(null)}
+          artifact={detailArtifact}
+          onToggleFavorite={handleToggleFavorite}
+        />
+      )}
     </div>
   );
-}
\ No newline at end of file
+}
@@ -0,0 +1,108 @@
+// src/components/admin/ArtifactExportCard.tsx
+import { useState } from "react";
+import { Download } from "lucide-react";
+import { revealItemInDir } from "@tauri-apps/plugin-opener";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import { Alert, AlertDescription } from "@/components/ui/alert";
+import { artifactApi, getErrorMessage } from "@/lib/api";
+import { ExportFormat, ExportResult } from "@/types";
+
+const formats: Array<{ value: ExportFormat; label: string }> = [
+  { value: "csv", label: "CSV" },
+  { value: "jsonl", label: "JSON Lines" },
+  { value: "dublin_core", label: "Dublin Core XML" },
+];
+
+export function ArtifactExportCard() {
+  const [format, setFormat] = useState<ExportFormat>("csv");
+  // 为空时导出全部文物
+  const [query, setQuery] = useState("");
+  const [includeImages, setIncludeImages] = useState(false);
+  const [result, setResult] = useState<ExportResult | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [exporting, setExporting] = useState(false);
+
+  const handleExport = async () => {
+    try {
+      setExporting(true);
+      setError(null);
+      setResult(
+        await artifactApi.exportArtifacts({
+          format,
+          params: query.trim() ? { query: query.trim() } : undefined,
+          include_images: includeImages,
+        })
+      );
+    } catch (err) {
+      setResult(null);
+      setError(getErrorMessage(err, "导出失败"));
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Download className="h-5 w-5" />
+          导出目录
+        </CardTitle>
+        <CardDescription>导出到下载目录，可把图片一起打包为 zip</CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="flex gap-2">
+          {formats.map((f) => (
+            <Button
+              key={f.value}
+              variant={format === f.value ? "default" : "outline"}
+              size="sm"
+              className="flex-1"
+              onClick={() => setFormat(f.value)}
+            >
+              {f.label}
+            </Button>
+          ))}
+        </div>
+        <div className="space-y-1">
+          <Label htmlFor="export-query">筛选关键词（留空导出全部）</Label>
+          <Input id="export-query" value={query} onChange={(e) => setQuery(e.target.value)} />
+        </div>
+        <div className="flex items-center justify-between">
+          <Label htmlFor="export-images">打包图片</Label>
+          <Switch id="export-images" checked={includeImages} onCheckedChange={setIncludeImages} />
+        </div>
+        <Button onClick={handleExport} disabled={exporting}>
+          {exporting ? "导出中..." : "导出"}
+        </Button>
+
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+
+        {result && (
+          <div className="text-sm space-y-1">
+            <div>
+              已导出 {result.count} 件文物{includeImages && `，${result.images} 张图片`}
+            </div>
+            {result.missing_images.length > 0 && (
+              <div className="text-destructive">未找到的图片：{result.missing_images.join("、")}</div>
+            )}
+            <div className="flex items-center gap-2">
+              <span className="font-mono text-xs truncate">{result.path}</span>
+              <Button variant="link" size="sm" onClick={() => revealItemInDir(result.path).catch(console.error)}>
+                在文件夹中显示
+              </Button>
+            </div>
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
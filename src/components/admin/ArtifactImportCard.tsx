@@ -0,0 +1,124 @@
+// src/components/admin/ArtifactImportCard.tsx
+import { useRef, useState } from "react";
+import { Upload } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Badge } from "@/components/ui/badge";
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
+import { Alert, AlertDescription } from "@/components/ui/alert";
+import { artifactApi, getErrorMessage } from "@/lib/api";
+import { ImportFormat, ImportReport } from "@/types";
+
+interface ArtifactImportCardProps {
+  // 导入写入数据库后刷新列表
+  onImported?: () => void;
+}
+
+export function ArtifactImportCard({ onImported }: ArtifactImportCardProps) {
+  const fileInputRef = useRef<HTMLInputElement>(null);
+  const [file, setFile] = useState<{ name: string; format: ImportFormat; content: string } | null>(null);
+  const [report, setReport] = useState<ImportReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [running, setRunning] = useState(false);
+
+  const run = async (dryRun: boolean, target = file) => {
+    if (!target) return;
+    try {
+      setRunning(true);
+      setError(null);
+      const result = await artifactApi.importArtifacts(target.format, target.content, dryRun);
+      setReport(result);
+      if (result.committed) onImported?.();
+    } catch (err) {
+      setReport(null);
+      setError(getErrorMessage(err, "导入失败"));
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  // 选择文件后立即预览
+  const handleFileChange = async (e: React.ChangeEvent<HTMLInputElement>) => {
+    const selected = e.target.files?.[0];
+    e.target.value = "";
+    if (!selected) return;
+    const format: ImportFormat = selected.name.toLowerCase().endsWith(".json") ? "json" : "csv";
+    const next = { name: selected.name, format, content: await selected.text() };
+    setFile(next);
+    await run(true, next);
+  };
+
+  const canImport = !!report && report.dry_run && report.errors.length === 0 && report.rows.length > 0;
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Upload className="h-5 w-5" />
+          批量导入
+        </CardTitle>
+        <CardDescription>
+          支持 CSV（首行为表头）或 JSON 对象数组，按藏品编号（accession_number）新增或更新；存在错误行时不会写入任何数据
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="flex items-center gap-2">
+          <input
+            ref={fileInputRef}
+            type="file"
+            accept=".csv,.json"
+            className="hidden"
+            onChange={handleFileChange}
+          />
+          <Button variant="outline" onClick={() => fileInputRef.current?.click()} disabled={running}>
+            选择文件
+          </Button>
+          {file && <span className="text-sm text-muted-foreground">{file.name}</span>}
+          <Button className="ml-auto" onClick={() => run(false)} disabled={!canImport || running}>
+            {running ? "处理中..." : "确认导入"}
+          </Button>
+        </div>
+
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+
+        {report && (
+          <div className="space-y-2 text-sm">
+            <div>
+              {report.committed ? "已导入" : "预览"}：共 {report.total} 行，新增 {report.created}，更新 {report.updated}
+              {report.errors.length > 0 && <span className="text-destructive">，错误 {report.errors.length}</span>}
+            </div>
+            {report.ignored_columns.length > 0 && (
+              <div className="text-muted-foreground">已忽略的列：{report.ignored_columns.join("、")}</div>
+            )}
+            {report.errors.length > 0 && (
+              <div className="max-h-48 overflow-auto border rounded-md p-2 space-y-1">
+                {report.errors.map((e, index) => (
+                  <div key={index} className="text-destructive">
+                    第 {e.row} 行{e.field ? `（${e.field}）` : ""}：{e.message}
+                  </div>
+                ))}
+              </div>
+            )}
+            {report.rows.length > 0 && (
+              <div className="max-h-64 overflow-auto border rounded-md p-2 space-y-1">
+                {report.rows.map((r) => (
+                  <div key={r.row} className="flex items-center gap-2">
+                    <span className="text-muted-foreground w-12">#{r.row}</span>
+                    <Badge variant={r.action === "create" ? "secondary" : "outline"}>
+                      {r.action === "create" ? "新增" : "更新"}
+                    </Badge>
+                    <span className="font-mono text-xs">{r.accession_number}</span>
+                    <span className="truncate">{r.title}</span>
+                  </div>
+                ))}
+              </div>
+            )}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,183 @@
+// src/components/admin/ArtifactMediaCard.tsx
+import { useEffect, useRef, useState } from "react";
+import { ArrowDown, ArrowUp, Images, Star, Trash2, Upload } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
+import { Input } from "@/components/ui/input";
+import { Badge } from "@/components/ui/badge";
+import { Alert, AlertDescription } from "@/components/ui/alert";
+import { artifactApi, getErrorMessage, mediaApi } from "@/lib/api";
+import { ArtifactMedia, MediaType } from "@/types";
+
+const mediaTypes: Array<{ value: MediaType; label: string }> = [
+  { value: "image", label: "图片" },
+  { value: "model_3d", label: "3D 模型" },
+  { value: "audio", label: "语音导览" },
+  { value: "video", label: "视频" },
+  { value: "document", label: "文档" },
+];
+
+const typeLabel = (type: MediaType) => mediaTypes.find((t) => t.value === type)?.label ?? type;
+
+interface ArtifactMediaCardProps {
+  artifactId: number;
+  // 媒体变化后回传最新列表，主图会同步到文物的 image_path
+  onChanged?: (media: ArtifactMedia[]) => void;
+}
+
+export function ArtifactMediaCard({ artifactId, onChanged }: ArtifactMediaCardProps) {
+  const [media, setMedia] = useState<ArtifactMedia[]>([]);
+  const [mediaType, setMediaType] = useState<MediaType>("image");
+  const [path, setPath] = useState("");
+  const [caption, setCaption] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+  const fileInputRef = useRef<HTMLInputElement>(null);
+
+  useEffect(() => {
+    artifactApi
+      .getArtifactById(artifactId)
+      .then((detail) => setMedia(detail?.media ?? []))
+      .catch((err) => setError(getErrorMessage(err, "加载媒体失败")));
+  }, [artifactId]);
+
+  const run = async (action: () => Promise<ArtifactMedia[]>, fallback: string) => {
+    try {
+      setBusy(true);
+      setError(null);
+      const result = await action();
+      setMedia(result);
+      onChanged?.(result);
+    } catch (err) {
+      setError(getErrorMessage(err, fallback));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleAdd = () =>
+    run(async () => {
+      const result = await mediaApi.add({ artifact_id: artifactId, media_type: mediaType, path, caption });
+      setPath("");
+      setCaption("");
+      return result;
+    }, "添加失败");
+
+  // 上传的图片直接添加到该文物
+  const handleUpload = (e: React.ChangeEvent<HTMLInputElement>) => {
+    const file = e.target.files?.[0];
+    e.target.value = "";
+    if (!file) return;
+    run(async () => {
+      const uploaded = await mediaApi.upload(file, { artifactId, caption });
+      setCaption("");
+      return uploaded.media;
+    }, "上传失败");
+  };
+
+  const handleMove = (index: number, offset: number) => {
+    const ids = media.map((m) => m.id);
+    [ids[index], ids[index + offset]] = [ids[index + offset], ids[index]];
+    run(() => mediaApi.reorder(artifactId, ids), "调整顺序失败");
+  };
+
+  const handleCaption = (item: ArtifactMedia, value: string) => {
+    if (value === item.caption) return;
+    run(() => mediaApi.update(item.id, { caption: value }), "保存说明失败");
+  };
+
+  const handleDelete = (item: ArtifactMedia) => {
+    if (!confirm(`确定要移除 ${item.path} 吗？（不会删除文件）`)) return;
+    run(() => mediaApi.delete(item.id), "删除失败");
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Images className="h-5 w-5" />
+          图片与媒体
+        </CardTitle>
+        <CardDescription>每种类型的主媒体排在展示首位，主图同时用作文物卡片图片</CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+
+        <div className="space-y-2">
+          {media.map((item, index) => (
+            <div key={item.id} className="flex items-center gap-2 border rounded-md p-2 text-sm">
+              <Badge variant="outline">{typeLabel(item.media_type)}</Badge>
+              <span className="font-mono text-xs truncate w-40" title={item.path}>{item.path}</span>
+              <Input
+                key={`${item.id}-${item.caption}`}
+                defaultValue={item.caption}
+                placeholder="说明，如：背面、铭文拓片"
+                className="h-8 flex-1"
+                onBlur={(e) => handleCaption(item, e.target.value.trim())}
+              />
+              <Button
+                variant={item.is_primary ? "default" : "ghost"}
+                size="icon"
+                title={item.is_primary ? "主媒体" : "设为主媒体"}
+                disabled={busy || item.is_primary}
+                onClick={() => run(() => mediaApi.update(item.id, { is_primary: true }), "设置失败")}
+              >
+                <Star className="h-4 w-4" />
+              </Button>
+              <Button variant="ghost" size="icon" disabled={busy || index === 0} onClick={() => handleMove(index, -1)}>
+                <ArrowUp className="h-4 w-4" />
+              </Button>
+              <Button
+                variant="ghost"
+                size="icon"
+                disabled={busy || index === media.length - 1}
+                onClick={() => handleMove(index, 1)}
+              >
+                <ArrowDown className="h-4 w-4" />
+              </Button>
+              <Button variant="ghost" size="icon" disabled={busy} onClick={() => handleDelete(item)}>
+                <Trash2 className="h-4 w-4" />
+              </Button>
+            </div>
+          ))}
+        </div>
+
+        <div className="flex gap-2">
+          {mediaTypes.map((t) => (
+            <Button
+              key={t.value}
+              variant={mediaType === t.value ? "default" : "outline"}
+              size="sm"
+              className="flex-1"
+              onClick={() => setMediaType(t.value)}
+            >
+              {t.label}
+            </Button>
+          ))}
+        </div>
+        <div className="flex gap-2">
+          <Input placeholder="文件名" value={path} onChange={(e) => setPath(e.target.value)} />
+          <Input placeholder="说明（可选）" value={caption} onChange={(e) => setCaption(e.target.value)} />
+          <Button onClick={handleAdd} disabled={busy || !path.trim()}>
+            添加
+          </Button>
+          <Button variant="outline" onClick={() => fileInputRef.current?.click()} disabled={busy}>
+            <Upload className="h-4 w-4 mr-2" />
+            上传图片
+          </Button>
+          <input
+            type="file"
+            ref={fileInputRef}
+            onChange={handleUpload}
+            accept="image/jpeg,image/png"
+            className="hidden"
+          />
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
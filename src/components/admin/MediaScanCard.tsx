@@ -0,0 +1,129 @@
+// src/components/admin/MediaScanCard.tsx
+import { useState } from "react";
+import { ScanSearch } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import { Alert, AlertDescription } from "@/components/ui/alert";
+import { getErrorMessage, maintenanceApi } from "@/lib/api";
+import { MediaScanReport, ScannedImageFile } from "@/types";
+
+interface MediaScanCardProps {
+  // 修复后刷新文物列表
+  onFixed?: () => void;
+}
+
+const formatSize = (size: number) => `${(size / 1024 / 1024).toFixed(1)} MB`;
+
+const describeFile = (file: ScannedImageFile) =>
+  `${file.name}（${file.location === "stored" ? "上传" : "内置"}，${formatSize(file.size)}` +
+  (file.width && file.height ? `，${file.width}×${file.height}` : "") +
+  "）";
+
+function FileList({ title, items }: { title: string; items: string[] }) {
+  if (items.length === 0) return null;
+  return (
+    <div className="space-y-1">
+      <div className="font-medium">
+        {title}（{items.length}）
+      </div>
+      <ul className="max-h-40 overflow-y-auto text-xs text-muted-foreground space-y-0.5">
+        {items.map((item, index) => (
+          <li key={index} className="break-all">
+            {item}
+          </li>
+        ))}
+      </ul>
+    </div>
+  );
+}
+
+export function MediaScanCard({ onFixed }: MediaScanCardProps) {
+  const [fix, setFix] = useState(false);
+  const [quarantine, setQuarantine] = useState(false);
+  const [report, setReport] = useState<MediaScanReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [scanning, setScanning] = useState(false);
+
+  const handleScan = async () => {
+    if ((fix || quarantine) && !confirm("修复和隔离会修改媒体记录或移动文件，确定继续吗？")) return;
+    try {
+      setScanning(true);
+      setError(null);
+      const result = await maintenanceApi.scanMedia({ fix, quarantine });
+      setReport(result);
+      if (result.actions.length > 0) onFixed?.();
+    } catch (err) {
+      setReport(null);
+      setError(getErrorMessage(err, "扫描失败"));
+    } finally {
+      setScanning(false);
+    }
+  };
+
+  const clean =
+    report &&
+    report.missing.length === 0 &&
+    report.orphans.length === 0 &&
+    report.duplicates.length === 0 &&
+    report.oversized.length === 0;
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <ScanSearch className="h-5 w-5" />
+          媒体检查
+        </CardTitle>
+        <CardDescription>检查缺失的媒体文件、未被引用的图片、重复及过大的图片</CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="flex items-center justify-between">
+          <Label htmlFor="scan-fix">修复记录（删除缺失的媒体、合并重复图片）</Label>
+          <Switch id="scan-fix" checked={fix} onCheckedChange={setFix} />
+        </div>
+        <div className="flex items-center justify-between">
+          <Label htmlFor="scan-quarantine">隔离未引用的图片</Label>
+          <Switch id="scan-quarantine" checked={quarantine} onCheckedChange={setQuarantine} />
+        </div>
+        <Button onClick={handleScan} disabled={scanning}>
+          {scanning ? "扫描中..." : "开始扫描"}
+        </Button>
+
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+
+        {report && (
+          <div className="text-sm space-y-2">
+            <div>
+              共 {report.media_count} 条媒体记录，{report.file_count} 个图片文件
+              {clean && "，未发现问题"}
+            </div>
+            <FileList
+              title="文件缺失的媒体"
+              items={report.missing.map(
+                (m) => `《${m.title}》${m.is_primary ? "主" : ""}${m.media_type === "image" ? "图片" : "媒体"}：${m.path}`
+              )}
+            />
+            <FileList title="未被引用的图片" items={report.orphans.map(describeFile)} />
+            <FileList
+              title="内容重复的图片"
+              items={report.duplicates.map((group) => group.files.map((f) => f.name).join(" = "))}
+            />
+            <FileList title="过大的图片" items={report.oversized.map(describeFile)} />
+            <FileList title="已执行的操作" items={report.actions} />
+            {report.quarantine_dir && (
+              <div className="text-xs">
+                隔离目录：<span className="font-mono break-all">{report.quarantine_dir}</span>
+              </div>
+            )}
+          </div>
+        )}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,277 @@
+// src/components/admin/VocabularyCard.tsx
+import { useState } from "react";
+import { ListTree } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
+import { Input } from "@/components/ui/input";
+import { Alert, AlertDescription } from "@/components/ui/alert";
+import { getErrorMessage, vocabularyApi } from "@/lib/api";
+import { useVocabulary } from "@/hooks/useVocabulary";
+import { Vocabulary, VocabularyTermWithUsage } from "@/types";
+
+interface VocabularyCardProps {
+  // 修改代码、合并词条会改动文物，每次操作后刷新文物列表和表单中的词表
+  onChanged?: () => void;
+}
+
+const VOCABULARIES: Array<{ value: Vocabulary; label: string }> = [
+  { value: "category", label: "类别" },
+  { value: "material", label: "材质" },
+  { value: "collection", label: "收藏机构" },
+];
+
+const emptyForm = { code: "", label_zh: "", label_en: "", parent_id: "", sort_order: "0" };
+
+const selectClass = "h-9 rounded-md border border-input bg-transparent px-3 text-sm";
+
+// 按上下级关系排列，返回词条及其层级
+function flattenTree(terms: VocabularyTermWithUsage[]) {
+  const ids = new Set(terms.map((t) => t.id));
+  const result: Array<{ term: VocabularyTermWithUsage; depth: number }> = [];
+  const visit = (parentId: number | null, depth: number) => {
+    terms
+      .filter((t) => (parentId === null ? t.parent_id === null || !ids.has(t.parent_id) : t.parent_id === parentId))
+      .forEach((term) => {
+        result.push({ term, depth });
+        visit(term.id, depth + 1);
+      });
+  };
+  visit(null, 0);
+  return result;
+}
+
+export function VocabularyCard({ onChanged }: VocabularyCardProps) {
+  const { termsOf, error: loadError, reload } = useVocabulary();
+  const [vocabulary, setVocabulary] = useState<Vocabulary>("category");
+  const [form, setForm] = useState(emptyForm);
+  const [editingId, setEditingId] = useState<number | null>(null);
+  const [mergeSource, setMergeSource] = useState("");
+  const [mergeTarget, setMergeTarget] = useState("");
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const terms = termsOf(vocabulary);
+  const tree = flattenTree(terms);
+  const describe = (term: VocabularyTermWithUsage) =>
+    term.code === term.label_zh ? term.label_zh : `${term.label_zh}（${term.code}）`;
+
+  const resetForm = () => {
+    setForm(emptyForm);
+    setEditingId(null);
+  };
+
+  const switchVocabulary = (next: Vocabulary) => {
+    setVocabulary(next);
+    resetForm();
+    setMergeSource("");
+    setMergeTarget("");
+  };
+
+  const run = async (action: () => Promise<string>) => {
+    try {
+      setBusy(true);
+      setError(null);
+      setMessage(await action());
+      await reload();
+      onChanged?.();
+    } catch (err) {
+      setMessage(null);
+      setError(getErrorMessage(err, "操作失败"));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const handleSubmit = (e: React.FormEvent) => {
+    e.preventDefault();
+    const input = {
+      vocabulary,
+      code: form.code,
+      label_zh: form.label_zh,
+      label_en: form.label_en,
+      parent_id: form.parent_id ? Number(form.parent_id) : null,
+      sort_order: Number(form.sort_order) || 0,
+    };
+    run(async () => {
+      if (editingId !== null) {
+        const term = await vocabularyApi.updateTerm(editingId, input);
+        resetForm();
+        return `已保存「${term.label_zh}」`;
+      }
+      const term = await vocabularyApi.createTerm(input);
+      resetForm();
+      return `已添加「${term.label_zh}」`;
+    });
+  };
+
+  const handleEdit = (term: VocabularyTermWithUsage) => {
+    setEditingId(term.id);
+    setForm({
+      code: term.code,
+      label_zh: term.label_zh,
+      label_en: term.label_en,
+      parent_id: term.parent_id === null ? "" : String(term.parent_id),
+      sort_order: String(term.sort_order),
+    });
+  };
+
+  const handleDelete = (term: VocabularyTermWithUsage) => {
+    if (!confirm(`确定删除「${term.label_zh}」吗？`)) return;
+    run(async () => {
+      await vocabularyApi.deleteTerm(term.id);
+      if (editingId === term.id) resetForm();
+      return `已删除「${term.label_zh}」`;
+    });
+  };
+
+  const handleMerge = () => {
+    const source = terms.find((t) => t.id === Number(mergeSource));
+    const target = terms.find((t) => t.id === Number(mergeTarget));
+    if (!source || !target) return;
+    if (!confirm(`把「${source.label_zh}」合并到「${target.label_zh}」？原词条将被删除。`)) return;
+    run(async () => {
+      const result = await vocabularyApi.mergeTerms(source.id, target.id);
+      setMergeSource("");
+      setMergeTarget("");
+      return `已合并到「${result.target.label_zh}」，${result.moved} 件文物已更新`;
+    });
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <ListTree className="h-5 w-5" />
+          词表管理
+        </CardTitle>
+        <CardDescription>维护类别、材质、收藏机构的词条及上下级关系，合并重复的词条</CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex gap-2">
+          {VOCABULARIES.map((v) => (
+            <Button
+              key={v.value}
+              size="sm"
+              variant={vocabulary === v.value ? "default" : "outline"}
+              onClick={() => switchVocabulary(v.value)}
+            >
+              {v.label}
+            </Button>
+          ))}
+        </div>
+
+        {(error || loadError) && (
+          <Alert variant="destructive">
+            <AlertDescription>{error || loadError}</AlertDescription>
+          </Alert>
+        )}
+        {message && (
+          <Alert>
+            <AlertDescription>{message}</AlertDescription>
+          </Alert>
+        )}
+
+        <ul className="max-h-72 overflow-y-auto divide-y text-sm">
+          {tree.map(({ term, depth }) => (
+            <li key={term.id} className="flex items-center justify-between py-1.5" style={{ paddingLeft: depth * 20 }}>
+              <div>
+                <span className="font-medium">{describe(term)}</span>
+                {term.label_en && <span className="ml-2 text-muted-foreground">{term.label_en}</span>}
+                <span className="ml-2 text-xs text-muted-foreground">{term.usage_count} 件</span>
+              </div>
+              <div className="flex gap-2">
+                <Button size="sm" variant="outline" onClick={() => handleEdit(term)} disabled={busy}>
+                  编辑
+                </Button>
+                <Button size="sm" variant="destructive" onClick={() => handleDelete(term)} disabled={busy}>
+                  删除
+                </Button>
+              </div>
+            </li>
+          ))}
+        </ul>
+
+        <form onSubmit={handleSubmit} className="grid grid-cols-1 md:grid-cols-2 gap-2">
+          <Input
+            placeholder="代码（写入文物的值）"
+            value={form.code}
+            onChange={(e) => setForm({ ...form, code: e.target.value })}
+            required
+          />
+          <Input
+            placeholder="中文名称"
+            value={form.label_zh}
+            onChange={(e) => setForm({ ...form, label_zh: e.target.value })}
+            required
+          />
+          <Input
+            placeholder="英文名称"
+            value={form.label_en}
+            onChange={(e) => setForm({ ...form, label_en: e.target.value })}
+          />
+          <div className="flex gap-2">
+            <select
+              className={`${selectClass} flex-1`}
+              value={form.parent_id}
+              onChange={(e) => setForm({ ...form, parent_id: e.target.value })}
+            >
+              <option value="">无上级词条</option>
+              {tree
+                .filter(({ term }) => term.id !== editingId)
+                .map(({ term, depth }) => (
+                  <option key={term.id} value={term.id}>
+                    {"　".repeat(depth)}
+                    {describe(term)}
+                  </option>
+                ))}
+            </select>
+            <Input
+              type="number"
+              className="w-24"
+              placeholder="排序"
+              value={form.sort_order}
+              onChange={(e) => setForm({ ...form, sort_order: e.target.value })}
+            />
+          </div>
+          <div className="flex gap-2 md:col-span-2">
+            <Button type="submit" disabled={busy}>
+              {editingId !== null ? "保存词条" : "添加词条"}
+            </Button>
+            {editingId !== null && (
+              <Button type="button" variant="outline" onClick={resetForm}>
+                取消编辑
+              </Button>
+            )}
+          </div>
+        </form>
+
+        <div className="flex flex-wrap items-center gap-2 text-sm">
+          <span>合并</span>
+          <select className={selectClass} value={mergeSource} onChange={(e) => setMergeSource(e.target.value)}>
+            <option value="">选择重复的词条</option>
+            {tree.map(({ term }) => (
+              <option key={term.id} value={term.id}>
+                {describe(term)}（{term.usage_count} 件）
+              </option>
+            ))}
+          </select>
+          <span>到</span>
+          <select className={selectClass} value={mergeTarget} onChange={(e) => setMergeTarget(e.target.value)}>
+            <option value="">选择保留的词条</option>
+            {tree
+              .filter(({ term }) => String(term.id) !== mergeSource)
+              .map(({ term }) => (
+                <option key={term.id} value={term.id}>
+                  {describe(term)}
+                </option>
+              ))}
+          </select>
+          <Button variant="outline" onClick={handleMerge} disabled={busy || !mergeSource || !mergeTarget}>
+            合并
+          </Button>
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
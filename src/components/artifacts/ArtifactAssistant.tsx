@@ -0,0 +1,160 @@
+// src/components/artifacts/ArtifactAssistant.tsx
+import { useRef, useState } from "react";
+import { Button } from "../ui/button";
+import { Input } from "../ui/input";
+import { Bot, Send, Square, Baby, Scale, ScrollText } from "lucide-react";
+import { aiApi, getErrorMessage } from "../../lib/api";
+import { ArtifactTask, ArtifactWithFavorite, Citation } from "../../types";
+
+interface ArtifactAssistantProps {
+  artifact: ArtifactWithFavorite;
+  // 点击回答中引用的相关藏品
+  onOpenCitation?: (artifactId: number) => void;
+}
+
+interface Exchange {
+  question: string;
+  answer: string;
+  citations: Citation[];
+}
+
+const tasks: { task: ArtifactTask; label: string; icon: typeof Baby }[] = [
+  { task: "explain_for_children", label: "讲给孩子听", icon: Baby },
+  { task: "compare_similar", label: "与相似藏品比较", icon: Scale },
+  { task: "historical_context", label: "历史背景", icon: ScrollText },
+];
+
+// 去掉回答中的 [#id] 引用标注
+const stripCitationMarkers = (content: string) => content.replace(/\s?\[#\d+\]/g, "");
+
+export function ArtifactAssistant({ artifact, onOpenCitation }: ArtifactAssistantProps) {
+  const [exchanges, setExchanges] = useState<Exchange[]>([]);
+  const [input, setInput] = useState("");
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const activeRequestRef = useRef<string | null>(null);
+
+  const ask = async (question: string, task?: ArtifactTask) => {
+    const label = question || tasks.find((t) => t.task === task)?.label || "";
+    const history = exchanges.slice(-3).flatMap((e) => [
+      { role: "user", content: e.question },
+      { role: "assistant", content: e.answer },
+    ]);
+
+    setExchanges((prev) => [...prev, { question: label, answer: "", citations: [] }]);
+    setInput("");
+    setError(null);
+    setIsLoading(true);
+
+    const requestId = crypto.randomUUID();
+    activeRequestRef.current = requestId;
+
+    const updateLast = (update: (exchange: Exchange) => Exchange) =>
+      setExchanges((prev) => prev.map((e, i) => (i === prev.length - 1 ? update(e) : e)));
+
+    try {
+      const { citations } = await aiApi.askAboutArtifact(
+        requestId,
+        {
+          artifact_id: artifact.id,
+          question: question || undefined,
+          task,
+          conversation_history: history,
+        },
+        (delta) => updateLast((e) => ({ ...e, answer: e.answer + delta }))
+      );
+      // 当前文物本身不作为引用链接展示
+      updateLast((e) => ({ ...e, citations: citations.filter((c) => c.artifact_id !== artifact.id) }));
+    } catch (err) {
+      setError(getErrorMessage(err, "AI 助手暂时无法回答"));
+    } finally {
+      activeRequestRef.current = null;
+      setIsLoading(false);
+    }
+  };
+
+  const handleSend = () => {
+    if (!input.trim() || isLoading) return;
+    ask(input.trim());
+  };
+
+  const handleStop = () => {
+    if (activeRequestRef.current) {
+      aiApi.cancelChat(activeRequestRef.current).catch(console.error);
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <h3 className="font-semibold text-lg flex items-center gap-2">
+        <Bot className="h-5 w-5" />
+        问问文博助手
+      </h3>
+
+      <div className="flex flex-wrap gap-2">
+        {tasks.map(({ task, label, icon: Icon }) => (
+          <Button
+            key={task}
+            variant="outline"
+            size="sm"
+            disabled={isLoading}
+            onClick={() => ask("", task)}
+          >
+            <Icon className="h-4 w-4 mr-1" />
+            {label}
+          </Button>
+        ))}
+      </div>
+
+      {exchanges.map((exchange, index) => (
+        <div key={index} className="space-y-2 text-sm">
+          <div className="font-medium">{exchange.question}</div>
+          <div className="rounded-lg bg-muted p-3 whitespace-pre-wrap">
+            {exchange.answer ? stripCitationMarkers(exchange.answer) : isLoading ? "思考中…" : "（已停止生成）"}
+          </div>
+          {exchange.citations.length > 0 && (
+            <div className="flex flex-wrap gap-1">
+              {exchange.citations.map((citation) => (
+                <Button
+                  key={citation.artifact_id}
+                  variant="outline"
+                  size="sm"
+                  className="h-6 px-2 text-xs"
+                  onClick={() => onOpenCitation?.(citation.artifact_id)}
+                >
+                  {citation.title}
+                </Button>
+              ))}
+            </div>
+          )}
+        </div>
+      ))}
+
+      {error && <p className="text-sm text-destructive">{error}</p>}
+
+      <div className="flex gap-2">
+        <Input
+          value={input}
+          onChange={(e) => setInput(e.target.value)}
+          onKeyDown={(e) => {
+            if (e.key === "Enter" && !e.shiftKey) {
+              e.preventDefault();
+              handleSend();
+            }
+          }}
+          placeholder={`关于「${artifact.title}」的问题…`}
+          disabled={isLoading}
+        />
+        {isLoading ? (
+          <Button onClick={handleStop} variant="outline" size="icon" title="停止生成">
+            <Square className="h-4 w-4" />
+          </Button>
+        ) : (
+          <Button onClick={handleSend} disabled={!input.trim()} size="icon">
+            <Send className="h-4 w-4" />
+          </Button>
+        )}
+      </div>
+    </div>
+  );
+}
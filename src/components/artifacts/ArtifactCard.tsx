@@ -5,6 +5,7 @@ import { Badge } from "../ui/badge";
 import { Heart, ZoomIn, Calendar, MapPin  } from "lucide-react";
 import { ArtifactWithFavorite } from "../../types";
 import { historyApi } from "../../lib/api";
+import { artifactImageUrl } from "../../lib/utils";
 
 interface ArtifactCardProps {
   artifact: ArtifactWithFavorite;
@@ -51,7 +52,7 @@ export function ArtifactCard({
         <div className="md:flex">
           <div className="md:w-2/5 h-64 md:h-auto">
             <img
-              src={`/images/${artifact.image_path}?t=${Date.now()}`}
+              src={artifactImageUrl(artifact)}
               alt={artifact.title}
               className="w-full h-full object-cover"
             />
@@ -109,7 +110,7 @@ export function ArtifactCard({
     >
       <div className="h-48 overflow-hidden">
         <img
-          src={`/images/${artifact.image_path}?t=${Date.now()}`}
+          src={artifactImageUrl(artifact)}
           alt={artifact.title}
           className="w-full h-full object-cover hover:scale-105 transition-transform duration-300"
         />
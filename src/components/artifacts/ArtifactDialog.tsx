@@ -16,9 +16,15 @@ import {
   Ruler, 
   Box, 
   Building, 
-  Share2
+  Share2,
+  ZoomIn
 } from "lucide-react";
 import { ArtifactWithFavorite } from "../../types";
+import { formatYearRange } from "@/lib/utils";
+import { ArtifactAssistant } from "./ArtifactAssistant";
+import { IiifViewer } from "./IiifViewer";
+import { useVocabulary } from "@/hooks/useVocabulary";
+import { ArtifactMediaGallery } from "./ArtifactMediaGallery";
 import { useState } from "react";
 
 interface ArtifactDialogProps {
@@ -26,15 +32,20 @@ interface ArtifactDialogProps {
   open: boolean;
   onOpenChange: (open: boolean) => void;
   onToggleFavorite: (artifactId: number) => Promise<void>;
+  // 打开 AI 回答中引用的其他文物
+  onOpenArtifact?: (artifactId: number) => void;
 }
 
 export function ArtifactDialog({
   artifact,
   open,
   onOpenChange,
-  onToggleFavorite
+  onToggleFavorite,
+  onOpenArtifact
 }: ArtifactDialogProps) {
   const [isFavoriteLoading, setIsFavoriteLoading] = useState(false);
+  const [viewerOpen, setViewerOpen] = useState(false);
+  const { label } = useVocabulary();
 
   if (!artifact) return null;
   
@@ -84,14 +95,7 @@ export function ArtifactDialog({
           <div className="grid grid-cols-1 lg:grid-cols-2 gap-6 pb-6">
             {/* Image section */}
             <div className="space-y-4">
-              <div className="overflow-hidden rounded-lg border">
-                <img
-                  src={`/images/${artifact.image_path}?t=${Date.now()}`}
-                  alt={artifact.title}
-                  className="w-full h-auto object-contain"
-                  style={{ maxHeight: '350px' }}
-                />
-              </div>
+              <ArtifactMediaGallery artifact={artifact} />
               
               {/* Action buttons */}
               <div className="flex gap-2">
@@ -108,6 +112,13 @@ export function ArtifactDialog({
                   />
                   {artifact.is_favorite ? '已收藏' : '收藏'}
                 </Button>
+                <Button
+                  variant="outline"
+                  onClick={() => setViewerOpen(true)}
+                >
+                  <ZoomIn className="h-4 w-4 mr-2" />
+                  高清浏览
+                </Button>
                 <Button
                   variant="outline"
                   size="icon"
@@ -128,6 +139,11 @@ export function ArtifactDialog({
                     <Calendar className="h-4 w-4 text-muted-foreground" />
                     <span className="font-medium">年代:</span>
                     <span>{artifact.dynasty} · {artifact.period}</span>
+                    {artifact.date_from !== null && (
+                      <span className="text-muted-foreground">
+                        （{formatYearRange(artifact.date_from, artifact.date_to)}）
+                      </span>
+                    )}
                   </div>
                   <div className="flex items-center gap-2">
                     <MapPin className="h-4 w-4 text-muted-foreground" />
@@ -171,7 +187,7 @@ export function ArtifactDialog({
                 <h3 className="font-semibold text-lg">分类标签</h3>
                 <div className="flex flex-wrap gap-2">
                   <Badge variant="secondary">
-                    {getCategoryLabel(artifact.category as any)}
+                    {label('category', artifact.category)}
                   </Badge>
                   <Badge variant="outline">
                     {artifact.material}
@@ -191,25 +207,30 @@ export function ArtifactDialog({
                   <div>数据更新时间: {formatDate(artifact.updated_at)}</div>
                 </div>
               </div>
+
+              <ArtifactAssistant
+                key={artifact.id}
+                artifact={artifact}
+                onOpenCitation={onOpenArtifact}
+              />
             </div>
           </div>
         </ScrollArea>
       </DialogContent>
+
+      <Dialog open={viewerOpen} onOpenChange={setViewerOpen}>
+        <DialogContent className="max-w-6xl">
+          <DialogHeader>
+            <DialogTitle>{artifact.title}</DialogTitle>
+            <DialogDescription>滚轮缩放，拖动平移</DialogDescription>
+          </DialogHeader>
+          {viewerOpen && <IiifViewer artifactId={artifact.id} />}
+        </DialogContent>
+      </Dialog>
     </Dialog>
   );
 }
 
-function getCategoryLabel(category: string): string {
-  const labels: Record<string, string> = {
-    ceramics: '陶瓷器',
-    bronze: '青铜器',
-    jade: '玉器',
-    calligraphy: '书画',
-    sculpture: '雕塑'
-  };
-  return labels[category] || category;
-}
-
 function formatDate(dateString: string): string {
   return new Date(dateString).toLocaleDateString('zh-CN', {
     year: 'numeric',
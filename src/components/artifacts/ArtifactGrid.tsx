@@ -1,5 +1,6 @@
 // src/components/artifacts/ArtifactGrid.tsx
 import { ArtifactCard } from "./ArtifactCard";
+import { LoadMoreTrigger } from "./LoadMoreTrigger";
 import { Button } from "../ui/button";
 import { Alert, AlertDescription } from "../ui/alert";
 import { Loader2, SearchX, RefreshCw } from "lucide-react";
@@ -14,6 +15,11 @@ interface ArtifactGridProps {
   searchQuery: string;
   activeSection?: 'home' | 'favorites' | 'browsing';
   className?: string;
+  // 符合条件的文物总数，未提供时显示已加载的数量
+  total?: number;
+  hasMore?: boolean;
+  loadingMore?: boolean;
+  onLoadMore?: () => void;
 }
 
 export function ArtifactGrid({ 
@@ -24,7 +30,11 @@ export function ArtifactGrid({
   onToggleFavorite, 
   searchQuery, 
   activeSection, 
-  className 
+  className,
+  total,
+  hasMore = false,
+  loadingMore = false,
+  onLoadMore,
 }: ArtifactGridProps) {
   // 添加调试信息
   console.log('🎯 ArtifactGrid render:', { 
@@ -121,7 +131,7 @@ export function ArtifactGrid({
             )}
           </div>
           <div className="text-sm text-muted-foreground">
-            共找到 {artifacts.length} 件文物
+            共找到 {total ?? artifacts.length} 件文物
           </div>
         </div>
         
@@ -143,6 +153,10 @@ export function ArtifactGrid({
           />
         ))}
       </div>
+
+      {onLoadMore && (
+        <LoadMoreTrigger hasMore={hasMore} loading={loadingMore} onLoadMore={onLoadMore} />
+      )}
     </div>
   );
 }
\ No newline at end of file
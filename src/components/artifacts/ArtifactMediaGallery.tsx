@@ -0,0 +1,83 @@
+// src/components/artifacts/ArtifactMediaGallery.tsx
+import { useEffect, useState } from "react";
+import { Box, FileText } from "lucide-react";
+import { artifactApi } from "@/lib/api";
+import { artifactImageUrl, cn, mediaUrl } from "@/lib/utils";
+import { ArtifactMedia, ArtifactWithFavorite } from "@/types";
+
+interface ArtifactMediaGalleryProps {
+  artifact: ArtifactWithFavorite;
+}
+
+export function ArtifactMediaGallery({ artifact }: ArtifactMediaGalleryProps) {
+  const [media, setMedia] = useState<ArtifactMedia[]>([]);
+  const [selectedId, setSelectedId] = useState<number | null>(null);
+
+  useEffect(() => {
+    setMedia([]);
+    setSelectedId(null);
+    artifactApi
+      .getArtifactById(artifact.id)
+      .then((detail) => setMedia(detail?.media ?? []))
+      .catch((err) => console.error("Failed to load artifact media:", err));
+  }, [artifact.id]);
+
+  const images = media.filter((m) => m.media_type === "image");
+  const others = media.filter((m) => m.media_type !== "image");
+  const selected =
+    images.find((m) => m.id === selectedId) ?? images.find((m) => m.is_primary) ?? images[0];
+
+  return (
+    <div className="space-y-3">
+      <div className="overflow-hidden rounded-lg border">
+        <img
+          src={selected ? mediaUrl(selected, "medium") : artifactImageUrl(artifact)}
+          alt={selected?.caption || artifact.title}
+          className="w-full h-auto object-contain"
+          style={{ maxHeight: '350px' }}
+        />
+      </div>
+      {selected?.caption && (
+        <div className="text-xs text-muted-foreground text-center">{selected.caption}</div>
+      )}
+
+      {images.length > 1 && (
+        <div className="flex gap-2 overflow-x-auto pb-1">
+          {images.map((image) => (
+            <button
+              key={image.id}
+              type="button"
+              title={image.caption}
+              onClick={() => setSelectedId(image.id)}
+              className={cn(
+                "h-16 w-16 shrink-0 overflow-hidden rounded border",
+                image.id === selected?.id && "ring-2 ring-primary"
+              )}
+            >
+              <img src={mediaUrl(image, "thumb")} alt={image.caption} className="h-full w-full object-cover" />
+            </button>
+          ))}
+        </div>
+      )}
+
+      {others.map((item) => (
+        <div key={item.id} className="space-y-1 text-sm">
+          {item.caption && <div className="text-muted-foreground">{item.caption}</div>}
+          {item.media_type === "audio" && <audio controls src={mediaUrl(item)} className="w-full" />}
+          {item.media_type === "video" && <video controls src={mediaUrl(item)} className="w-full rounded" />}
+          {(item.media_type === "model_3d" || item.media_type === "document") && (
+            <a
+              href={mediaUrl(item)}
+              target="_blank"
+              rel="noreferrer"
+              className="flex items-center gap-2 text-primary hover:underline"
+            >
+              {item.media_type === "model_3d" ? <Box className="h-4 w-4" /> : <FileText className="h-4 w-4" />}
+              {item.media_type === "model_3d" ? "3D 模型" : "相关文档"}：{item.path}
+            </a>
+          )}
+        </div>
+      ))}
+    </div>
+  );
+}
@@ -1,5 +1,6 @@
 import { Heart } from "lucide-react";
 import { ArtifactWithFavorite } from "../../types";
+import { artifactImageUrl, PLACEHOLDER_IMAGE } from "../../lib/utils";
 import { useState, MouseEvent, KeyboardEvent } from "react";
 
 interface CompactArtifactCardProps {
@@ -36,10 +37,10 @@ export function CompactArtifactCard({
   };
 
   const getImageUrl = () => {
-    if (imageError || !artifact.image_path) {
-      return "/placeholder-image.jpg";
+    if (imageError) {
+      return PLACEHOLDER_IMAGE;
     }
-    return `/images/${artifact.image_path}?t=${Date.now()}`;
+    return artifactImageUrl(artifact, "thumb");
   };
 
   // 确保有默认值
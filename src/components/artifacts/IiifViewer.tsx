@@ -0,0 +1,224 @@
+// src/components/artifacts/IiifViewer.tsx
+import { useEffect, useRef, useState } from "react";
+import { Minus, Plus, Maximize } from "lucide-react";
+import { Button } from "../ui/button";
+import { Alert, AlertDescription } from "../ui/alert";
+import { iiifApi, getErrorMessage } from "@/lib/api";
+import { IiifCanvas, IiifImageInfo } from "@/types";
+
+interface IiifViewerProps {
+  artifactId: number;
+}
+
+// 视图状态：scale 为屏幕像素 / 原图像素，x、y 为原图左上角在容器中的位置
+interface View {
+  scale: number;
+  x: number;
+  y: number;
+}
+
+const MAX_SCALE = 4;
+
+function serviceOf(canvas: IiifCanvas): string {
+  return canvas.items[0].items[0].body.service[0].id;
+}
+
+function labelOf(canvas: IiifCanvas, index: number): string {
+  const label = canvas.label && Object.values(canvas.label)[0]?.[0];
+  return label || `图 ${index + 1}`;
+}
+
+export function IiifViewer({ artifactId }: IiifViewerProps) {
+  const containerRef = useRef<HTMLDivElement>(null);
+  const dragRef = useRef<{ x: number; y: number } | null>(null);
+  const [canvases, setCanvases] = useState<IiifCanvas[]>([]);
+  const [current, setCurrent] = useState(0);
+  const [info, setInfo] = useState<IiifImageInfo | null>(null);
+  const [view, setView] = useState<View | null>(null);
+  const [size, setSize] = useState({ width: 0, height: 0 });
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    iiifApi
+      .getManifest(artifactId)
+      .then((manifest) => setCanvases(manifest.items))
+      .catch((err) => setError(getErrorMessage(err, "加载图像清单失败")));
+  }, [artifactId]);
+
+  useEffect(() => {
+    const canvas = canvases[current];
+    if (!canvas) return;
+    setInfo(null);
+    fetch(`${serviceOf(canvas)}/info.json`)
+      .then((res) => {
+        if (!res.ok) throw new Error(`加载图像信息失败（${res.status}）`);
+        return res.json();
+      })
+      .then(setInfo)
+      .catch((err) => setError(getErrorMessage(err, "加载图像信息失败")));
+  }, [canvases, current]);
+
+  useEffect(() => {
+    const el = containerRef.current;
+    if (!el) return;
+    const observer = new ResizeObserver(() => setSize({ width: el.clientWidth, height: el.clientHeight }));
+    observer.observe(el);
+    return () => observer.disconnect();
+  }, []);
+
+  // 整图适配容器
+  const fitView = (): View | null => {
+    if (!info || !size.width || !size.height) return null;
+    const scale = Math.min(size.width / info.width, size.height / info.height);
+    return {
+      scale,
+      x: (size.width - info.width * scale) / 2,
+      y: (size.height - info.height * scale) / 2,
+    };
+  };
+
+  useEffect(() => {
+    setView(fitView());
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [info, size.width, size.height]);
+
+  // 以容器内某点为中心缩放
+  const zoomAt = (factor: number, cx: number, cy: number) => {
+    setView((v) => {
+      const fit = fitView();
+      if (!v || !fit) return v;
+      const scale = Math.min(Math.max(v.scale * factor, fit.scale), MAX_SCALE);
+      const ratio = scale / v.scale;
+      return { scale, x: cx - (cx - v.x) * ratio, y: cy - (cy - v.y) * ratio };
+    });
+  };
+
+  const handleWheel = (e: React.WheelEvent) => {
+    const rect = e.currentTarget.getBoundingClientRect();
+    zoomAt(e.deltaY < 0 ? 1.2 : 1 / 1.2, e.clientX - rect.left, e.clientY - rect.top);
+  };
+
+  const handlePointerDown = (e: React.PointerEvent) => {
+    e.currentTarget.setPointerCapture(e.pointerId);
+    dragRef.current = { x: e.clientX, y: e.clientY };
+  };
+
+  const handlePointerMove = (e: React.PointerEvent) => {
+    const last = dragRef.current;
+    if (!last) return;
+    dragRef.current = { x: e.clientX, y: e.clientY };
+    setView((v) => v && { ...v, x: v.x + e.clientX - last.x, y: v.y + e.clientY - last.y });
+  };
+
+  const handlePointerUp = () => {
+    dragRef.current = null;
+  };
+
+  // 按当前缩放选择切片层级，只请求可见区域内的切片
+  const renderTiles = () => {
+    if (!info || !view) return null;
+    const tiles = info.tiles[0];
+    const factors = [...tiles.scaleFactors].sort((a, b) => a - b);
+    const factor = factors.filter((f) => f <= 1 / view.scale).pop() ?? factors[0];
+    const span = tiles.width * factor;
+
+    const left = Math.max(0, Math.floor(-view.x / view.scale / span));
+    const top = Math.max(0, Math.floor(-view.y / view.scale / span));
+    const right = Math.min(Math.ceil(info.width / span), Math.ceil((size.width - view.x) / view.scale / span));
+    const bottom = Math.min(Math.ceil(info.height / span), Math.ceil((size.height - view.y) / view.scale / span));
+
+    const result = [];
+    for (let row = top; row < bottom; row++) {
+      for (let col = left; col < right; col++) {
+        const x = col * span;
+        const y = row * span;
+        const w = Math.min(span, info.width - x);
+        const h = Math.min(span, info.height - y);
+        const url = `${info.id}/${x},${y},${w},${h}/${Math.ceil(w / factor)},${Math.ceil(h / factor)}/0/default.jpg`;
+        result.push(
+          <img
+            key={`${factor}-${col}-${row}`}
+            src={url}
+            alt=""
+            draggable={false}
+            className="absolute max-w-none"
+            style={{
+              left: view.x + x * view.scale,
+              top: view.y + y * view.scale,
+              width: w * view.scale,
+              height: h * view.scale,
+            }}
+          />
+        );
+      }
+    }
+    return result;
+  };
+
+  if (error) {
+    return (
+      <Alert variant="destructive">
+        <AlertDescription>{error}</AlertDescription>
+      </Alert>
+    );
+  }
+
+  // 小图直接取原尺寸，避免请求放大
+  const preview = info && Math.max(info.width, info.height) > 1024 ? "!1024,1024" : "max";
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2">
+        {canvases.length > 1 &&
+          canvases.map((canvas, index) => (
+            <Button
+              key={canvas.id}
+              size="sm"
+              variant={index === current ? "default" : "outline"}
+              onClick={() => setCurrent(index)}
+            >
+              {labelOf(canvas, index)}
+            </Button>
+          ))}
+        <div className="ml-auto flex gap-1">
+          <Button size="icon" variant="outline" onClick={() => zoomAt(1.5, size.width / 2, size.height / 2)}>
+            <Plus className="h-4 w-4" />
+          </Button>
+          <Button size="icon" variant="outline" onClick={() => zoomAt(1 / 1.5, size.width / 2, size.height / 2)}>
+            <Minus className="h-4 w-4" />
+          </Button>
+          <Button size="icon" variant="outline" onClick={() => setView(fitView())}>
+            <Maximize className="h-4 w-4" />
+          </Button>
+        </div>
+      </div>
+
+      <div
+        ref={containerRef}
+        className="relative h-[70vh] overflow-hidden rounded-lg border bg-muted cursor-grab active:cursor-grabbing touch-none"
+        onWheel={handleWheel}
+        onPointerDown={handlePointerDown}
+        onPointerMove={handlePointerMove}
+        onPointerUp={handlePointerUp}
+        onPointerCancel={handlePointerUp}
+      >
+        {info && view && (
+          // 低分辨率底图，切片加载完成前先显示
+          <img
+            src={`${info.id}/full/${preview}/0/default.jpg`}
+            alt=""
+            draggable={false}
+            className="absolute max-w-none"
+            style={{ left: view.x, top: view.y, width: info.width * view.scale, height: info.height * view.scale }}
+          />
+        )}
+        {renderTiles()}
+        {!info && (
+          <div className="absolute inset-0 flex items-center justify-center text-sm text-muted-foreground">
+            加载中...
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,42 @@
+// src/components/artifacts/LoadMoreTrigger.tsx
+import { useEffect, useRef } from "react";
+import { Loader2 } from "lucide-react";
+import { Button } from "../ui/button";
+
+interface LoadMoreTriggerProps {
+  hasMore: boolean;
+  loading: boolean;
+  onLoadMore: () => void;
+}
+
+// 列表底部：滚动到可见时自动加载下一页，也可点击按钮加载
+export function LoadMoreTrigger({ hasMore, loading, onLoadMore }: LoadMoreTriggerProps) {
+  const sentinelRef = useRef<HTMLDivElement>(null);
+
+  useEffect(() => {
+    const sentinel = sentinelRef.current;
+    if (!sentinel || !hasMore || loading) return;
+    const observer = new IntersectionObserver(
+      (entries) => {
+        if (entries.some((entry) => entry.isIntersecting)) onLoadMore();
+      },
+      { rootMargin: "200px" }
+    );
+    observer.observe(sentinel);
+    return () => observer.disconnect();
+  }, [hasMore, loading, onLoadMore]);
+
+  if (!hasMore) return null;
+
+  return (
+    <div ref={sentinelRef} className="flex justify-center py-6">
+      {loading ? (
+        <Loader2 className="h-6 w-6 animate-spin text-muted-foreground" />
+      ) : (
+        <Button variant="outline" size="sm" className="rounded-full" onClick={onLoadMore}>
+          加载更多文物
+        </Button>
+      )}
+    </div>
+  );
+}
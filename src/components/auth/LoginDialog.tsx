@@ -13,7 +13,7 @@ import { Label } from "../ui/label";
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "../ui/tabs";
 import { Alert, AlertDescription } from "../ui/alert";
 import { Loader2, Mail, Lock, User, CheckCircle } from "lucide-react";
-import { authApi } from "../../lib/api";
+import { authApi, getErrorMessage } from "../../lib/api";
 import { setAuthToken } from "../../lib/api";
 
 interface LoginDialogProps {
@@ -82,7 +82,7 @@ export function LoginDialog({ open, onOpenChange, onSuccess }: LoginDialogProps)
         onSuccess?.();
       }, 1500);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "登录失败，请重试");
+      setError(getErrorMessage(err, "登录失败，请重试"));
     } finally {
       setLoading(false);
     }
@@ -121,7 +121,7 @@ export function LoginDialog({ open, onOpenChange, onSuccess }: LoginDialogProps)
         onSuccess?.();
       }, 1500);
     } catch (err) {
-      setError(err instanceof Error ? err.message : "注册失败，请重试");
+      setError(getErrorMessage(err, "注册失败，请重试"));
     } finally {
       setLoading(false);
     }
@@ -12,7 +12,7 @@ import {
   Shield
 } from "lucide-react";
 import { useNavigate } from 'react-router-dom';
-import { getAuthToken } from '../../lib/api';
+import { getAuthToken, roleApi } from '../../lib/api';
 import { useState, useEffect } from 'react';
 import { User } from '../../types';
 
@@ -46,12 +46,22 @@ export function Sidebar({
   ];
 
   const [isLoggedIn, setIsLoggedIn] = useState(false);
-  const isAdmin = currentUser?.email === 'yi@example.com';
+  const [isAdmin, setIsAdmin] = useState(false);
 
   useEffect(() => {
     setIsLoggedIn(!!getAuthToken());
   }, []);
 
+  useEffect(() => {
+    if (!currentUser) {
+      setIsAdmin(false);
+      return;
+    }
+    roleApi.getMyPermissions()
+      .then((perms) => setIsAdmin(!!perms && perms.permissions.some((p) => p.startsWith('artifact:'))))
+      .catch(() => setIsAdmin(false));
+  }, [currentUser]);
+
   const personalSections = [
     { id: 'favorites', label: '收藏的文物', icon: Heart, requiresAuth: true },
     { id: 'browsing', label: '浏览历史', icon: Bookmark, requiresAuth: true },
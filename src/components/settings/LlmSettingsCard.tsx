@@ -0,0 +1,168 @@
+// src/components/settings/LlmSettingsCard.tsx
+import { useEffect, useState } from "react";
+import { Bot } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Alert, AlertDescription } from "@/components/ui/alert";
+import { aiApi, getErrorMessage } from "@/lib/api";
+import { LlmConfigView, LlmProviderKind } from "@/types";
+
+const providers: Array<{ value: LlmProviderKind; label: string }> = [
+  { value: "simulator", label: "模拟模式" },
+  { value: "deepseek", label: "DeepSeek" },
+  { value: "openai_compatible", label: "OpenAI 兼容服务" },
+];
+
+interface LlmSettingsCardProps {
+  onSaved?: (message: string) => void;
+}
+
+export function LlmSettingsCard({ onSaved }: LlmSettingsCardProps) {
+  const [config, setConfig] = useState<LlmConfigView | null>(null);
+  // 留空表示保留原有密钥
+  const [apiKey, setApiKey] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    aiApi.getLlmConfig()
+      .then(setConfig)
+      .catch((err) => setError(getErrorMessage(err, "读取模型配置失败")));
+  }, []);
+
+  if (!config) {
+    return null;
+  }
+
+  const update = <K extends keyof LlmConfigView>(key: K, value: LlmConfigView[K]) =>
+    setConfig({ ...config, [key]: value });
+
+  const handleSave = async () => {
+    try {
+      setSaving(true);
+      setError(null);
+      const saved = await aiApi.setLlmConfig({
+        provider: config.provider,
+        base_url: config.base_url,
+        model: config.model,
+        temperature: config.temperature,
+        max_tokens: config.max_tokens,
+        context_window: config.context_window,
+        api_key: apiKey ? apiKey : undefined,
+      });
+      setConfig(saved);
+      setApiKey("");
+      onSaved?.("模型配置已保存");
+    } catch (err) {
+      setError(getErrorMessage(err, "保存模型配置失败"));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const isRemote = config.provider !== "simulator";
+
+  return (
+    <Card className="mb-4">
+      <CardHeader>
+        <CardTitle className="flex items-center gap-2">
+          <Bot className="h-5 w-5" />
+          AI 助手模型
+        </CardTitle>
+        <CardDescription>选择 AI 助手使用的模型服务，本地 Ollama / llama.cpp 可选择 OpenAI 兼容服务</CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-4">
+        <div className="flex gap-2">
+          {providers.map((provider) => (
+            <Button
+              key={provider.value}
+              variant={config.provider === provider.value ? "default" : "outline"}
+              size="sm"
+              onClick={() => setConfig({ ...config, provider: provider.value, base_url: null, model: null })}
+              className="flex-1"
+            >
+              {provider.label}
+            </Button>
+          ))}
+        </div>
+
+        {isRemote && (
+          <div className="grid grid-cols-2 gap-4">
+            <div className="col-span-2 space-y-1">
+              <Label htmlFor="llm-base-url">服务地址</Label>
+              <Input
+                id="llm-base-url"
+                value={config.base_url ?? ""}
+                onChange={(e) => update("base_url", e.target.value || null)}
+                placeholder="http://localhost:11434/v1"
+              />
+            </div>
+            <div className="space-y-1">
+              <Label htmlFor="llm-model">模型</Label>
+              <Input
+                id="llm-model"
+                value={config.model ?? ""}
+                onChange={(e) => update("model", e.target.value || null)}
+                placeholder="qwen2.5:7b"
+              />
+            </div>
+            <div className="space-y-1">
+              <Label htmlFor="llm-api-key">API 密钥</Label>
+              <Input
+                id="llm-api-key"
+                type="password"
+                value={apiKey}
+                onChange={(e) => setApiKey(e.target.value)}
+                placeholder={config.has_api_key ? "已配置，留空保持不变" : "本地服务可不填"}
+              />
+            </div>
+            <div className="space-y-1">
+              <Label htmlFor="llm-temperature">temperature</Label>
+              <Input
+                id="llm-temperature"
+                type="number"
+                step="0.1"
+                min="0"
+                max="2"
+                value={config.temperature}
+                onChange={(e) => update("temperature", Number(e.target.value))}
+              />
+            </div>
+            <div className="space-y-1">
+              <Label htmlFor="llm-max-tokens">max_tokens</Label>
+              <Input
+                id="llm-max-tokens"
+                type="number"
+                min="1"
+                value={config.max_tokens}
+                onChange={(e) => update("max_tokens", Number(e.target.value))}
+              />
+            </div>
+            <div className="space-y-1">
+              <Label htmlFor="llm-context-window">上下文窗口（token）</Label>
+              <Input
+                id="llm-context-window"
+                type="number"
+                min="1"
+                value={config.context_window}
+                onChange={(e) => update("context_window", Number(e.target.value))}
+              />
+            </div>
+          </div>
+        )}
+
+        {error && (
+          <Alert variant="destructive">
+            <AlertDescription>{error}</AlertDescription>
+          </Alert>
+        )}
+
+        <Button onClick={handleSave} disabled={saving} size="sm">
+          {saving ? "保存中..." : "保存模型配置"}
+        </Button>
+      </CardContent>
+    </Card>
+  );
+}
@@ -1,29 +1,48 @@
 import { useState, useEffect } from 'react';
-import { artifactApi } from '../lib/api';
+import { artifactApi, getErrorMessage } from '../lib/api';
 import { ArtifactWithFavorite, SearchParams } from '../types';
 
 export function useArtifacts(initialParams?: SearchParams) {
   const [artifacts, setArtifacts] = useState<ArtifactWithFavorite[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  const [total, setTotal] = useState(0);
+  const [nextCursor, setNextCursor] = useState<string | null>(null);
+  const [loadingMore, setLoadingMore] = useState(false);
 
   const loadArtifacts = async (params: SearchParams = {}) => {
     try {
       setLoading(true);
       setError(null);
-      console.log('🔍 Loading artifacts with params:', params); // Debug log
-      const data = await artifactApi.getArtifacts(params);
-      console.log('📦 Received artifacts:', data.length, 'items'); // Debug log
-      console.log('📝 Artifacts details:', data.map(a => ({ id: a.id, title: a.title, is_favorite: a.is_favorite }))); // Debug log
-      setArtifacts(data);
+      const page = await artifactApi.getArtifacts(params);
+      setArtifacts(page.items);
+      setTotal(page.total);
+      setNextCursor(page.next_cursor);
     } catch (err) {
       console.error('❌ Failed to load artifacts:', err);
-      setError(err instanceof Error ? err.message : 'Failed to load artifacts');
+      setError(getErrorMessage(err, 'Failed to load artifacts'));
     } finally {
       setLoading(false);
     }
   };
 
+  // 无限滚动：用上一页返回的游标加载下一页
+  const loadMore = async () => {
+    if (!nextCursor || loading || loadingMore) return;
+    try {
+      setLoadingMore(true);
+      const page = await artifactApi.getArtifacts({ ...(initialParams || {}), cursor: nextCursor });
+      setArtifacts(prev => [...prev, ...page.items]);
+      setTotal(page.total);
+      setNextCursor(page.next_cursor);
+    } catch (err) {
+      console.error('❌ Failed to load more artifacts:', err);
+      setError(getErrorMessage(err, 'Failed to load artifacts'));
+    } finally {
+      setLoadingMore(false);
+    }
+  };
+
   const toggleFavorite = async (artifactId: number) => {
     try {
       const newFavoriteStatus = await artifactApi.toggleFavorite(artifactId);
@@ -43,7 +62,6 @@ export function useArtifacts(initialParams?: SearchParams) {
 
   // 使用字符串化的参数作为依赖
   useEffect(() => {
-    console.log('🔄 useArtifacts useEffect triggered with params:', initialParams);
     loadArtifacts(initialParams || {});
   }, [JSON.stringify(initialParams)]);
 
@@ -51,6 +69,10 @@ export function useArtifacts(initialParams?: SearchParams) {
     artifacts,
     loading,
     error,
+    total,
+    hasMore: nextCursor !== null,
+    loadingMore,
+    loadMore,
     toggleFavorite,
   };
-}
\ No newline at end of file
+}
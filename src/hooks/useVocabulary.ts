@@ -0,0 +1,37 @@
+import { useCallback, useEffect, useState } from 'react';
+import { getErrorMessage, vocabularyApi } from '../lib/api';
+import { Vocabulary, VocabularyTermWithUsage } from '../types';
+
+// 受控词表：类别、材质、收藏机构的词条及显示名称
+export function useVocabulary() {
+  const [terms, setTerms] = useState<VocabularyTermWithUsage[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  const reload = useCallback(async () => {
+    try {
+      setError(null);
+      setTerms(await vocabularyApi.getVocabulary());
+    } catch (err) {
+      console.error('Failed to load vocabulary:', err);
+      setError(getErrorMessage(err, '加载词表失败'));
+    }
+  }, []);
+
+  useEffect(() => {
+    reload();
+  }, [reload]);
+
+  const termsOf = useCallback(
+    (vocabulary: Vocabulary) => terms.filter(t => t.vocabulary === vocabulary),
+    [terms]
+  );
+
+  // 代码对应的中文名称，不在词表中时原样返回
+  const label = useCallback(
+    (vocabulary: Vocabulary, code: string) =>
+      terms.find(t => t.vocabulary === vocabulary && t.code === code)?.label_zh ?? code,
+    [terms]
+  );
+
+  return { terms, termsOf, label, error, reload };
+}
@@ -1,6 +1,35 @@
 // src/lib/api.ts
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { 
+  ApiError,
+  ArtifactDetail,
+  ArtifactMedia,
+  ArtifactQuestionRequest,
+  ExportRequest,
+  ExportResult,
+  IiifManifest,
+  MapArtifacts,
+  MapClusters,
+  ClusterQuery,
+  ChronologyPeriod,
+  Vocabulary,
+  VocabularyTerm,
+  VocabularyTermInput,
+  VocabularyTermWithUsage,
+  VocabularyMergeResult,
+  Timeline,
+  TimelineQuery,
+  MapQuery,
+  Place,
+  MediaInput,
+  MediaScanOptions,
+  MediaScanReport,
+  UploadedImage,
+  ImportFormat,
+  ImportReport,
+  ArtifactFacets,
+  ArtifactPage,
   ArtifactWithFavorite, 
   SearchParams, 
   User, 
@@ -8,7 +37,20 @@ import {
   RegisterRequest,
   CreateArtifactRequest,
   UpdateArtifactRequest,
-  AuthResponse
+  AuthResponse,
+  ChatStreamEvent,
+  Citation,
+  Conversation,
+  ConversationDetail,
+  ConversationTurn,
+  DbStatus,
+  LlmConfigUpdate,
+  LlmConfigView,
+  RoleInfo,
+  SearchHit,
+  SessionInfo,
+  UserPermissions,
+  UserWithRoles
 } from '../types';
 
 // Token 管理
@@ -31,11 +73,72 @@ export const clearAuthToken = () => {
   localStorage.removeItem('auth_token');
 };
 
+// 后端错误统一为 { code, message, details }
+export const isApiError = (err: unknown, code?: string): err is ApiError =>
+  typeof err === 'object' &&
+  err !== null &&
+  typeof (err as ApiError).code === 'string' &&
+  typeof (err as ApiError).message === 'string' &&
+  (code === undefined || (err as ApiError).code === code);
+
+export const getErrorMessage = (err: unknown, fallback = '操作失败，请重试'): string => {
+  if (isApiError(err) || err instanceof Error) return err.message;
+  if (typeof err === 'string') return err;
+  return fallback;
+};
+
+const authRequired = (): ApiError => ({ code: 'AUTH_REQUIRED', message: '用户未登录' });
+
+// 前端的驼峰字段转为后端的 SearchParams 字段
+const toParams = (params?: SearchParams) => ({
+  ...params,
+  favorites_only: params?.favoritesOnly,
+  year_from: params?.yearFrom,
+  year_to: params?.yearTo,
+});
+
+export const systemApi = {
+  getDatabaseStatus: (): Promise<DbStatus> => invoke('get_database_status'),
+
+  // 等待数据库初始化完成；初始化失败时以失败原因 reject
+  waitForDatabase: async (): Promise<void> => {
+    const settle = (status: DbStatus) => {
+      if (status.state === 'ready') return true;
+      if (status.state === 'failed') {
+        throw new Error(`数据库初始化失败 (${status.stage}): ${status.message}`);
+      }
+      return false;
+    };
+
+    let resolveReady!: () => void;
+    let rejectReady!: (err: unknown) => void;
+    const ready = new Promise<void>((resolve, reject) => {
+      resolveReady = resolve;
+      rejectReady = reject;
+    });
+
+    // 先注册监听再查询当前状态，避免错过已发出的事件
+    const unlisten = await listen<DbStatus>('database-status', (event) => {
+      try {
+        if (settle(event.payload)) resolveReady();
+      } catch (err) {
+        rejectReady(err);
+      }
+    });
+    try {
+      if (settle(await systemApi.getDatabaseStatus())) return;
+      await ready;
+    } finally {
+      unlisten();
+    }
+  },
+};
+
 export const historyApi = {
   addToHistory: (artifactId: number): Promise<void> => {
     const token = getAuthToken();
     if (!token) {
-      return Promise.reject(new Error('用户未登录'));
+      return Promise.reject(authRequired());
     }
     return invoke('add_to_history', { artifactId, token });
   },
@@ -47,7 +150,7 @@ export const historyApi = {
   }>> => {
     const token = getAuthToken();
     if (!token) {
-      return Promise.reject(new Error('用户未登录'));
+      return Promise.reject(authRequired());
     }
     return invoke('get_browsing_history', { token });
   },
@@ -55,51 +158,72 @@ export const historyApi = {
   clearBrowsingHistory: (): Promise<void> => {
     const token = getAuthToken();
     if (!token) {
-      return Promise.reject(new Error('用户未登录'));
+      return Promise.reject(authRequired());
     }
     return invoke('clear_browsing_history', { token });
   },
 };
 
 export const artifactApi = {
-  getArtifacts: (params?: SearchParams): Promise<ArtifactWithFavorite[]> => {
+  getArtifacts: (params?: SearchParams): Promise<ArtifactPage> => {
     const token = getAuthToken();
     console.log('🚀 API call getArtifacts with params:', params); // Debug
     return invoke('get_artifacts', { 
-      params: { ...params, favorites_only: params?.favoritesOnly },
+      params: toParams(params),
       token
     });
   },
   
-  getArtifactById: (id: number): Promise<ArtifactWithFavorite | null> => 
-    invoke('get_artifact_by_id', { id }),
+  getFacets: (params?: SearchParams): Promise<ArtifactFacets> =>
+    invoke('get_artifact_facets', {
+      params: toParams(params),
+      token: getAuthToken()
+    }),
   
-  searchArtifacts: (query: string): Promise<ArtifactWithFavorite[]> => 
-    invoke('search_artifacts', { query }),
+  getArtifactById: (id: number): Promise<ArtifactDetail | null> => 
+    invoke('get_artifact_by_id', { id, token: getAuthToken() }),
+  
+  searchArtifacts: (query: string, limit?: number): Promise<SearchHit[]> => 
+    invoke('search_artifacts', { query, limit, token: getAuthToken() }),
   
   toggleFavorite: (artifactId: number): Promise<boolean> => {
     const token = getAuthToken();
-    if (!token) return Promise.reject(new Error('用户未登录'));
+    if (!token) return Promise.reject(authRequired());
     return invoke('toggle_favorite', { artifactId, token });
   },
   
   createArtifact: (data: CreateArtifactRequest): Promise<ArtifactWithFavorite> => {
     const token = getAuthToken();
-    if (!token) return Promise.reject(new Error('用户未登录'));
+    if (!token) return Promise.reject(authRequired());
     return invoke('create_artifact', { artifact: data, token });
   },
   
   updateArtifact: (data: UpdateArtifactRequest): Promise<ArtifactWithFavorite> => {
     const token = getAuthToken();
-    if (!token) return Promise.reject(new Error('用户未登录'));
+    if (!token) return Promise.reject(authRequired());
     return invoke('update_artifact', { artifact: data, token });
   },
   
   deleteArtifact: (id: number): Promise<boolean> => {
     const token = getAuthToken();
-    if (!token) return Promise.reject(new Error('用户未登录'));
+    if (!token) return Promise.reject(authRequired());
     return invoke('delete_artifact', { id, token });
   },
+
+  // 导出到下载目录，返回文件路径
+  exportArtifacts: (request: ExportRequest): Promise<ExportResult> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    const params = request.params && toParams(request.params);
+    return invoke('export_artifacts', { token, request: { ...request, params } });
+  },
+
+  // 批量导入；dryRun 为 true 时只校验并预览
+  importArtifacts: (format: ImportFormat, content: string, dryRun: boolean): Promise<ImportReport> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('import_artifacts', { token, request: { format, content, dry_run: dryRun } });
+  },
 };
 
 export const authApi = {
@@ -125,6 +249,76 @@ export const authApi = {
   
   updateProfile: (data: Partial<User>): Promise<User> => 
     invoke('update_profile', { data }),
+
+  listSessions: (): Promise<SessionInfo[]> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('list_sessions', { token });
+  },
+
+  revokeSession: (sessionId: number): Promise<boolean> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('revoke_session', { token, sessionId });
+  },
+
+  revokeOtherSessions: (): Promise<number> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('revoke_other_sessions', { token });
+  },
+};
+
+export const roleApi = {
+  getMyPermissions: (): Promise<UserPermissions | null> => {
+    const token = getAuthToken();
+    if (!token) return Promise.resolve(null);
+    return invoke('get_my_permissions', { token });
+  },
+
+  listRoles: (): Promise<RoleInfo[]> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('list_roles', { token });
+  },
+
+  listUsersWithRoles: (): Promise<UserWithRoles[]> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('list_users_with_roles', { token });
+  },
+
+  grantRole: (userId: number, role: string): Promise<string[]> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('grant_role', { token, userId, role });
+  },
+
+  revokeRole: (userId: number, role: string): Promise<string[]> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('revoke_role', { token, userId, role });
+  },
+};
+
+// 在命令执行期间监听 request_id 对应的流式事件
+const withChatStream = async <T>(
+  requestId: string,
+  onDelta: (delta: string) => void,
+  run: () => Promise<T>,
+): Promise<{ result: T; cancelled: boolean }> => {
+  let cancelled = false;
+  const unlisten = await listen<ChatStreamEvent>('ai-chat-stream', (event) => {
+    const payload = event.payload;
+    if (payload.request_id !== requestId) return;
+    if (payload.type === 'delta') onDelta(payload.content);
+    if (payload.type === 'done') cancelled = payload.cancelled;
+  });
+  try {
+    return { result: await run(), cancelled };
+  } finally {
+    unlisten();
+  }
 };
 
 export const aiApi = {
@@ -142,4 +336,221 @@ export const aiApi = {
       throw error;
     }
   },
-};
\ No newline at end of file
+
+  // 流式对话：onDelta 收到增量文本，返回的 promise 在生成结束（或取消）后以完整回复 resolve
+  chatWithAIStream: async (
+    requestId: string,
+    message: string,
+    conversationHistory: Array<{role: string, content: string}>,
+    onDelta: (delta: string) => void,
+  ): Promise<{ response: string; citations: Citation[]; cancelled: boolean }> => {
+    const { result, cancelled } = await withChatStream(requestId, onDelta, () =>
+      invoke<{ response: string; citations: Citation[] }>('chat_with_ai_stream', {
+        requestId,
+        request: {
+          message,
+          conversation_history: conversationHistory
+        }
+      })
+    );
+    return { response: result.response, citations: result.citations, cancelled };
+  },
+
+  // 针对单件文物提问，同样以流式事件推送
+  askAboutArtifact: async (
+    requestId: string,
+    request: ArtifactQuestionRequest,
+    onDelta: (delta: string) => void,
+  ): Promise<{ response: string; citations: Citation[]; cancelled: boolean }> => {
+    const { result, cancelled } = await withChatStream(requestId, onDelta, () =>
+      invoke<{ response: string; citations: Citation[] }>('ask_about_artifact', { requestId, request })
+    );
+    return { response: result.response, citations: result.citations, cancelled };
+  },
+
+  cancelChat: (requestId: string): Promise<boolean> =>
+    invoke('cancel_chat', { requestId }),
+
+  getLlmConfig: (): Promise<LlmConfigView> => invoke('get_llm_config'),
+
+  setLlmConfig: (config: LlmConfigUpdate): Promise<LlmConfigView> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('set_llm_config', { token, config });
+  },
+};
+
+export const conversationApi = {
+  list: (): Promise<Conversation[]> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('list_conversations', { token });
+  },
+
+  create: (title?: string): Promise<Conversation> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('create_conversation', { token, title });
+  },
+
+  get: (conversationId: number): Promise<ConversationDetail> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('get_conversation', { token, conversationId });
+  },
+
+  rename: (conversationId: number, title: string): Promise<Conversation> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('rename_conversation', { token, conversationId, title });
+  },
+
+  delete: (conversationId: number): Promise<boolean> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('delete_conversation', { token, conversationId });
+  },
+
+  // 在对话中继续提问，历史由后端读取；onDelta 收到增量文本
+  continue: async (
+    conversationId: number,
+    requestId: string,
+    message: string,
+    onDelta: (delta: string) => void,
+  ): Promise<ConversationTurn> => {
+    const token = getAuthToken();
+    if (!token) throw authRequired();
+    const { result } = await withChatStream(requestId, onDelta, () =>
+      invoke<ConversationTurn>('continue_conversation', { token, conversationId, requestId, message })
+    );
+    return result;
+  },
+};
+
+const toBase64 = async (file: Blob): Promise<string> => {
+  const bytes = new Uint8Array(await file.arrayBuffer());
+  let binary = '';
+  // 分块转换，避免参数过多导致栈溢出
+  for (let i = 0; i < bytes.length; i += 0x8000) {
+    binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+  }
+  return btoa(binary);
+};
+
+// 文物媒体管理，均返回该文物的全部媒体
+export const mediaApi = {
+  // 上传图片；提供 artifactId 时同时添加为该文物的图片
+  upload: async (
+    file: Blob,
+    options: { artifactId?: number; caption?: string; isPrimary?: boolean } = {}
+  ): Promise<UploadedImage> => {
+    const token = getAuthToken();
+    if (!token) throw authRequired();
+    return invoke('upload_artifact_image', {
+      token,
+      upload: {
+        content: await toBase64(file),
+        artifact_id: options.artifactId,
+        caption: options.caption,
+        is_primary: options.isPrimary ?? false,
+      },
+    });
+  },
+
+  add: (media: MediaInput): Promise<ArtifactMedia[]> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('add_artifact_media', { token, media });
+  },
+
+  update: (mediaId: number, update: { caption?: string; is_primary?: boolean }): Promise<ArtifactMedia[]> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('update_artifact_media', { token, mediaId, update });
+  },
+
+  reorder: (artifactId: number, mediaIds: number[]): Promise<ArtifactMedia[]> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('reorder_artifact_media', { token, artifactId, mediaIds });
+  },
+
+  delete: (mediaId: number): Promise<ArtifactMedia[]> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('delete_artifact_media', { token, mediaId });
+  },
+};
+
+export const maintenanceApi = {
+  // 媒体完整性扫描；fix、quarantine 会修改数据或移动文件
+  scanMedia: (options: MediaScanOptions = {}): Promise<MediaScanReport> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('scan_media', { token, options });
+  },
+};
+
+export const geoApi = {
+  // 查询范围或半径内有坐标的文物
+  getMapArtifacts: (query: MapQuery = {}): Promise<MapArtifacts> => {
+    const params = query.params && toParams(query.params);
+    return invoke('get_map_artifacts', { query: { ...query, params }, token: getAuthToken() });
+  },
+
+  // 按视野和缩放级别聚合文物，附带热力图网格
+  getMapClusters: (query: ClusterQuery): Promise<MapClusters> => {
+    const params = query.params && toParams(query.params);
+    return invoke('get_map_clusters', { query: { ...query, params }, token: getAuthToken() });
+  },
+};
+
+export const chronologyApi = {
+  // 朝代、分期及年号的起止年份
+  getChronology: (): Promise<ChronologyPeriod[]> => invoke('get_chronology'),
+
+  // 按世纪或朝代分组的时间轴
+  getTimeline: (query: TimelineQuery = {}): Promise<Timeline> => {
+    const params = query.params && toParams(query.params);
+    return invoke('get_timeline', { query: { ...query, params }, token: getAuthToken() });
+  },
+
+  // 按内置地名表解析地点文本，无法识别时返回 null
+  geocode: (text: string): Promise<Place | null> => invoke('geocode_location', { text }),
+};
+
+export const vocabularyApi = {
+  // 词表（含各词条的使用数量），不指定时返回全部词表
+  getVocabulary: (vocabulary?: Vocabulary): Promise<VocabularyTermWithUsage[]> =>
+    invoke('get_vocabulary', { vocabulary }),
+
+  createTerm: (term: VocabularyTermInput): Promise<VocabularyTerm> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('create_vocabulary_term', { token, term });
+  },
+
+  updateTerm: (id: number, term: VocabularyTermInput): Promise<VocabularyTerm> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('update_vocabulary_term', { token, id, term });
+  },
+
+  deleteTerm: (id: number): Promise<void> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('delete_vocabulary_term', { token, id });
+  },
+
+  // 把 source 合并到 target，文物和下级词条都移到 target
+  mergeTerms: (sourceId: number, targetId: number): Promise<VocabularyMergeResult> => {
+    const token = getAuthToken();
+    if (!token) return Promise.reject(authRequired());
+    return invoke('merge_vocabulary_terms', { token, sourceId, targetId });
+  },
+};
+
+export const iiifApi = {
+  getManifest: (artifactId: number): Promise<IiifManifest> =>
+    invoke('get_iiif_manifest', { artifactId }),
+};
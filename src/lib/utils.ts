@@ -1,5 +1,7 @@
 import { clsx, type ClassValue } from "clsx"
 import { twMerge } from "tailwind-merge"
+import { convertFileSrc } from "@tauri-apps/api/core"
+import type { ArtifactMedia, ArtifactWithFavorite, MediaType } from "@/types"
 
 export function cn(...inputs: ClassValue[]) {
   return twMerge(clsx(inputs))
@@ -10,4 +12,56 @@ export function formatDate(date: string): string {
     month: 'long',
     day: 'numeric'
   });
-}
\ No newline at end of file
+}
+
+// 年份（公元前为负数）显示为「公元前1300年」「618年」
+export function formatYear(year: number): string {
+  return year < 0 ? `公元前${-year}年` : `${year}年`;
+}
+
+export function formatYearRange(from: number | null, to: number | null): string {
+  if (from === null || to === null) return "";
+  return from === to ? formatYear(from) : `${formatYear(from)} – ${formatYear(to)}`;
+}
+
+// 图片版本：原图、中等尺寸（长边 1280）、缩略图（长边 320）
+export type ImageRendition = "original" | "medium" | "thumb";
+
+// 图片缺失时的占位图
+export const PLACEHOLDER_IMAGE =
+  "data:image/svg+xml;utf8," +
+  encodeURIComponent(
+    '<svg xmlns="http://www.w3.org/2000/svg" width="400" height="300" viewBox="0 0 400 300">' +
+      '<rect width="400" height="300" fill="#f1f5f9"/>' +
+      '<text x="200" y="158" font-size="20" text-anchor="middle" fill="#94a3b8">图片缺失</text>' +
+      "</svg>"
+  );
+
+// 图片统一通过 media 协议读取，上传的图片和随应用分发的图片都可访问
+export function imageUrl(imagePath: string, rendition: ImageRendition = "original"): string {
+  return convertFileSrc(`${rendition}/${imagePath}`, "media");
+}
+
+export function artifactImageUrl(
+  artifact: Pick<ArtifactWithFavorite, "image_path" | "image_missing">,
+  rendition: ImageRendition = "medium"
+): string {
+  if (artifact.image_missing || !artifact.image_path) return PLACEHOLDER_IMAGE;
+  return imageUrl(artifact.image_path, rendition);
+}
+
+// 除图片外的媒体在 public 下的目录
+const mediaDirs: Record<Exclude<MediaType, "image">, string> = {
+  model_3d: "/3DModels",
+  audio: "/media/audio",
+  video: "/media/video",
+  document: "/media/documents",
+};
+
+export function mediaUrl(
+  media: Pick<ArtifactMedia, "media_type" | "path">,
+  rendition: ImageRendition = "original"
+): string {
+  if (media.media_type === "image") return imageUrl(media.path, rendition);
+  return `${mediaDirs[media.media_type]}/${media.path}`;
+}
@@ -1,11 +1,17 @@
 import { useEffect, useState, useRef } from "react";
-import { artifactApi } from "../lib/api";
-import { ArtifactWithFavorite } from "../types";
+import { artifactApi, geoApi, getErrorMessage, isApiError, mediaApi, roleApi } from "../lib/api";
+import { ArtifactMedia, ArtifactWithFavorite, Permission, Vocabulary } from "../types";
 import { Button } from "../components/ui/button";
 import { Input } from "../components/ui/input";
 import { Card, CardContent, CardHeader, CardTitle } from "../components/ui/card";
 import { Alert, AlertDescription } from "../components/ui/alert";
 import { Label } from "../components/ui/label";
+import { ArtifactImportCard } from "../components/admin/ArtifactImportCard";
+import { ArtifactExportCard } from "../components/admin/ArtifactExportCard";
+import { ArtifactMediaCard } from "../components/admin/ArtifactMediaCard";
+import { MediaScanCard } from "../components/admin/MediaScanCard";
+import { VocabularyCard } from "../components/admin/VocabularyCard";
+import { useVocabulary } from "../hooks/useVocabulary";
 
 const emptyForm = {
   id: 0,
@@ -21,22 +27,54 @@ const emptyForm = {
   discovery_location: "",
   collection: "",
   category: "",
+  // 坐标以文本编辑，提交时转换为数字
+  latitude: "",
+  longitude: "",
+  location_uncertainty_m: "",
+  // 起止年份，公元前为负数
+  date_from: "",
+  date_to: "",
 };
 
+const toNumber = (value: string) => (value.trim() === "" ? null : Number(value));
+
 export function AdminPage({ currentUser }: { currentUser: { email?: string } | null }) {
   const [artifacts, setArtifacts] = useState<ArtifactWithFavorite[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
+  // 后端校验未通过的字段，在表单中标红
+  const [invalidFields, setInvalidFields] = useState<string[]>([]);
+  const invalid = (field: string) => invalidFields.includes(field) || undefined;
   const [form, setForm] = useState<typeof emptyForm>(emptyForm);
   const [isEditing, setIsEditing] = useState(false);
-  const isAdmin = currentUser?.email === "yi@example.com";
+  const [permissions, setPermissions] = useState<Permission[]>([]);
+  const isAdmin = permissions.some((p) => p.startsWith("artifact:"));
+  const canManageVocabulary = permissions.includes("vocabulary:manage");
+  const { termsOf, reload: reloadVocabulary } = useVocabulary();
+
+  useEffect(() => {
+    if (!currentUser) {
+      setPermissions([]);
+      return;
+    }
+    roleApi.getMyPermissions()
+      .then((perms) => setPermissions(perms?.permissions ?? []))
+      .catch(() => setPermissions([]));
+  }, [currentUser]);
 
   const loadData = async () => {
     try {
       setLoading(true);
       setError(null);
-      const data = await artifactApi.getArtifacts();
-      setArtifacts(data);
+      // 管理列表需要全部文物，按游标逐页读取
+      const items: ArtifactWithFavorite[] = [];
+      let cursor: string | undefined;
+      do {
+        const page = await artifactApi.getArtifacts({ limit: 200, cursor });
+        items.push(...page.items);
+        cursor = page.next_cursor ?? undefined;
+      } while (cursor);
+      setArtifacts(items);
     } catch (err) {
       setError("加载文物列表失败");
     } finally {
@@ -55,13 +93,27 @@ export function AdminPage({ currentUser }: { currentUser: { email?: string } | n
     setForm((prev) => ({ ...prev, [name]: value }));
   };
 
-  const handleFileChange = (e: React.ChangeEvent<HTMLInputElement>) => {
+  const [uploading, setUploading] = useState(false);
+  const [uploadNotice, setUploadNotice] = useState<string | null>(null);
+
+  // 上传图片到应用数据目录，保存后的文件名写入表单
+  const handleFileChange = async (e: React.ChangeEvent<HTMLInputElement>) => {
     const file = e.target.files?.[0];
-    if (file) {
-      // Get the full path of the selected file
-      // @ts-ignore - webkitRelativePath is not in the type definition but exists in some browsers
-      const fullPath = (e.target as any).value || file.webkitRelativePath || file.name;
-      setForm(prev => ({ ...prev, image_path: fullPath }));
+    e.target.value = "";
+    if (!file) return;
+    try {
+      setUploading(true);
+      setError(null);
+      const uploaded = await mediaApi.upload(file);
+      setForm(prev => ({ ...prev, image_path: uploaded.path }));
+      setUploadNotice(
+        `已上传 ${file.name}（${uploaded.width}×${uploaded.height}）` +
+          (uploaded.gps_removed ? "，已去除 GPS 定位信息" : "")
+      );
+    } catch (err) {
+      setError(getErrorMessage(err, "上传图片失败"));
+    } finally {
+      setUploading(false);
     }
   };
 
@@ -83,6 +135,11 @@ export function AdminPage({ currentUser }: { currentUser: { email?: string } | n
     discovery_location: a.discovery_location,
     collection: a.collection,
     category: a.category,
+    latitude: a.latitude?.toString() ?? "",
+    longitude: a.longitude?.toString() ?? "",
+    location_uncertainty_m: a.location_uncertainty_m?.toString() ?? "",
+    date_from: a.date_from?.toString() ?? "",
+    date_to: a.date_to?.toString() ?? "",
   });
 
   const handleEdit = (artifact: ArtifactWithFavorite) => {
@@ -90,17 +147,60 @@ export function AdminPage({ currentUser }: { currentUser: { email?: string } | n
     setIsEditing(true);
   };
 
+  // 主图可能已更换，同步到表单，避免保存时用旧图片覆盖
+  const handleMediaChanged = (media: ArtifactMedia[]) => {
+    const primary = media.find((m) => m.media_type === "image" && m.is_primary);
+    if (primary) setForm((prev) => ({ ...prev, image_path: primary.path }));
+    loadData();
+  };
+
+  const [geocodeNotice, setGeocodeNotice] = useState<string | null>(null);
+
+  // 按发现地点（或所在地）查内置地名表，填入坐标
+  const handleGeocode = async () => {
+    const text = form.discovery_location.trim() || form.location.trim();
+    if (!text) {
+      setGeocodeNotice("请先填写发现地点或所在地");
+      return;
+    }
+    try {
+      const place = await geoApi.geocode(text);
+      if (!place) {
+        setGeocodeNotice(`未能识别「${text}」，请手动填写经纬度`);
+        return;
+      }
+      setForm((prev) => ({
+        ...prev,
+        latitude: place.latitude.toString(),
+        longitude: place.longitude.toString(),
+        location_uncertainty_m: place.uncertainty_m.toString(),
+      }));
+      setGeocodeNotice(`已定位到 ${place.name}（${place.province}）`);
+    } catch (err) {
+      setGeocodeNotice(getErrorMessage(err, "定位失败"));
+    }
+  };
+
   const handleReset = () => {
+    setUploadNotice(null);
+    setGeocodeNotice(null);
     setForm(emptyForm);
     setIsEditing(false);
+    setInvalidFields([]);
   };
 
   const handleSubmit = async () => {
     try {
       setError(null);
+      setInvalidFields([]);
       const payload = {
         ...form,
         id: isEditing ? form.id : undefined,
+        latitude: toNumber(form.latitude),
+        longitude: toNumber(form.longitude),
+        location_uncertainty_m: toNumber(form.location_uncertainty_m),
+        date_from: toNumber(form.date_from),
+        date_to: toNumber(form.date_to),
       };
       if (isEditing) {
         await artifactApi.updateArtifact(payload as any);
@@ -110,7 +210,15 @@ export function AdminPage({ currentUser }: { currentUser: { email?: string } | n
       await loadData();
       handleReset();
     } catch (err) {
-      setError("保存失败，请确认已登录管理员账号 (yi@example.com)");
+      if (isApiError(err, "VALIDATION")) {
+        const fields = err.details?.errors?.map((e) => e.field) ?? [err.details?.field];
+        setInvalidFields(fields.filter((f): f is string => !!f));
+      }
+      setError(
+        isApiError(err, "FORBIDDEN")
+          ? "保存失败，请确认当前账号拥有文物编辑权限"
+          : getErrorMessage(err, "保存失败")
+      );
     }
   };
 
@@ -121,7 +229,11 @@ export function AdminPage({ currentUser }: { currentUser: { email?: string } | n
       await loadData();
       if (form.id === id) handleReset();
     } catch (err) {
-      setError("删除失败，请确认已登录管理员账号 (yi@example.com)");
+      setError(
+        isApiError(err, "FORBIDDEN")
+          ? "删除失败，请确认当前账号拥有文物删除权限"
+          : getErrorMessage(err, "删除失败")
+      );
     }
   };
 
@@ -129,7 +241,7 @@ export function AdminPage({ currentUser }: { currentUser: { email?: string } | n
     return (
       <div className="p-6">
         <Alert variant="destructive">
-          <AlertDescription>当前账户无权限访问后台管理（需 yi@example.com）</AlertDescription>
+          <AlertDescription>当前账户无权限访问后台管理（需策展人或管理员角色）</AlertDescription>
         </Alert>
       </div>
     );
@@ -161,7 +273,7 @@ export function AdminPage({ currentUser }: { currentUser: { email?: string } | n
           <div className="grid grid-cols-1 md:grid-cols-2 gap-3">
             <div className="space-y-2">
               <Label htmlFor="title">标题</Label>
-              <Input id="title" name="title" value={form.title} onChange={handleChange} />
+              <Input id="title" name="title" aria-invalid={invalid("title")} value={form.title} onChange={handleChange} />
             </div>
             <div className="space-y-2">
               <Label htmlFor="image_path">图片文件</Label>
@@ -170,55 +282,93 @@ export function AdminPage({ currentUser }: { currentUser: { email?: string } | n
                   type="button" 
                   variant="outline" 
                   onClick={triggerFileInput}
+                  disabled={uploading}
                   className="flex-1 justify-start text-left overflow-hidden"
                 >
-                  {form.image_path ? form.image_path : '选择图片文件...'}
+                  {uploading ? '上传中...' : form.image_path ? form.image_path : '上传图片（JPEG / PNG）...'}
                 </Button>
                 <input
                   type="file"
                   ref={fileInputRef}
                   onChange={handleFileChange}
-                  accept="image/*"
+                  accept="image/jpeg,image/png"
                   className="hidden"
                 />
               </div>
-              {form.image_path && (
-                <div className="text-xs text-muted-foreground">
-                  文件名: {form.image_path}
-                </div>
+              {uploadNotice && (
+                <div className="text-xs text-muted-foreground">{uploadNotice}</div>
               )}
               <Input 
                 id="image_path" 
-                name="image_path" 
+                name="image_path"
+                aria-invalid={invalid("image_path")}
                 value={form.image_path} 
                 onChange={handleChange} 
                 placeholder="或手动输入图片路径"
                 className="mt-1"
               />
             </div>
-            <Input name="period" placeholder="时期" value={form.period} onChange={handleChange} />
-            <Input name="dynasty" placeholder="朝代" value={form.dynasty} onChange={handleChange} />
-            <Input name="location" placeholder="出土地/地点" value={form.location} onChange={handleChange} />
-            <Input name="material" placeholder="材质" value={form.material} onChange={handleChange} />
-            <Input name="dimensions" placeholder="尺寸" value={form.dimensions} onChange={handleChange} />
-            <Input name="collection" placeholder="收藏机构" value={form.collection} onChange={handleChange} />
-            <Input name="category" placeholder="分类 (bronze/jade/ceramics/...)" value={form.category} onChange={handleChange} />
-            <Input name="discovery_location" placeholder="发现地点" value={form.discovery_location} onChange={handleChange} />
+            <Input name="period" aria-invalid={invalid("period")} placeholder="时期" value={form.period} onChange={handleChange} />
+            <Input name="dynasty" aria-invalid={invalid("dynasty")} placeholder="朝代" value={form.dynasty} onChange={handleChange} />
+            <Input name="location" aria-invalid={invalid("location")} placeholder="出土地/地点" value={form.location} onChange={handleChange} />
+            <Input name="material" aria-invalid={invalid("material")} list="vocabulary-material" placeholder="材质" value={form.material} onChange={handleChange} />
+            <Input name="dimensions" aria-invalid={invalid("dimensions")} placeholder="尺寸" value={form.dimensions} onChange={handleChange} />
+            <Input name="collection" aria-invalid={invalid("collection")} list="vocabulary-collection" placeholder="收藏机构" value={form.collection} onChange={handleChange} />
+            <Input name="category" aria-invalid={invalid("category")} list="vocabulary-category" placeholder="类别" value={form.category} onChange={handleChange} />
+            {/* 只能填写词表中的词条，可填代码或名称 */}
+            {(["category", "material", "collection"] as Vocabulary[]).map((vocabulary) => (
+              <datalist key={vocabulary} id={`vocabulary-${vocabulary}`}>
+                {termsOf(vocabulary).map((term) => (
+                  <option key={term.id} value={term.code}>
+                    {term.label_zh}
+                  </option>
+                ))}
+              </datalist>
+            ))}
+            <Input name="discovery_location" aria-invalid={invalid("discovery_location")} placeholder="发现地点" value={form.discovery_location} onChange={handleChange} />
+          </div>
+          <div className="space-y-2">
+            <div className="flex gap-2">
+              <Input name="latitude" aria-invalid={invalid("latitude")} placeholder="纬度" value={form.latitude} onChange={handleChange} />
+              <Input name="longitude" aria-invalid={invalid("longitude")} placeholder="经度" value={form.longitude} onChange={handleChange} />
+              <Input
+                name="location_uncertainty_m"
+                aria-invalid={invalid("location_uncertainty_m")}
+                placeholder="误差半径（米）"
+                value={form.location_uncertainty_m}
+                onChange={handleChange}
+              />
+              <Button type="button" variant="outline" onClick={handleGeocode}>
+                按地名定位
+              </Button>
+            </div>
+            <div className="text-xs text-muted-foreground">
+              {geocodeNotice ?? "经纬度留空时按发现地点或所在地自动定位"}
+            </div>
+          </div>
+          <div className="space-y-2">
+            <div className="flex gap-2">
+              <Input name="date_from" aria-invalid={invalid("date_from")} placeholder="起始年份" value={form.date_from} onChange={handleChange} />
+              <Input name="date_to" aria-invalid={invalid("date_to")} placeholder="终止年份" value={form.date_to} onChange={handleChange} />
+            </div>
+            <div className="text-xs text-muted-foreground">公元前用负数表示；留空时按朝代和时期推断</div>
           </div>
           <textarea
             name="description"
+            aria-invalid={invalid("description")}
             placeholder="简介"
             value={form.description}
             onChange={handleChange}
-            className="w-full border rounded-md p-2 text-sm"
+            className="w-full border rounded-md p-2 text-sm aria-invalid:border-destructive"
             rows={2}
           />
           <textarea
             name="detailed_description"
+            aria-invalid={invalid("detailed_description")}
             placeholder="详细描述"
             value={form.detailed_description}
             onChange={handleChange}
-            className="w-full border rounded-md p-2 text-sm"
+            className="w-full border rounded-md p-2 text-sm aria-invalid:border-destructive"
             rows={4}
           />
           <div className="flex gap-2">
@@ -234,6 +384,25 @@ export function AdminPage({ currentUser }: { currentUser: { email?: string } | n
         </CardContent>
       </Card>
 
+      {isEditing && (
+        <ArtifactMediaCard key={form.id} artifactId={form.id} onChanged={handleMediaChanged} />
+      )}
+
+      <ArtifactImportCard onImported={loadData} />
+
+      <ArtifactExportCard />
+
+      <MediaScanCard onFixed={loadData} />
+
+      {canManageVocabulary && (
+        <VocabularyCard
+          onChanged={() => {
+            loadData();
+            reloadVocabulary();
+          }}
+        />
+      )}
+
       <Card>
         <CardHeader>
           <CardTitle>文物列表（点击编辑，删除按钮可删除）</CardTitle>
@@ -7,7 +7,7 @@ import { Card, CardContent, CardHeader, CardTitle, CardDescription } from '../co
 import { Badge } from '../components/ui/badge';
 import { Skeleton } from '../components/ui/skeleton';
 import { useNavigate } from 'react-router-dom';
-import { historyApi } from '../lib/api';
+import { historyApi, isApiError } from '../lib/api';
 import { ArtifactWithFavorite } from '../types';
 
 interface HistoryItem {
@@ -35,12 +35,11 @@ export function BrowsingHistoryPage() {
         if (isMounted) {
           setHistory(data);
         }
-      } catch (err: any) {
+      } catch (err) {
         console.error('Failed to load browsing history:', err);
         if (isMounted) {
           // 检查是否是未登录错误
-          const errorMessage = err?.message || String(err);
-          if (errorMessage.includes('未登录') || errorMessage.includes('用户未登录')) {
+          if (isApiError(err, 'AUTH_REQUIRED')) {
             setError('请先登录以查看浏览历史');
           } else {
             setError('无法加载浏览历史，请稍后重试');
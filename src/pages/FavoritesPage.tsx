@@ -3,6 +3,7 @@ import { ArtifactDialog } from '../components/artifacts/ArtifactDialog';
 import { useArtifacts } from '../hooks/useArtifacts';
 import { useState } from 'react';
 import { ArtifactWithFavorite } from '../types';
+import { artifactApi } from '../lib/api';
 
 export function FavoritesPage() {
   const [selectedArtifact, setSelectedArtifact] = useState<ArtifactWithFavorite | null>(null);
@@ -12,6 +13,10 @@ export function FavoritesPage() {
     artifacts,
     loading,
     error,
+    total,
+    hasMore,
+    loadingMore,
+    loadMore,
     toggleFavorite,
   } = useArtifacts({ 
     favoritesOnly: true
@@ -26,6 +31,16 @@ export function FavoritesPage() {
     await toggleFavorite(artifactId);
   };
 
+  // 打开 AI 回答中引用的其他文物
+  const handleOpenArtifact = async (artifactId: number) => {
+    try {
+      const artifact = await artifactApi.getArtifactById(artifactId);
+      if (artifact) setSelectedArtifact(artifact);
+    } catch (err) {
+      console.error('Failed to load cited artifact:', err);
+    }
+  };
+
   return (
     <div className="p-6">
       <ArtifactGrid
@@ -36,6 +51,10 @@ export function FavoritesPage() {
         onToggleFavorite={handleToggleFavorite}
         searchQuery=""
         activeSection="favorites"
+        total={total}
+        hasMore={hasMore}
+        loadingMore={loadingMore}
+        onLoadMore={loadMore}
       />
       
       {/* Artifact Dialog */}
@@ -45,6 +64,7 @@ export function FavoritesPage() {
           onOpenChange={setIsArtifactDialogOpen}
           artifact={selectedArtifact}
           onToggleFavorite={handleToggleFavorite}
+          onOpenArtifact={handleOpenArtifact}
         />
       )}
     </div>
@@ -3,6 +3,8 @@ import { useState, useMemo } from "react";
 import { ArtifactDialog } from "../components/artifacts/ArtifactDialog";
 import { useArtifacts } from "../hooks/useArtifacts";
 import { ArtifactWithFavorite } from "../types";
+import { artifactApi } from "../lib/api";
+import { artifactImageUrl } from "../lib/utils";
 
 // 功能卡片
 import { TrendingCard } from "../components/artifacts/TrendingCard";
@@ -10,6 +12,7 @@ import { TopicsCard } from "../components/artifacts/TopicsCard";
 import { GuideCard } from "../components/artifacts/GuideCard";
 import { NewsCard } from "../components/artifacts/NewsCard";
 import { CompactArtifactCard } from "../components/artifacts/CompactArtifactCard";
+import { LoadMoreTrigger } from "../components/artifacts/LoadMoreTrigger";
 
 interface HomePageContext {
   searchQuery: string;
@@ -40,7 +43,7 @@ export function HomePage() {
   const [selectedArtifact, setSelectedArtifact] = useState<ArtifactWithFavorite | null>(null);
   const [isArtifactDialogOpen, setIsArtifactDialogOpen] = useState(false);
 
-  const { artifacts, loading, error, toggleFavorite } = useArtifacts({
+  const { artifacts, loading, error, hasMore, loadingMore, loadMore, toggleFavorite } = useArtifacts({
     category: "all",
     query: searchQuery,
     favoritesOnly: false,
@@ -55,6 +58,16 @@ export function HomePage() {
     await toggleFavorite(artifactId);
   };
 
+  // 打开 AI 回答中引用的其他文物
+  const handleOpenArtifact = async (artifactId: number) => {
+    try {
+      const artifact = await artifactApi.getArtifactById(artifactId);
+      if (artifact) setSelectedArtifact(artifact);
+    } catch (err) {
+      console.error("Failed to load cited artifact:", err);
+    }
+  };
+
   // 智能布局算法：使用跨越行和列来填充空白
   const mixedCards = useMemo<MixedCardItem[]>(() => {
     const cards: MixedCardItem[] = [];
@@ -199,7 +212,7 @@ export function HomePage() {
     }
 
     // 继续添加更多文物，创建更多跨越效果
-    for (let i = 11; i < Math.min(20, artifacts.length); i++) {
+    for (let i = 11; i < artifacts.length; i++) {
       const position = i - 11;
       
       let colSpan = "col-span-1";
@@ -238,7 +251,7 @@ export function HomePage() {
             key={`featured-${index}`}
           >
             <img
-              src={`/images/${item.content.image_path}?t=${Date.now()}`}
+              src={artifactImageUrl(item.content)}
               alt={item.content.title}
               className="w-full h-full object-cover group-hover:scale-105 transition-transform duration-300"
             />
@@ -269,6 +282,7 @@ export function HomePage() {
             artifact={item.content}
             onViewDetails={handleViewDetails}
             onToggleFavorite={handleToggleFavorite}
+            onOpenArtifact={handleOpenArtifact}
           />
         );
 
@@ -371,14 +385,8 @@ export function HomePage() {
         </div>
       )}
 
-      {/* 加载更多按钮 */}
-      {artifacts.length > 20 && (
-        <div className="text-center py-6">
-          <button className="text-sm text-gray-600 hover:text-gray-800 px-4 py-2 border rounded-full hover:border-gray-400">
-            加载更多文物
-          </button>
-        </div>
-      )}
+      {/* 滚动到底部时加载下一页 */}
+      <LoadMoreTrigger hasMore={hasMore} loading={loadingMore} onLoadMore={loadMore} />
 
       {selectedArtifact && (
         <ArtifactDialog
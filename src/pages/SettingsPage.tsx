@@ -21,6 +21,7 @@ import {
   AlertTriangle
 } from "lucide-react";
 import { Alert, AlertDescription } from "@/components/ui/alert";
+import { LlmSettingsCard } from "@/components/settings/LlmSettingsCard";
 
 type Theme = "light" | "dark" | "system";
 
@@ -252,6 +253,9 @@ export default function SettingsPage() {
         </CardContent>
       </Card>
 
+      {/* AI 助手模型 */}
+      <LlmSettingsCard onSaved={showMessage} />
+
       {/* 常规设置 */}
       <Card className="mb-4">
         <CardHeader>
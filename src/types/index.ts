@@ -13,12 +13,234 @@ export interface Artifact {
   discovery_location: string;
   collection: string;
   category: string;
+  // 出土地点坐标（WGS84），未定位时为 null
+  latitude: number | null;
+  longitude: number | null;
+  // 坐标的不确定半径（米）
+  location_uncertainty_m: number | null;
+  // 年份范围（公元前为负数），未能确定时为 null
+  date_from: number | null;
+  date_to: number | null;
   created_at: string;
   updated_at: string;
 }
 
 export interface ArtifactWithFavorite extends Artifact {
   is_favorite: boolean;
+  // 主图文件缺失（由媒体扫描维护）时为 true，显示占位图
+  image_missing: boolean;
+}
+
+// 文物媒体
+export type MediaType = 'image' | 'model_3d' | 'audio' | 'video' | 'document';
+
+export interface ArtifactMedia {
+  id: number;
+  artifact_id: number;
+  media_type: MediaType;
+  // 媒体目录下的文件名
+  path: string;
+  caption: string;
+  sort_order: number;
+  is_primary: boolean;
+  // 上次扫描或写入时文件不存在
+  file_missing: boolean;
+  created_at: string;
+}
+
+export interface ArtifactDetail extends ArtifactWithFavorite {
+  media: ArtifactMedia[];
+}
+
+// 上传图片的结果；path 可直接作为 image_path 或图片媒体的 path
+export interface UploadedImage {
+  path: string;
+  width: number;
+  height: number;
+  size: number;
+  gps_removed: boolean;
+  duplicate: boolean;
+  media: ArtifactMedia[];
+}
+
+export interface MediaScanOptions {
+  // 删除文件缺失的媒体记录，重复图片的引用统一指向同一文件
+  fix?: boolean;
+  // 将未被引用的图片移入隔离目录
+  quarantine?: boolean;
+}
+
+export interface ScannedImageFile {
+  name: string;
+  location: "bundled" | "stored";
+  size: number;
+  width: number | null;
+  height: number | null;
+  hash: string;
+}
+
+export interface MissingMedia {
+  media_id: number;
+  artifact_id: number;
+  title: string;
+  media_type: MediaType;
+  path: string;
+  is_primary: boolean;
+}
+
+export interface MediaScanReport {
+  media_count: number;
+  file_count: number;
+  missing: MissingMedia[];
+  orphans: ScannedImageFile[];
+  // 每组第一个为修复时保留的文件
+  duplicates: Array<{ hash: string; files: ScannedImageFile[] }>;
+  oversized: ScannedImageFile[];
+  actions: string[];
+  quarantine_dir: string | null;
+}
+
+export interface MediaInput {
+  artifact_id: number;
+  media_type: MediaType;
+  path: string;
+  caption?: string;
+  is_primary?: boolean;
+}
+
+// 分面统计
+export interface FacetCount {
+  value: string;
+  count: number;
+}
+
+export interface ArtifactFacets {
+  total: number;
+  category: FacetCount[];
+  dynasty: FacetCount[];
+  material: FacetCount[];
+  collection: FacetCount[];
+  discovery_location: FacetCount[];
+}
+
+// 全文检索结果，snippet 中的命中词以 <mark> 包裹
+// 地图查询
+export interface GeoPoint {
+  latitude: number;
+  longitude: number;
+}
+
+export interface BoundingBox {
+  south: number;
+  west: number;
+  north: number;
+  east: number;
+}
+
+export interface MapQuery {
+  bbox?: BoundingBox;
+  // 与 radius_km 一起使用，结果按距离排序
+  center?: GeoPoint;
+  radius_km?: number;
+  params?: SearchParams;
+  limit?: number;
+}
+
+export interface MapArtifact extends ArtifactWithFavorite {
+  distance_km: number | null;
+}
+
+export interface MapArtifacts {
+  items: MapArtifact[];
+  // 超出 limit 未返回的数量
+  truncated: number;
+  // 符合筛选条件但没有坐标的文物数量
+  unlocated: number;
+}
+
+export interface ClusterQuery {
+  bbox: BoundingBox;
+  // 地图缩放级别，决定聚合粒度
+  zoom: number;
+  params?: SearchParams;
+  heatmap_cols?: number;
+}
+
+export interface MapCluster extends GeoPoint {
+  count: number;
+  bounds: BoundingBox;
+  dominant_category: string;
+  dominant_dynasty: string;
+  // 只有一件文物时给出
+  artifact_id: number | null;
+  title: string | null;
+}
+
+export interface DensityCell extends GeoPoint {
+  row: number;
+  col: number;
+  count: number;
+}
+
+// 热力图网格，只包含有文物的格子
+export interface DensityGrid {
+  south: number;
+  west: number;
+  cell_lat: number;
+  cell_lng: number;
+  rows: number;
+  cols: number;
+  max: number;
+  cells: DensityCell[];
+}
+
+export interface MapClusters {
+  clusters: MapCluster[];
+  heatmap: DensityGrid;
+  total: number;
+}
+
+export interface ChronologyPeriod {
+  id: number;
+  name: string;
+  start_year: number;
+  end_year: number;
+  parent: string | null;
+  kind: 'dynasty' | 'reign';
+}
+
+export interface TimelineQuery {
+  group_by?: 'century' | 'dynasty';
+  params?: SearchParams;
+  limit_per_bucket?: number;
+}
+
+export interface TimelineBucket {
+  label: string;
+  start_year: number;
+  end_year: number;
+  count: number;
+  items: ArtifactWithFavorite[];
+}
+
+export interface Timeline {
+  buckets: TimelineBucket[];
+  // 没有年份的文物数量
+  undated: number;
+  // 按朝代分组时无法归入朝代的文物数量
+  unassigned: number;
+}
+
+// 地名表中的地点
+export interface Place extends GeoPoint {
+  name: string;
+  province: string;
+  uncertainty_m: number;
+}
+
+export interface SearchHit extends ArtifactWithFavorite {
+  snippet: string | null;
+  score: number;
 }
 
 export interface User {
@@ -28,13 +250,103 @@ export interface User {
   created_at: string;
 }
 
-export type Category = 'all' | 'ceramics' | 'bronze' | 'jade' | 'calligraphy' | 'sculpture';
+export interface SessionInfo {
+  id: number;
+  created_at: string;
+  last_seen_at: string;
+  expires_at: string;
+  is_current: boolean;
+}
+
+// 角色与权限
+export type Permission =
+  | 'artifact:create'
+  | 'artifact:update'
+  | 'artifact:delete'
+  | 'user:manage'
+  | 'vocabulary:manage';
+
+export interface UserPermissions {
+  user_id: number;
+  roles: string[];
+  permissions: Permission[];
+}
+
+export interface RoleInfo {
+  id: number;
+  name: string;
+  description: string;
+  permissions: Permission[];
+}
+
+export interface UserWithRoles {
+  id: number;
+  username: string;
+  email: string;
+  roles: string[];
+}
+
+// 类别词表中的代码，如 bronze、ding；'all' 表示不限。筛选时包含下级词条
+export type Category = 'all' | (string & {});
+
+// 受控词表
+export type Vocabulary = 'category' | 'material' | 'collection';
+
+export interface VocabularyTerm {
+  id: number;
+  vocabulary: Vocabulary;
+  // 写入文物对应字段的值
+  code: string;
+  label_zh: string;
+  label_en: string;
+  parent_id: number | null;
+  sort_order: number;
+}
+
+export interface VocabularyTermWithUsage extends VocabularyTerm {
+  // 直接使用该词条的文物数量
+  usage_count: number;
+}
+
+export interface VocabularyTermInput {
+  vocabulary: Vocabulary;
+  code: string;
+  label_zh: string;
+  label_en?: string;
+  parent_id?: number | null;
+  sort_order?: number;
+}
+
+export interface VocabularyMergeResult {
+  target: VocabularyTerm;
+  // 改为使用目标词条的文物数量
+  moved: number;
+}
 
 export interface SearchParams {
   query?: string;
   category?: Category;
   dynasty?: string;
+  material?: string;
+  collection?: string;
+  // 年份范围（公元前为负数），返回年代与之有交集的文物
+  yearFrom?: number;
+  yearTo?: number;
   favoritesOnly?: boolean;
+  limit?: number;
+  cursor?: string;
+  offset?: number;
+  sort?: ArtifactSortField;
+  order?: SortOrder;
+}
+
+export type ArtifactSortField = 'title' | 'dynasty' | 'created_at' | 'updated_at' | 'popularity';
+
+// get_artifacts 返回的分页结果
+export interface ArtifactPage {
+  items: ArtifactWithFavorite[];
+  total: number;
+  next_cursor: string | null;
 }
 
 // 认证相关类型
@@ -68,6 +380,13 @@ export interface CreateArtifactRequest {
   discovery_location: string;
   collection: string;
   category: string;
+  // 经纬度均为空时按发现地点或所在地推断
+  latitude?: number | null;
+  longitude?: number | null;
+  location_uncertainty_m?: number | null;
+  // 起止年份均为空时按朝代和年代推断
+  date_from?: number | null;
+  date_to?: number | null;
 }
 
 export interface UpdateArtifactRequest extends Partial<CreateArtifactRequest> {
@@ -89,11 +408,194 @@ export interface PaginatedResponse<T> {
   totalPages: number;
 }
 
+// 批量导入
+export type ImportFormat = 'csv' | 'json';
+
+export interface ImportRowResult {
+  row: number;
+  accession_number: string;
+  title: string;
+  action: 'create' | 'update';
+  artifact_id: number | null;
+}
+
+export interface ImportRowError {
+  row: number;
+  field: string | null;
+  message: string;
+}
+
+export interface ImportReport {
+  dry_run: boolean;
+  // 存在错误行时不会写入
+  committed: boolean;
+  total: number;
+  created: number;
+  updated: number;
+  rows: ImportRowResult[];
+  errors: ImportRowError[];
+  ignored_columns: string[];
+}
+
+// 导出
+export type ExportFormat = 'csv' | 'jsonl' | 'dublin_core';
+
+export interface ExportRequest {
+  format: ExportFormat;
+  // 与 getArtifacts 相同的筛选和排序条件，不提供时导出全部文物
+  params?: SearchParams;
+  include_images?: boolean;
+}
+
+export interface ExportResult {
+  path: string;
+  count: number;
+  images: number;
+  missing_images: string[];
+}
+
+// IIIF 类型（只声明前端用到的字段）
+export interface IiifTiles {
+  width: number;
+  height: number;
+  scaleFactors: number[];
+}
+
+export interface IiifImageInfo {
+  id: string;
+  width: number;
+  height: number;
+  tiles: IiifTiles[];
+}
+
+export interface IiifImageService {
+  id: string;
+  type: string;
+  profile: string;
+}
+
+export interface IiifCanvas {
+  id: string;
+  width: number;
+  height: number;
+  label?: Record<string, string[]>;
+  items: Array<{
+    items: Array<{
+      body: { id: string; width: number; height: number; service: IiifImageService[] };
+    }>;
+  }>;
+}
+
+export interface IiifManifest {
+  id: string;
+  label: Record<string, string[]>;
+  items: IiifCanvas[];
+}
+
+// 数据库就绪状态
+export type DbStatus =
+  | { state: 'initializing' }
+  | { state: 'ready' }
+  | { state: 'failed'; stage: 'config' | 'connect' | 'migrate' | 'bootstrap'; message: string };
+
+// AI 回答中引用的馆藏文物
+export interface Citation {
+  artifact_id: number;
+  title: string;
+}
+
+// 针对单件文物的预设任务
+export type ArtifactTask = 'explain_for_children' | 'compare_similar' | 'historical_context';
+
+export interface ArtifactQuestionRequest {
+  artifact_id: number;
+  // 只选择预设任务时可不填
+  question?: string;
+  task?: ArtifactTask;
+  conversation_history?: Array<{ role: string; content: string }>;
+}
+
+// AI 助手模型配置
+export type LlmProviderKind = 'simulator' | 'deepseek' | 'openai_compatible';
+
+export interface LlmConfigView {
+  provider: LlmProviderKind;
+  base_url: string | null;
+  model: string | null;
+  temperature: number;
+  max_tokens: number;
+  // 上下文窗口（token），对话历史超出时从最早的消息开始裁剪
+  context_window: number;
+  has_api_key: boolean;
+}
+
+export interface LlmConfigUpdate {
+  provider: LlmProviderKind;
+  base_url: string | null;
+  model: string | null;
+  temperature: number;
+  max_tokens: number;
+  context_window: number;
+  // 不传表示保留原有密钥，空字符串表示清除
+  api_key?: string;
+}
+
+// AI 助手对话
+export interface Conversation {
+  id: number;
+  title: string;
+  message_count: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ConversationMessage {
+  id: number;
+  role: 'user' | 'assistant';
+  content: string;
+  citations: Citation[];
+  // 回复因取消而不完整
+  cancelled: boolean;
+  created_at: string;
+}
+
+export interface ConversationDetail extends Conversation {
+  messages: ConversationMessage[];
+}
+
+// 还未生成任何内容就被取消时本轮不保存，两条消息均为 null
+export interface ConversationTurn {
+  user_message: ConversationMessage | null;
+  assistant_message: ConversationMessage | null;
+  cancelled: boolean;
+}
+
+// 流式对话事件
+export type ChatStreamEvent = { request_id: string } & (
+  | { type: 'delta'; content: string }
+  | { type: 'done'; cancelled: boolean; citations: Citation[] }
+  | { type: 'error'; error: ApiError }
+);
+
 // 错误类型
+export type ApiErrorCode =
+  | 'AUTH_REQUIRED'
+  | 'INVALID_CREDENTIALS'
+  | 'FORBIDDEN'
+  | 'VALIDATION'
+  | 'NOT_FOUND'
+  | 'CONFLICT'
+  | 'UPSTREAM'
+  | 'DATABASE'
+  | 'DATABASE_NOT_READY'
+  | 'IO'
+  | 'INTERNAL';
+
 export interface ApiError {
   message: string;
-  code: string;
-  details?: unknown;
+  code: ApiErrorCode | string;
+  // 校验错误时 field 为第一个出错的字段，errors 为全部字段的错误
+  details?: { field?: string; errors?: Array<{ field: string; message: string }> } | null;
 }
 
 // 表单类型